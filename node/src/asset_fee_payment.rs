@@ -0,0 +1,59 @@
+//! Fee estimates in assets.
+//!
+//! `payment_queryAssetFee` complements `payment_queryInfo`: it returns the fee the sender of an
+//! extrinsic would pay in given asset, none if the asset is not approved for fee payment.
+
+use std::sync::Arc;
+use codec::Decode;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use logion_node_runtime::{opaque::Block, Balance, Hash};
+use pallet_asset_fee_payment_runtime_api::AssetFeePaymentApi as AssetFeePaymentRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Fee estimates in assets.
+#[rpc]
+pub trait AssetFeePaymentApi<BlockHash> {
+	/// Returns the fee of given encoded extrinsic in given asset at given block, the best block by default.
+	#[rpc(name = "payment_queryAssetFee")]
+	fn query_asset_fee(&self, encoded_xt: Bytes, asset_id: u64, at: Option<BlockHash>) -> RpcResult<Option<NumberOrHex>>;
+}
+
+/// Implements `AssetFeePaymentApi` with the `AssetFeePaymentApi` runtime API.
+pub struct AssetFeePayment<C> {
+	client: Arc<C>,
+}
+
+impl<C> AssetFeePayment<C> {
+	pub fn new(client: Arc<C>) -> Self {
+		AssetFeePayment { client }
+	}
+}
+
+impl<C> AssetFeePaymentApi<Hash> for AssetFeePayment<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AssetFeePaymentRuntimeApi<Block, u64, Balance>,
+{
+	fn query_asset_fee(&self, encoded_xt: Bytes, asset_id: u64, at: Option<Hash>) -> RpcResult<Option<NumberOrHex>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let encoded_len = encoded_xt.len() as u32;
+		let uxt: <Block as BlockT>::Extrinsic = Decode::decode(&mut &*encoded_xt)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(1),
+				message: "Unable to decode extrinsic".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+		let fee = self.client.runtime_api().query_asset_fee(&at, uxt, encoded_len, asset_id)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(2),
+				message: "Unable to query asset fee".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+		Ok(fee.map(NumberOrHex::from))
+	}
+}
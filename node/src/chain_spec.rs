@@ -1,6 +1,10 @@
+use std::path::Path;
 use std::str::FromStr;
 
-use sp_core::{Pair, Public, sr25519, ed25519, OpaquePeerId};
+use sp_core::{Pair, Public, sr25519, ed25519, OpaquePeerId, hashing::blake2_256};
+use pallet_logion_loc_genesis::{
+	GenesisFile, GenesisLink, GenesisLoc, GenesisLocType, GenesisMetadataItem, GenesisRequester, GenesisVoidInfo,
+};
 use logion_node_runtime::{
 	opaque::SessionKeys,
 	AccountId,
@@ -9,7 +13,14 @@ use logion_node_runtime::{
 	BalancesConfig,
 	GenesisConfig,
 	GrandpaConfig,
+	IndicesConfig,
+	Hash,
 	LoAuthorityListConfig,
+	LoIdentityConfig,
+	LoNodeAuthorizationConfig,
+	LoProfilesConfig,
+	LocDepositsConfig,
+	LogionLocGenesisConfig,
 	NodeAuthorizationConfig,
 	Signature,
 	SessionConfig,
@@ -18,6 +29,10 @@ use logion_node_runtime::{
 	ValidatorSetConfig,
 	WASM_BINARY
 };
+#[cfg(feature = "dev-fixtures")]
+use codec::Encode;
+#[cfg(feature = "dev-fixtures")]
+use logion_node_runtime::{BalancesCall, BlockNumber, Call, DevFixturesConfig, LGNT, Runtime};
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{Verify, IdentifyAccount};
@@ -59,14 +74,26 @@ pub fn authority_keys_from_seed(s: &str) -> (AccountId, AuraId, GrandpaId) {
 	)
 }
 
-pub fn development_config() -> Result<ChainSpec, String> {
+pub fn development_config(fixtures: bool) -> Result<ChainSpec, String> {
+	ensure_fixtures_available(fixtures)?;
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	let mut endowed_accounts = vec![
+		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		get_account_id_from_seed::<sr25519::Public>("Bob"),
+		get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
+	];
+	if fixtures {
+		endowed_accounts.push(get_account_id_from_seed::<sr25519::Public>("Charlie"));
+		endowed_accounts.push(get_account_id_from_seed::<sr25519::Public>("Dave"));
+		endowed_accounts.push(get_account_id_from_seed::<sr25519::Public>("Eve"));
+	}
 
 	Ok(ChainSpec::from_genesis(
 		// Name
-		"Logion Development",
+		if fixtures { "Logion Development with fixtures" } else { "Logion Development" },
 		// ID
-		"logion_dev",
+		if fixtures { "logion_dev_fixtures" } else { "logion_dev" },
 		ChainType::Development,
 		move || logion_genesis(
 			wasm_binary,
@@ -77,12 +104,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 			// Sudo account
 			get_account_id_from_seed::<sr25519::Public>("Alice"),
 			// Pre-funded accounts
-			vec![
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-			],
+			endowed_accounts.clone(),
 			// Initial authorized nodes
 			vec![
 				(
@@ -95,6 +117,10 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Bob"),
 				get_account_id_from_seed::<sr25519::Public>("Charlie"),
 			],
+			// LOCs imported from another chain
+			if fixtures { fixture_locs() } else { LogionLocGenesisConfig::default() },
+			// Development fixtures
+			fixtures,
 		),
 		// Bootnodes
 		vec![],
@@ -109,14 +135,15 @@ pub fn development_config() -> Result<ChainSpec, String> {
 	))
 }
 
-pub fn local_testnet_config() -> Result<ChainSpec, String> {
+pub fn local_testnet_config(fixtures: bool) -> Result<ChainSpec, String> {
+	ensure_fixtures_available(fixtures)?;
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	Ok(ChainSpec::from_genesis(
 		// Name
-		"Logion Local Testnet",
+		if fixtures { "Logion Local Testnet with fixtures" } else { "Logion Local Testnet" },
 		// ID
-		"logion_local_testnet",
+		if fixtures { "logion_local_testnet_fixtures" } else { "logion_local_testnet" },
 		ChainType::Local,
 		move || logion_genesis(
 			wasm_binary,
@@ -163,6 +190,10 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Bob"),
 				get_account_id_from_seed::<sr25519::Public>("Charlie"),
 			],
+			// LOCs imported from another chain
+			if fixtures { fixture_locs() } else { LogionLocGenesisConfig::default() },
+			// Development fixtures
+			fixtures,
 		),
 		// Bootnodes
 		vec![],
@@ -177,7 +208,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 	))
 }
 
-pub fn mvp_config() -> Result<ChainSpec, String> {
+pub fn mvp_config(locs: LogionLocGenesisConfig) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	const ROOT_PUBLIC_SR25519: &str = "5FUg3QWfipPf8yKv5hMK6wQf8nn6og9BbRNcr3Y8CwUJwTh9";
@@ -247,6 +278,10 @@ pub fn mvp_config() -> Result<ChainSpec, String> {
 			],
 			vec![ // Initial set of Logion Legal Officers
 			],
+			// LOCs imported from another chain
+			locs.clone(),
+			// Development fixtures
+			false,
 		),
 		// Bootnodes
 		vec![],
@@ -303,6 +338,10 @@ pub fn test_testnet_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
 				get_account_id_from_seed::<sr25519::Public>("Bob"),
 			],
+			// LOCs imported from another chain
+			LogionLocGenesisConfig::default(),
+			// Development fixtures
+			false,
 		),
 		// Bootnodes
 		vec![],
@@ -317,7 +356,7 @@ pub fn test_testnet_config() -> Result<ChainSpec, String> {
 	))
 }
 
-const INITIAL_BALANCE: Balance = 100_000_000_000_000_000_000_000;
+pub const INITIAL_BALANCE: Balance = 100_000_000_000_000_000_000_000;
 
 /// Configure initial storage state for FRAME modules.
 fn logion_genesis(
@@ -327,6 +366,8 @@ fn logion_genesis(
 	endowed_accounts: Vec<AccountId>,
 	initial_authorized_nodes: Vec<(OpaquePeerId, AccountId)>,
 	legal_officers: Vec<AccountId>,
+	locs: LogionLocGenesisConfig,
+	fixtures: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
 		frame_system: Some(SystemConfig {
@@ -336,7 +377,12 @@ fn logion_genesis(
 		}),
 		pallet_balances: Some(BalancesConfig {
 			// Configure endowed accounts with initial balance.
-			balances: endowed_accounts.iter().cloned().map(|k|(k, INITIAL_BALANCE)).collect(),
+			balances: endowed_accounts.iter().cloned().map(|k|(k, INITIAL_BALANCE))
+				.chain(fixture_balances(fixtures))
+				.collect(),
+		}),
+		pallet_indices: Some(IndicesConfig {
+			indices: vec![],
 		}),
 		pallet_validator_set: Some(ValidatorSetConfig {
 			validators: initial_authorities.iter().map(|x| x.0.clone()).collect::<Vec<_>>(),
@@ -360,9 +406,167 @@ fn logion_genesis(
 		pallet_node_authorization: Some(NodeAuthorizationConfig {
 			nodes: initial_authorized_nodes.iter().map(|x| (x.0.clone(), x.1.clone())).collect(),
 		}),
+		pallet_lo_node_authorization: Some(LoNodeAuthorizationConfig {}),
 		pallet_lo_authority_list: Some(LoAuthorityListConfig {
 			legal_officers: legal_officers.iter().map(|x| x.clone()).collect(),
-		})
+		}),
+		pallet_logion_loc_genesis: Some(locs),
+		#[cfg(feature = "dev-fixtures")]
+		pallet_dev_fixtures: Some(if fixtures { dev_fixtures() } else { DevFixturesConfig::default() }),
+		pallet_lo_identity: Some(LoIdentityConfig {
+			registrars: legal_officers.clone(),
+		}),
+		pallet_lo_profiles: Some(LoProfilesConfig {
+			profiles: legal_officers.iter().map(|x| (x.clone(), Default::default())).collect(),
+		}),
+		pallet_loc_deposits: Some(LocDepositsConfig {}),
+	}
+}
+
+/// LOCs of development chains with fixtures: identity, transaction and collection LOCs owned by
+/// Alice, Bob and Charlie and requested by Dave and Eve.
+fn fixture_locs() -> LogionLocGenesisConfig {
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
+	let charlie = get_account_id_from_seed::<sr25519::Public>("Charlie");
+	let dave = get_account_id_from_seed::<sr25519::Public>("Dave");
+	let eve = get_account_id_from_seed::<sr25519::Public>("Eve");
+
+	let loc = |owner: &AccountId, requester, loc_type| GenesisLoc {
+		owner: owner.clone(),
+		requester,
+		metadata: vec![],
+		files: vec![],
+		closed: true,
+		loc_type,
+		links: vec![],
+		void_info: None,
+		replacer_of: None,
+		collection_last_block_submission: None,
+		collection_max_size: None,
+	};
+	let metadata = |name: &str, value: &str, submitter: &AccountId| GenesisMetadataItem {
+		name: name.as_bytes().to_vec(),
+		value: value.as_bytes().to_vec(),
+		submitter: submitter.clone(),
+	};
+
+	LogionLocGenesisConfig {
+		locs: vec![
+			// Dave's identity, verified by Alice and Bob
+			(1, GenesisLoc {
+				metadata: vec![
+					metadata("firstName", "Dave", &alice),
+					metadata("lastName", "Fixture", &alice),
+				],
+				..loc(&alice, GenesisRequester::Account(dave.clone()), GenesisLocType::Identity)
+			}),
+			(2, GenesisLoc {
+				metadata: vec![
+					metadata("firstName", "Dave", &bob),
+					metadata("lastName", "Fixture", &bob),
+				],
+				..loc(&bob, GenesisRequester::Account(dave.clone()), GenesisLocType::Identity)
+			}),
+			// A closed transaction with a file and a link to the identity of its requester
+			(3, GenesisLoc {
+				metadata: vec![metadata("purpose", "Sale of a painting", &dave)],
+				files: vec![GenesisFile {
+					hash: Hash::from(blake2_256(b"Painting sale agreement")),
+					nature: b"Agreement".to_vec(),
+					submitter: dave.clone(),
+				}],
+				links: vec![GenesisLink { id: 1, nature: b"Seller identity".to_vec() }],
+				..loc(&alice, GenesisRequester::Account(dave.clone()), GenesisLocType::Transaction)
+			}),
+			// A void transaction and its replacer
+			(4, GenesisLoc {
+				void_info: Some(GenesisVoidInfo { replacer: Some(5) }),
+				..loc(&bob, GenesisRequester::Account(dave.clone()), GenesisLocType::Transaction)
+			}),
+			(5, GenesisLoc {
+				metadata: vec![metadata("purpose", "Corrected lease contract", &bob)],
+				replacer_of: Some(4),
+				..loc(&bob, GenesisRequester::Account(dave.clone()), GenesisLocType::Transaction)
+			}),
+			// A collection with items
+			(6, GenesisLoc {
+				collection_max_size: Some(100),
+				..loc(&charlie, GenesisRequester::Account(eve.clone()), GenesisLocType::Collection)
+			}),
+			// An identity not linked to an account and a transaction it requested
+			(7, GenesisLoc {
+				metadata: vec![metadata("companyName", "Fixture Ltd", &charlie)],
+				..loc(&charlie, GenesisRequester::None, GenesisLocType::Identity)
+			}),
+			(8, GenesisLoc {
+				closed: false,
+				..loc(&charlie, GenesisRequester::Loc(7), GenesisLocType::Transaction)
+			}),
+		],
+		collection_items: vec![
+			(6, Hash::from(blake2_256(b"Item 1")), b"First item".to_vec()),
+			(6, Hash::from(blake2_256(b"Item 2")), b"Second item".to_vec()),
+		],
+	}
+}
+
+/// Chains with fixtures need the development pallets, which are only part of the runtime when the
+/// node is built with feature `dev-fixtures`.
+fn ensure_fixtures_available(fixtures: bool) -> Result<(), String> {
+	if fixtures && !cfg!(feature = "dev-fixtures") {
+		Err("Chains with fixtures need development pallets, build with --features dev-fixtures".into())
+	} else {
+		Ok(())
+	}
+}
+
+/// Fixtures of development chains: a transfer from Dave's vault waiting for approval of Alice
+/// or Bob, and Dave's account recoverable by Alice and Bob.
+#[cfg(feature = "dev-fixtures")]
+fn dev_fixtures() -> DevFixturesConfig {
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
+	let dave = get_account_id_from_seed::<sr25519::Public>("Dave");
+	let eve = get_account_id_from_seed::<sr25519::Public>("Eve");
+
+	let transfer = Call::Balances(BalancesCall::transfer(eve.into(), LGNT));
+	let delay_period: BlockNumber = 10;
+	DevFixturesConfig {
+		vault_calls: vec![(dave.clone(), vec![alice.clone(), bob.clone()], blake2_256(&transfer.encode()))],
+		recoveries: vec![(dave, vec![alice, bob], 2, delay_period)],
+	}
+}
+
+/// Balances of the vaults of `dev_fixtures`, so that their transfers can be executed.
+#[cfg(feature = "dev-fixtures")]
+fn fixture_balances(fixtures: bool) -> Vec<(AccountId, Balance)> {
+	if !fixtures {
+		return Vec::new();
+	}
+	dev_fixtures().vault_calls.iter()
+		.map(|(requester, legal_officers, _)| (
+			pallet_dev_fixtures::Module::<Runtime>::vault_account(requester, legal_officers),
+			INITIAL_BALANCE,
+		))
+		.collect()
+}
+
+#[cfg(not(feature = "dev-fixtures"))]
+fn fixture_balances(_fixtures: bool) -> Vec<(AccountId, Balance)> {
+	Vec::new()
+}
+
+/// Loads LOCs exported with `export-locs`, none if no dump is given.
+pub fn load_loc_dump(path: Option<&Path>) -> Result<LogionLocGenesisConfig, String> {
+	match path {
+		Some(path) => {
+			let file = std::fs::File::open(path)
+				.map_err(|e| format!("Unable to open LOC dump {}: {}", path.display(), e))?;
+			serde_json::from_reader(file)
+				.map_err(|e| format!("Invalid LOC dump {}: {}", path.display(), e))
+		},
+		None => Ok(LogionLocGenesisConfig::default()),
 	}
 }
 
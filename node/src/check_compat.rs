@@ -0,0 +1,463 @@
+//! `check-compat` subcommand: checks that a new runtime is backward compatible with an old one.
+//!
+//! The rules of `docs/storage_backward_compatibility.md` are enforced on the metadata of both
+//! runtimes:
+//! * the type of a storage item must not change unless the pallet carries a migration, i.e. the
+//!   default value of its `PalletStorageVersion` changed;
+//! * pallets, calls and events must not be removed or reordered unless `transaction_version`
+//!   was bumped;
+//! * when custom types are given, typed enums only get new variants at the end, and the fields of
+//!   structs do not change unless all storage items using them are migrated.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use codec::Decode;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed, StorageEntryType};
+use sc_executor::{WasmExecutionMethod, WasmExecutor};
+use serde_json::{Map, Value};
+use sp_core::traits::{CallInWasm, MissingHostFunctions};
+use sp_state_machine::BasicExternalities;
+use sp_version::RuntimeVersion;
+use sp_wasm_interface::HostFunctions;
+use structopt::StructOpt;
+use crate::check_types::{decoded, decoded_array};
+
+/// The storage item holding the storage version of a pallet, see
+/// `docs/storage_backward_compatibility.md`.
+const STORAGE_VERSION_ITEM: &str = "PalletStorageVersion";
+
+/// The `check-compat` command.
+#[derive(Debug, StructOpt)]
+pub struct CheckCompatCmd {
+	/// The runtime currently running on chain.
+	#[structopt(long, parse(from_os_str))]
+	pub old: PathBuf,
+
+	/// The runtime to upgrade to.
+	#[structopt(long, parse(from_os_str))]
+	pub new: PathBuf,
+
+	/// The custom types of the old runtime.
+	#[structopt(long, parse(from_os_str), requires = "new-types")]
+	pub old_types: Option<PathBuf>,
+
+	/// The custom types of the new runtime.
+	#[structopt(long, parse(from_os_str), requires = "old-types")]
+	pub new_types: Option<PathBuf>,
+}
+
+/// A pallet as seen by the checker.
+struct Pallet {
+	index: u8,
+	storage: BTreeMap<String, (String, Vec<u8>)>,
+	calls: Vec<String>,
+	events: Vec<String>,
+}
+
+impl CheckCompatCmd {
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let (old_version, old_pallets) = load_runtime(&self.old)?;
+		let (new_version, new_pallets) = load_runtime(&self.new)?;
+		let transaction_version_bumped = new_version.transaction_version > old_version.transaction_version;
+
+		let mut errors = Vec::new();
+		let mut warnings = Vec::new();
+
+		let changed_types = match (&self.old_types, &self.new_types) {
+			(Some(old_types), Some(new_types)) =>
+				check_types(&mut errors, &load_types(old_types)?, &load_types(new_types)?),
+			_ => BTreeMap::new(),
+		};
+		for (name, change) in changed_types.iter() {
+			warnings.push(format!("custom type {} {}", name, change));
+		}
+
+		if new_version.spec_version <= old_version.spec_version {
+			errors.push(format!("spec_version must be bumped (old {}, new {})",
+				old_version.spec_version, new_version.spec_version));
+		}
+
+		for (name, old) in old_pallets.iter() {
+			let new = match new_pallets.get(name) {
+				Some(new) => new,
+				None => {
+					interface_change(&mut errors, &mut warnings, transaction_version_bumped,
+						format!("pallet {} was removed", name));
+					continue;
+				},
+			};
+			if new.index != old.index {
+				interface_change(&mut errors, &mut warnings, transaction_version_bumped,
+					format!("pallet {} moved from index {} to {}", name, old.index, new.index));
+			}
+
+			let migrated = old.storage.get(STORAGE_VERSION_ITEM).map(|item| &item.1)
+				!= new.storage.get(STORAGE_VERSION_ITEM).map(|item| &item.1);
+			for (item, (old_type, _)) in old.storage.iter() {
+				let message = match new.storage.get(item) {
+					Some((new_type, _)) if new_type != old_type =>
+						format!("type of storage item {}::{} changed from {} to {}", name, item, old_type, new_type),
+					Some((new_type, _)) => match type_names(new_type).find(|ty| changed_types.contains_key(*ty)) {
+						Some(ty) => format!("type of storage item {}::{} changed: custom type {} {}",
+							name, item, ty, changed_types[ty]),
+						None => continue,
+					},
+					None => {
+						warnings.push(format!("storage item {}::{} was removed", name, item));
+						continue;
+					},
+				};
+				if migrated { warnings.push(message) } else { errors.push(message + " without migration") }
+			}
+
+			check_enum(&mut errors, &mut warnings, transaction_version_bumped,
+				&format!("call of pallet {}", name), &old.calls, &new.calls);
+			check_enum(&mut errors, &mut warnings, transaction_version_bumped,
+				&format!("event of pallet {}", name), &old.events, &new.events);
+		}
+
+		for warning in warnings.iter() {
+			println!("WARNING: {}", warning);
+		}
+		for error in errors.iter() {
+			println!("ERROR: {}", error);
+		}
+		if errors.is_empty() {
+			println!("Runtime {} is compatible with runtime {}", new_version.spec_version, old_version.spec_version);
+			Ok(())
+		} else {
+			Err(format!("{} incompatibilities found", errors.len()).into())
+		}
+	}
+}
+
+/// A change of the extrinsics interface, acceptable only if `transaction_version` was bumped.
+fn interface_change(errors: &mut Vec<String>, warnings: &mut Vec<String>, transaction_version_bumped: bool, message: String) {
+	if transaction_version_bumped {
+		warnings.push(message);
+	} else {
+		errors.push(message + " without transaction_version bump");
+	}
+}
+
+/// Calls and events are encoded with their index: they may only be appended.
+fn check_enum(
+	errors: &mut Vec<String>,
+	warnings: &mut Vec<String>,
+	transaction_version_bumped: bool,
+	what: &str,
+	old: &Vec<String>,
+	new: &Vec<String>,
+) {
+	for (index, variant) in old.iter().enumerate() {
+		match new.iter().position(|candidate| candidate == variant) {
+			None => interface_change(errors, warnings, transaction_version_bumped,
+				format!("{} {} was removed", what, variant)),
+			Some(new_index) if new_index != index => interface_change(errors, warnings, transaction_version_bumped,
+				format!("{} {} moved from index {} to {}", what, variant, index, new_index)),
+			_ => (),
+		}
+	}
+}
+
+/// Typed enums of custom types must only get new variants at the end. Returns the other custom
+/// types whose encoding changed, i.e. aliases, struct fields (names, order and types) and types of
+/// existing enum variants, directly or through a type they contain, along with the change.
+fn check_types(errors: &mut Vec<String>, old: &Map<String, Value>, new: &Map<String, Value>) -> BTreeMap<String, String> {
+	let mut changed = BTreeMap::new();
+	for (name, old_definition) in old.iter() {
+		let new_definition = match new.get(name) {
+			Some(new_definition) => new_definition,
+			None => {
+				changed.insert(name.clone(), "was removed".to_string());
+				continue;
+			},
+		};
+		let old_variants = enum_variants(old_definition);
+		let new_variants = enum_variants(new_definition);
+		if !old_variants.is_empty() {
+			if !new_variants.starts_with(&old_variants) {
+				errors.push(format!("variants of enum {} changed from {:?} to {:?}, only appending is allowed",
+					name, names(&old_variants), names(&new_variants)));
+			} else if let Some(((variant, old_type), (_, new_type))) = old_variants.iter().zip(new_variants.iter())
+				.find(|((_, old_type), (_, new_type))| old_type != new_type) {
+				changed.insert(name.clone(), format!("variant {} changed from {} to {}", variant, old_type, new_type));
+			}
+			continue;
+		}
+		let old_fields = struct_fields(old_definition);
+		let new_fields = struct_fields(new_definition);
+		if old_fields != new_fields {
+			changed.insert(name.clone(), format!("changed from {:?} to {:?}", old_fields, new_fields));
+		}
+	}
+
+	loop {
+		let containing: Vec<(String, String)> = new.iter()
+			.filter(|(name, _)| !changed.contains_key(*name))
+			.filter_map(|(name, definition)| referenced_types(definition).into_iter()
+				.flat_map(|ty| type_names(&ty).map(str::to_string).collect::<Vec<String>>())
+				.find(|ty| changed.contains_key(ty))
+				.map(|ty| (name.clone(), format!("contains changed type {}", ty))))
+			.collect();
+		if containing.is_empty() {
+			return changed;
+		}
+		changed.extend(containing);
+	}
+}
+
+/// The variants of an enum definition and their types, `Null` for variants without data.
+fn enum_variants(definition: &Value) -> Vec<(String, String)> {
+	match definition.get("_enum") {
+		Some(Value::Array(variants)) => variants.iter()
+			.map(|variant| (variant.as_str().unwrap_or_default().to_string(), "Null".to_string()))
+			.collect(),
+		Some(Value::Object(variants)) => variants.iter()
+			.map(|(variant, ty)| (variant.clone(), ty.as_str().unwrap_or_default().to_string()))
+			.collect(),
+		_ => Vec::new(),
+	}
+}
+
+fn names(variants: &Vec<(String, String)>) -> Vec<&String> {
+	variants.iter().map(|(name, _)| name).collect()
+}
+
+/// The fields of a struct definition and their types, in order, or the aliased type.
+fn struct_fields(definition: &Value) -> Vec<(String, String)> {
+	match definition {
+		Value::Object(fields) => fields.iter()
+			.map(|(field, ty)| (field.clone(), ty.as_str().map(str::to_string).unwrap_or_else(|| ty.to_string())))
+			.collect(),
+		Value::String(ty) => vec![(String::new(), ty.clone())],
+		_ => Vec::new(),
+	}
+}
+
+/// The types used by a definition.
+fn referenced_types(definition: &Value) -> Vec<String> {
+	match definition.get("_enum") {
+		Some(_) => enum_variants(definition).into_iter().map(|(_, ty)| ty).collect(),
+		None => struct_fields(definition).into_iter().map(|(_, ty)| ty).collect(),
+	}
+}
+
+/// The type names found in a type expression, e.g. `Vec` and `LocLink` in `Vec<LocLink>`.
+fn type_names(ty: &str) -> impl Iterator<Item = &str> {
+	ty.split(|c: char| !(c.is_alphanumeric() || c == '_')).filter(|name| !name.is_empty())
+}
+
+fn load_types(path: &PathBuf) -> sc_cli::Result<Map<String, Value>> {
+	let content = std::fs::read_to_string(path)?;
+	serde_json::from_str(&content)
+		.map_err(|e| format!("Invalid types file {}: {}", path.display(), e).into())
+}
+
+fn load_runtime(path: &PathBuf) -> sc_cli::Result<(RuntimeVersion, BTreeMap<String, Pallet>)> {
+	let code = std::fs::read(path)?;
+	let version: RuntimeVersion = Decode::decode(&mut &call_runtime(&code, "Core_version")?[..])
+		.map_err(|e| format!("Unable to decode version of {}: {}", path.display(), e))?;
+	let metadata: Vec<u8> = Decode::decode(&mut &call_runtime(&code, "Metadata_metadata")?[..])
+		.map_err(|e| format!("Unable to decode metadata of {}: {}", path.display(), e))?;
+	let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+		.map_err(|e| format!("Unable to decode metadata of {}: {}", path.display(), e))?;
+	Ok((version, pallets(metadata.1)?))
+}
+
+fn call_runtime(code: &[u8], method: &str) -> sc_cli::Result<Vec<u8>> {
+	let executor = WasmExecutor::new(
+		WasmExecutionMethod::Interpreted,
+		None,
+		sp_io::SubstrateHostFunctions::host_functions(),
+		1,
+		None,
+	);
+	let mut ext = BasicExternalities::default();
+	executor.call_in_wasm(code, None, method, &[], &mut ext, MissingHostFunctions::Allow)
+		.map_err(|e| format!("Unable to call {}: {}", method, e).into())
+}
+
+fn pallets(metadata: RuntimeMetadata) -> sc_cli::Result<BTreeMap<String, Pallet>> {
+	let modules = match metadata {
+		RuntimeMetadata::V12(metadata) => decoded_array(&metadata.modules),
+		_ => return Err("Unsupported metadata version".into()),
+	};
+	Ok(modules.iter()
+		.map(|module| {
+			let storage = module.storage.as_ref().map(decoded)
+				.map(|storage| decoded_array(&storage.entries))
+				.unwrap_or_default()
+				.into_iter()
+				.map(|entry| {
+					let ty = match &entry.ty {
+						StorageEntryType::Plain(value) => decoded(value),
+						StorageEntryType::Map { key, value, .. } =>
+							format!("{} => {}", decoded(key), decoded(value)),
+						StorageEntryType::DoubleMap { key1, key2, value, .. } =>
+							format!("({}, {}) => {}", decoded(key1), decoded(key2), decoded(value)),
+					};
+					(decoded(&entry.name), (ty, decoded(&entry.default)))
+				})
+				.collect();
+			let calls = module.calls.as_ref().map(decoded_array).unwrap_or_default()
+				.iter()
+				.map(|call| decoded(&call.name))
+				.collect();
+			let events = module.event.as_ref().map(decoded_array).unwrap_or_default()
+				.iter()
+				.map(|event| decoded(&event.name))
+				.collect();
+			(decoded(&module.name), Pallet { index: module.index, storage, calls, events })
+		})
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	fn variants(names: &[&str]) -> Vec<String> {
+		names.iter().map(|name| name.to_string()).collect()
+	}
+
+	fn types(value: Value) -> Map<String, Value> {
+		match value {
+			Value::Object(types) => types,
+			_ => panic!("types must be an object"),
+		}
+	}
+
+	fn check_enum_with(transaction_version_bumped: bool, old: &[&str], new: &[&str]) -> (Vec<String>, Vec<String>) {
+		let mut errors = Vec::new();
+		let mut warnings = Vec::new();
+		check_enum(&mut errors, &mut warnings, transaction_version_bumped, "call", &variants(old), &variants(new));
+		(errors, warnings)
+	}
+
+	fn check_types_with(old: Value, new: Value) -> (Vec<String>, BTreeMap<String, String>) {
+		let mut errors = Vec::new();
+		let changed = check_types(&mut errors, &types(old), &types(new));
+		(errors, changed)
+	}
+
+	#[test]
+	fn it_accepts_appended_variants() {
+		let (errors, warnings) = check_enum_with(false, &["a", "b"], &["a", "b", "c"]);
+		assert!(errors.is_empty());
+		assert!(warnings.is_empty());
+	}
+
+	#[test]
+	fn it_rejects_removed_variant_without_bump() {
+		let (errors, _) = check_enum_with(false, &["a", "b"], &["a"]);
+		assert_eq!(errors, vec!["call b was removed without transaction_version bump".to_string()]);
+	}
+
+	#[test]
+	fn it_rejects_reordered_variants_without_bump() {
+		let (errors, _) = check_enum_with(false, &["a", "b"], &["b", "a"]);
+		assert_eq!(errors.len(), 2);
+	}
+
+	#[test]
+	fn it_warns_about_reordered_variants_with_bump() {
+		let (errors, warnings) = check_enum_with(true, &["a", "b"], &["b", "a"]);
+		assert!(errors.is_empty());
+		assert_eq!(warnings.len(), 2);
+	}
+
+	#[test]
+	fn it_accepts_unchanged_types() {
+		let definitions = json!({
+			"LocId": "u128",
+			"LocType": { "_enum": ["Transaction", "Identity"] },
+			"LocLink": { "id": "LocId", "nature": "Vec<u8>" }
+		});
+		let (errors, changed) = check_types_with(definitions.clone(), definitions);
+		assert!(errors.is_empty());
+		assert!(changed.is_empty());
+	}
+
+	#[test]
+	fn it_accepts_appended_enum_variants() {
+		let (errors, changed) = check_types_with(
+			json!({ "LocType": { "_enum": ["Transaction", "Identity"] } }),
+			json!({ "LocType": { "_enum": ["Transaction", "Identity", "Collection"] } }),
+		);
+		assert!(errors.is_empty());
+		assert!(changed.is_empty());
+	}
+
+	#[test]
+	fn it_rejects_inserted_enum_variant() {
+		let (errors, _) = check_types_with(
+			json!({ "LocType": { "_enum": ["Transaction", "Identity"] } }),
+			json!({ "LocType": { "_enum": ["Transaction", "Collection", "Identity"] } }),
+		);
+		assert_eq!(errors.len(), 1);
+	}
+
+	#[test]
+	fn it_detects_changed_enum_variant_type() {
+		let (errors, changed) = check_types_with(
+			json!({ "Requester": { "_enum": { "None": "Null", "Account": "AccountId" } } }),
+			json!({ "Requester": { "_enum": { "None": "Null", "Account": "LocId" } } }),
+		);
+		assert!(errors.is_empty());
+		assert!(changed.contains_key("Requester"));
+	}
+
+	#[test]
+	fn it_detects_added_struct_field() {
+		let (_, changed) = check_types_with(
+			json!({ "LocLink": { "id": "LocId", "nature": "Vec<u8>" } }),
+			json!({ "LocLink": { "id": "LocId", "nature": "Vec<u8>", "submitter": "AccountId" } }),
+		);
+		assert!(changed.contains_key("LocLink"));
+	}
+
+	#[test]
+	fn it_detects_reordered_struct_fields() {
+		let (_, changed) = check_types_with(
+			json!({ "LocLink": { "id": "LocId", "nature": "Vec<u8>" } }),
+			json!({ "LocLink": { "nature": "Vec<u8>", "id": "LocId" } }),
+		);
+		assert!(changed.contains_key("LocLink"));
+	}
+
+	#[test]
+	fn it_detects_changed_struct_field_type() {
+		let (_, changed) = check_types_with(
+			json!({ "LocLink": { "id": "LocId", "nature": "Vec<u8>" } }),
+			json!({ "LocLink": { "id": "u64", "nature": "Vec<u8>" } }),
+		);
+		assert!(changed.contains_key("LocLink"));
+	}
+
+	#[test]
+	fn it_detects_changed_alias_and_containing_types() {
+		let (_, changed) = check_types_with(
+			json!({
+				"LocId": "u128",
+				"LocLink": { "id": "LocId", "nature": "Vec<u8>" },
+				"LegalOfficerCase": { "links": "Vec<LocLink>" },
+				"MetadataItem": { "name": "Vec<u8>" }
+			}),
+			json!({
+				"LocId": "u64",
+				"LocLink": { "id": "LocId", "nature": "Vec<u8>" },
+				"LegalOfficerCase": { "links": "Vec<LocLink>" },
+				"MetadataItem": { "name": "Vec<u8>" }
+			}),
+		);
+		assert_eq!(changed.keys().collect::<Vec<&String>>(), vec!["LegalOfficerCase", "LocId", "LocLink"]);
+	}
+
+	#[test]
+	fn it_extracts_type_names() {
+		assert_eq!(type_names("Option<LocVoidInfo<LocId>>").collect::<Vec<&str>>(), vec!["Option", "LocVoidInfo", "LocId"]);
+		assert_eq!(type_names("(T::AccountId, u32) => Vec<u8>").collect::<Vec<&str>>(), vec!["T", "AccountId", "u32", "Vec", "u8"]);
+	}
+}
@@ -0,0 +1,265 @@
+//! `check-spec` subcommand: rejects chain specs which are unsafe for a live network.
+//!
+//! The genesis storage of the chain spec is inspected for:
+//! * accounts and session keys derived from well-known development seeds (`//Alice`, ...);
+//! * well-known nodes using development node keys, or the same peer ID more than once, in the
+//!   genesis configuration or in the raw storage;
+//! * validators which do not own a well-known node;
+//! * legal officers without balance;
+//! * a sudo key which is also a validator key.
+//!
+//! The same checks are run when a node starts a `Live` chain.
+
+use std::collections::{BTreeSet, HashMap};
+use codec::Decode;
+use logion_node_runtime::{opaque::SessionKeys, AccountId, Balance, Index};
+use sc_cli::{CliConfiguration, SharedParams};
+use sc_service::ChainSpec;
+use serde_json::Value;
+use sp_core::{ed25519, hashing::twox_128, sr25519, storage::Storage, Public};
+use structopt::StructOpt;
+use crate::chain_spec::get_from_seed;
+
+/// Seeds of development accounts and keys.
+const DEV_SEEDS: &[&str] = &["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+
+/// Peer IDs of development node keys.
+const DEV_PEER_IDS: &[(&str, &str)] = &[
+	("Alice", "12D3KooWBmAwcd4PJNJvfV89HwE48nwkRmAgo8Vy3uQEyNNHBox2"),
+	("Bob", "12D3KooWQYV9dGMFoRzNStwpXztXaBUjtPqi6aU76ZgUriHhKust"),
+	("Charlie", "12D3KooWJvyP3VJYymTqG7eH4PM5rN4T2agk5cdNCfNymAqwqcvZ"),
+];
+
+/// Length of `Twox64Concat` and `Blake2_128Concat` hash prefixes.
+const TWOX_64_LENGTH: usize = 8;
+const BLAKE2_128_LENGTH: usize = 16;
+
+type AccountInfo = frame_system::AccountInfo<Index, pallet_balances::AccountData<Balance>>;
+
+/// The `check-spec` command.
+#[derive(Debug, StructOpt)]
+pub struct CheckSpecCmd {
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+}
+
+impl CheckSpecCmd {
+	pub fn run(&self, chain_spec: Box<dyn ChainSpec>) -> sc_cli::Result<()> {
+		let problems = check(&*chain_spec)?;
+		for problem in problems.iter() {
+			println!("ERROR: {}", problem);
+		}
+		if problems.is_empty() {
+			println!("Chain spec {} is safe", chain_spec.id());
+			Ok(())
+		} else {
+			Err(format!("{} problems found in chain spec {}", problems.len(), chain_spec.id()).into())
+		}
+	}
+}
+
+impl CliConfiguration for CheckSpecCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+}
+
+/// Refuses to start a live chain with an unsafe chain spec unless `allow_unsafe` is set.
+pub fn enforce(chain_spec: &dyn ChainSpec, allow_unsafe: bool) -> sc_cli::Result<()> {
+	let problems = check(chain_spec)?;
+	if problems.is_empty() {
+		return Ok(());
+	}
+	for problem in problems.iter() {
+		eprintln!("Unsafe chain spec: {}", problem);
+	}
+	if allow_unsafe {
+		Ok(())
+	} else {
+		Err(sc_cli::Error::Input(format!(
+			"Chain spec {} is unsafe for a live chain, run `logion-node check-spec` for details \
+			or start with `--allow-unsafe-spec`",
+			chain_spec.id(),
+		)))
+	}
+}
+
+/// Returns the problems found in given chain spec.
+pub fn check(chain_spec: &dyn ChainSpec) -> sc_cli::Result<Vec<String>> {
+	let storage = GenesisStorage(chain_spec.as_storage_builder().build_storage()?);
+	let dev_keys = dev_keys();
+	let dev_key = |key: &[u8]| dev_keys.get(key).cloned();
+	let mut problems = Vec::new();
+
+	let sudo: Option<AccountId> = storage.value(b"Sudo", b"Key");
+	let validators: Vec<AccountId> = storage.value(b"ValidatorSet", b"Validators").unwrap_or_default();
+	let session_keys: Vec<(AccountId, SessionKeys)> = storage.map(b"Session", b"NextKeys", TWOX_64_LENGTH);
+	let legal_officers: Vec<(AccountId, ())> = storage.map(b"LoAuthorityList", b"LegalOfficerSet", BLAKE2_128_LENGTH);
+	// Decoded as a list, a set would drop the duplicates of a raw chain spec
+	let well_known_node_list: Vec<Vec<u8>> = storage.value(b"NodeAuthorization", b"WellKnownNodes").unwrap_or_default();
+	let well_known_nodes: BTreeSet<Vec<u8>> = well_known_node_list.iter().cloned().collect();
+	let node_owners: Vec<(Vec<u8>, AccountId)> = storage.map(b"NodeAuthorization", b"Owners", BLAKE2_128_LENGTH);
+
+	if let Some(seed) = sudo.as_ref().and_then(|sudo| dev_key(raw(sudo))) {
+		problems.push(format!("sudo key is derived from development seed {}", seed));
+	}
+	for validator in validators.iter() {
+		if let Some(seed) = dev_key(raw(validator)) {
+			problems.push(format!("validator {} is derived from development seed {}", validator, seed));
+		}
+	}
+	for (validator, keys) in session_keys.iter() {
+		if let Some(seed) = dev_key(&keys.aura.to_raw_vec()) {
+			problems.push(format!("Aura key of {} is derived from development seed {}", validator, seed));
+		}
+		if let Some(seed) = dev_key(&keys.grandpa.to_raw_vec()) {
+			problems.push(format!("GRANDPA key of {} is derived from development seed {}", validator, seed));
+		}
+	}
+	for (legal_officer, _) in legal_officers.iter() {
+		if let Some(seed) = dev_key(raw(legal_officer)) {
+			problems.push(format!("legal officer {} is derived from development seed {}", legal_officer, seed));
+		}
+		let balance = storage.account(legal_officer).map(|info| info.data.free).unwrap_or_default();
+		if balance == 0 {
+			problems.push(format!("legal officer {} has no balance", legal_officer));
+		}
+	}
+
+	for (name, peer_id) in DEV_PEER_IDS.iter() {
+		let peer_id = bs58::decode(peer_id).into_vec().expect("static values are valid; qed");
+		if well_known_nodes.contains(&peer_id) {
+			problems.push(format!("well-known node {} uses the development node key of {}", bs58::encode(&peer_id).into_string(), name));
+		}
+	}
+	for peer_id in duplicate_peer_ids(chain_spec, &well_known_node_list) {
+		problems.push(format!("peer ID {} is declared more than once", peer_id));
+	}
+	for validator in validators.iter() {
+		if !node_owners.iter().any(|(_, owner)| owner == validator) {
+			problems.push(format!("validator {} does not own a well-known node", validator));
+		}
+	}
+
+	if let Some(sudo) = sudo {
+		let validator_keys: Vec<Vec<u8>> = validators.iter().map(|validator| raw(validator).to_vec())
+			.chain(session_keys.iter().map(|(_, keys)| keys.aura.to_raw_vec()))
+			.chain(session_keys.iter().map(|(_, keys)| keys.grandpa.to_raw_vec()))
+			.collect();
+		if validator_keys.iter().any(|key| &key[..] == raw(&sudo)) {
+			problems.push(format!("sudo key {} is also a validator key", sudo));
+		}
+	}
+
+	Ok(problems)
+}
+
+/// Public keys derived from development seeds and the name of the seed.
+fn dev_keys() -> HashMap<Vec<u8>, String> {
+	let mut keys = HashMap::new();
+	for seed in DEV_SEEDS.iter() {
+		for seed in [seed.to_string(), format!("{}//stash", seed)].iter() {
+			keys.insert(get_from_seed::<sr25519::Public>(seed).to_raw_vec(), seed.clone());
+			keys.insert(get_from_seed::<ed25519::Public>(seed).to_raw_vec(), seed.clone());
+		}
+	}
+	keys
+}
+
+/// Peer IDs declared more than once. Building the genesis storage of well-known nodes loses
+/// duplicates, they are looked up in the genesis configuration when the chain spec is not raw and
+/// in the stored list when it is raw.
+fn duplicate_peer_ids(chain_spec: &dyn ChainSpec, stored: &[Vec<u8>]) -> Vec<String> {
+	let configured: Vec<Vec<u8>> = chain_spec.as_json(false).ok()
+		.and_then(|json| serde_json::from_str::<Value>(&json).ok())
+		.and_then(|json| json.pointer("/genesis/runtime/palletNodeAuthorization/nodes").and_then(Value::as_array).cloned())
+		.unwrap_or_default()
+		.iter()
+		.filter_map(|node| node.get(0))
+		.filter_map(|peer_id| serde_json::from_value(peer_id.clone()).ok())
+		.collect();
+	let mut duplicates = duplicates_in(&configured);
+	duplicates.extend(duplicates_in(stored));
+	duplicates.into_iter().collect()
+}
+
+fn duplicates_in(peer_ids: &[Vec<u8>]) -> BTreeSet<String> {
+	let mut seen = BTreeSet::new();
+	peer_ids.iter()
+		.filter(|peer_id| !seen.insert(peer_id.clone()))
+		.map(|peer_id| bs58::encode(peer_id).into_string())
+		.collect()
+}
+
+struct GenesisStorage(Storage);
+
+impl GenesisStorage {
+	fn value<T: Decode>(&self, pallet: &[u8], item: &[u8]) -> Option<T> {
+		self.0.top.get(&prefix(pallet, item))
+			.and_then(|value| T::decode(&mut &value[..]).ok())
+	}
+
+	fn map<K: Decode, V: Decode>(&self, pallet: &[u8], item: &[u8], hash_length: usize) -> Vec<(K, V)> {
+		let prefix = prefix(pallet, item);
+		self.0.top.iter()
+			.filter(|(key, _)| key.starts_with(&prefix) && key.len() > prefix.len() + hash_length)
+			.filter_map(|(key, value)| {
+				let key = K::decode(&mut &key[prefix.len() + hash_length..]).ok()?;
+				let value = V::decode(&mut &value[..]).ok()?;
+				Some((key, value))
+			})
+			.collect()
+	}
+
+	fn account(&self, account: &AccountId) -> Option<AccountInfo> {
+		self.map::<AccountId, AccountInfo>(b"System", b"Account", BLAKE2_128_LENGTH)
+			.into_iter()
+			.find(|(candidate, _)| candidate == account)
+			.map(|(_, info)| info)
+	}
+}
+
+fn raw(account: &AccountId) -> &[u8] {
+	account.as_ref()
+}
+
+fn prefix(pallet: &[u8], item: &[u8]) -> Vec<u8> {
+	[twox_128(pallet), twox_128(item)].concat()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+
+	fn storage_with_nodes(peer_ids: Vec<Vec<u8>>) -> GenesisStorage {
+		let mut storage = Storage::default();
+		storage.top.insert(prefix(b"NodeAuthorization", b"WellKnownNodes"), peer_ids.encode());
+		GenesisStorage(storage)
+	}
+
+	#[test]
+	fn it_finds_duplicates_in_raw_storage() {
+		let storage = storage_with_nodes(vec![vec![1], vec![2], vec![1]]);
+		let stored: Vec<Vec<u8>> = storage.value(b"NodeAuthorization", b"WellKnownNodes").unwrap();
+		let expected: BTreeSet<String> = vec![bs58::encode(vec![1u8]).into_string()].into_iter().collect();
+		assert_eq!(duplicates_in(&stored), expected);
+	}
+
+	#[test]
+	fn it_finds_no_duplicates_in_raw_storage() {
+		let storage = storage_with_nodes(vec![vec![1], vec![2]]);
+		let stored: Vec<Vec<u8>> = storage.value(b"NodeAuthorization", b"WellKnownNodes").unwrap();
+		assert!(duplicates_in(&stored).is_empty());
+	}
+
+	#[test]
+	fn it_decodes_set_encoding_as_list() {
+		let set: BTreeSet<Vec<u8>> = vec![vec![1], vec![2]].into_iter().collect();
+		let mut storage = Storage::default();
+		storage.top.insert(prefix(b"NodeAuthorization", b"WellKnownNodes"), set.encode());
+		let stored: Vec<Vec<u8>> = GenesisStorage(storage).value(b"NodeAuthorization", b"WellKnownNodes").unwrap();
+		assert_eq!(stored, vec![vec![1], vec![2]]);
+	}
+}
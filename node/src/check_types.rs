@@ -0,0 +1,389 @@
+//! `check-types` subcommand: checks the polkadot-js custom types declared for the runtime.
+//!
+//! Definitions are written by hand in `additional-types.json`, the subcommand does not generate
+//! them: runtime metadata only carries type names. It collects the names used by every storage
+//! item, event and call argument of the compiled runtime and resolves them against the declared
+//! definitions. It fails if the runtime uses a type which is not declared or if a declared type is
+//! not used by the runtime anymore.
+//!
+//! Definitions are then compared with the runtime types: values of each declared type, built from
+//! the runtime type (see `type_samples`), are encoded and decoded with the definition. The
+//! subcommand fails if a value cannot be decoded, if it is not entirely consumed, or if a declared
+//! type has no samples.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::PathBuf;
+use codec::{Compact, Decode, Encode};
+use frame_metadata::{
+	DecodeDifferent, DecodeDifferentArray, RuntimeMetadata, RuntimeMetadataPrefixed, StorageEntryType,
+};
+use serde_json::{Map, Value};
+use structopt::StructOpt;
+
+/// Types known by polkadot-js which do not have to be declared.
+const BUILTIN_TYPES: &[&str] = &[
+	"u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "bool", "Bytes", "Text",
+	"Vec", "Option", "Result", "Compact", "BTreeMap", "BTreeSet", "Box", "Cow", "PhantomData",
+	"AccountId", "AccountIndex", "Balance", "BalanceOf", "BlockNumber", "Hash", "Moment", "Index",
+	"Weight", "Perbill", "Permill", "FixedU128", "Call", "Event", "DispatchInfo", "DispatchError",
+	"DispatchResult", "DispatchResultWithPostInfo", "Timepoint", "Multisig", "OpaqueCall",
+	"CallHash", "RecoveryConfig", "ActiveRecovery", "SessionIndex", "ValidatorId", "OpaqueTimeSlot",
+	"AuthorityList", "AuthorityId", "AuthorityWeight", "NextAuthority", "SetId", "StoredState",
+	"StoredPendingChange", "EquivocationProof", "KeyOwnerProof", "Slot", "AccountData",
+	"EventRecord", "Phase", "Digest", "DigestOf", "LastRuntimeUpgradeInfo", "ChangesTrieConfiguration",
+	"EventIndex", "Key", "KeyTypeId", "Releases", "BalanceLock", "ReserveData", "ReserveIdentifier",
+	"BalanceStatus", "Multiplier", "PerDispatchClass", "ConsumedWeight", "RefCount",
+	"DepositBalance", "H256", "Registration", "RegistrarInfo", "RegistrarIndex", "IdentityInfo",
+	"IdentityFields", "Judgement", "Data", "Scheduled", "TaskAddress", "Period", "Priority",
+	"PalletsOrigin",
+];
+
+/// Declared overrides of polkadot-js types which are not named by metadata
+/// (extrinsic format, pallet_assets balance), always considered used.
+const ALWAYS_USED: &[&str] = &["Address", "LookupSource", "TAssetBalance"];
+
+/// Definitions of the polkadot-js types referenced by declared definitions, as configured by
+/// the runtime.
+const BUILTIN_DEFINITIONS: &str = r#"{
+  "AccountId": "[u8; 32]",
+  "AccountIndex": "u32",
+  "Balance": "u128",
+  "DepositBalance": "u128",
+  "BlockNumber": "u32",
+  "Hash": "[u8; 32]",
+  "Index": "u32",
+  "Bytes": "Vec<u8>",
+  "MultiAddress": {
+    "_enum": {
+      "Id": "AccountId",
+      "Index": "Compact<AccountIndex>",
+      "Raw": "Bytes",
+      "Address32": "[u8; 32]",
+      "Address20": "[u8; 20]"
+    }
+  },
+  "AccountData": {
+    "free": "Balance",
+    "reserved": "Balance",
+    "misc_frozen": "Balance",
+    "fee_frozen": "Balance"
+  },
+  "AccountInfoWithDualRefCount": {
+    "nonce": "Index",
+    "consumers": "u32",
+    "providers": "u32",
+    "data": "AccountData"
+  },
+  "SessionKeys2": "([u8; 32], [u8; 32])"
+}"#;
+
+/// The `check-types` command.
+#[derive(Debug, StructOpt)]
+pub struct CheckTypesCmd {
+	/// The file declaring the custom types, usually `additional-types.json`.
+	#[structopt(parse(from_os_str))]
+	pub types: PathBuf,
+}
+
+impl CheckTypesCmd {
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let content = std::fs::read_to_string(&self.types)?;
+		let declared: Map<String, Value> = serde_json::from_str(&content)
+			.map_err(|e| format!("Invalid types file {}: {}", self.types.display(), e))?;
+		let used = runtime_type_names(&runtime_metadata()?);
+		check_usage(&declared, &used)?;
+		check_definitions(&declared)?;
+		println!("{} matches runtime types", self.types.display());
+		Ok(())
+	}
+}
+
+fn runtime_metadata() -> sc_cli::Result<RuntimeMetadata> {
+	let encoded = logion_node_runtime::Runtime::metadata().encode();
+	RuntimeMetadataPrefixed::decode(&mut &encoded[..])
+		.map(|prefixed| prefixed.1)
+		.map_err(|e| format!("Unable to decode runtime metadata: {}", e).into())
+}
+
+pub(crate) fn decoded<B, O: Clone>(value: &DecodeDifferent<B, O>) -> O where B: 'static {
+	match value {
+		DecodeDifferent::Decoded(o) => o.clone(),
+		DecodeDifferent::Encode(_) => unreachable!("metadata was decoded; qed"),
+	}
+}
+
+pub(crate) fn decoded_array<B, O: Clone>(value: &DecodeDifferentArray<B, O>) -> Vec<O> where B: 'static {
+	match value {
+		DecodeDifferent::Decoded(o) => o.clone(),
+		DecodeDifferent::Encode(_) => unreachable!("metadata was decoded; qed"),
+	}
+}
+
+/// Returns the names of all types used by storage items, events and calls.
+pub fn runtime_type_names(metadata: &RuntimeMetadata) -> BTreeSet<String> {
+	let mut names = BTreeSet::new();
+	let modules = match metadata {
+		RuntimeMetadata::V12(metadata) => decoded_array(&metadata.modules),
+		_ => return names,
+	};
+	for module in modules {
+		if let Some(storage) = module.storage.as_ref().map(decoded) {
+			for entry in decoded_array(&storage.entries) {
+				match entry.ty {
+					StorageEntryType::Plain(value) => collect(&decoded(&value), &mut names),
+					StorageEntryType::Map { key, value, .. } => {
+						collect(&decoded(&key), &mut names);
+						collect(&decoded(&value), &mut names);
+					},
+					StorageEntryType::DoubleMap { key1, key2, value, .. } => {
+						collect(&decoded(&key1), &mut names);
+						collect(&decoded(&key2), &mut names);
+						collect(&decoded(&value), &mut names);
+					},
+				}
+			}
+		}
+		for call in module.calls.as_ref().map(decoded_array).unwrap_or_default() {
+			for argument in decoded_array(&call.arguments) {
+				collect(&decoded(&argument.ty), &mut names);
+			}
+		}
+		for event in module.event.as_ref().map(decoded_array).unwrap_or_default() {
+			for argument in decoded_array(&event.arguments) {
+				collect(&argument, &mut names);
+			}
+		}
+	}
+	names
+}
+
+/// Extracts type names from a Rust type expression, e.g. `Vec<LocLink<T::LocId>>` gives
+/// `Vec`, `LocLink` and `LocId`.
+fn collect(type_expression: &str, names: &mut BTreeSet<String>) {
+	let type_expression = normalize(type_expression);
+	for token in type_expression.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':')) {
+		let name = token.rsplit("::").next().unwrap_or("");
+		if !name.is_empty() && name != "T" && name != "I" && !name.chars().all(|c| c.is_numeric()) {
+			names.insert(name.to_string());
+		}
+	}
+}
+
+/// Replaces qualified paths the way polkadot-js does, e.g. `<T::Lookup as StaticLookup>::Source`
+/// becomes `LookupSource`.
+fn normalize(type_expression: &str) -> String {
+	let mut normalized = type_expression.replace("<T::Lookup as StaticLookup>::Source", "LookupSource");
+	while let Some(start) = normalized.find(" as ") {
+		let open = normalized[..start].rfind('<').unwrap_or(0);
+		let close = match normalized[start..].find(">::") {
+			Some(close) => start + close + 3,
+			None => break,
+		};
+		normalized.replace_range(open..close, "");
+	}
+	normalized
+}
+
+/// Type names referenced by a declared definition.
+fn referenced_names(definition: &Value, names: &mut BTreeSet<String>) {
+	match definition {
+		Value::String(expression) => collect(expression, names),
+		Value::Object(fields) => fields.iter()
+			.filter(|(key, _)| key.as_str() != "_enum" || !fields["_enum"].is_array())
+			.for_each(|(_, value)| referenced_names(value, names)),
+		_ => (),
+	}
+}
+
+/// Checks that the runtime only uses declared types and that all declared types are used.
+fn check_usage(declared: &Map<String, Value>, used: &BTreeSet<String>) -> sc_cli::Result<()> {
+	let builtin: HashSet<&str> = BUILTIN_TYPES.iter().cloned().collect();
+	let missing: Vec<&String> = used.iter()
+		.filter(|name| !builtin.contains(name.as_str()) && !declared.contains_key(name.as_str()))
+		.collect();
+	if !missing.is_empty() {
+		return Err(format!("Runtime types are not declared: {:?}", missing).into());
+	}
+
+	let mut needed: BTreeSet<String> = used.iter()
+		.map(String::as_str)
+		.chain(ALWAYS_USED.iter().cloned())
+		.filter(|name| declared.contains_key(*name))
+		.map(String::from)
+		.collect();
+	loop {
+		let mut next = needed.clone();
+		for name in needed.iter() {
+			referenced_names(&declared[name], &mut next);
+		}
+		next.retain(|name| declared.contains_key(name));
+		if next == needed {
+			break;
+		}
+		needed = next;
+	}
+
+	let unused: Vec<&String> = declared.keys().filter(|name| !needed.contains(*name)).collect();
+	if !unused.is_empty() {
+		return Err(format!("Declared types are not used by the runtime: {:?}", unused).into());
+	}
+	Ok(())
+}
+
+/// Decodes the samples of each declared type with its definition.
+fn check_definitions(declared: &Map<String, Value>) -> sc_cli::Result<()> {
+	let mut definitions: Map<String, Value> = serde_json::from_str(BUILTIN_DEFINITIONS)
+		.map_err(|e| format!("Invalid builtin definitions: {}", e))?;
+	definitions.extend(declared.clone());
+	let decoder = Decoder { definitions: &definitions };
+
+	let samples: BTreeMap<&str, Vec<Vec<u8>>> = crate::type_samples::samples().into_iter().collect();
+	let mut errors = Vec::new();
+	for name in declared.keys() {
+		let encoded_samples = match samples.get(name.as_str()) {
+			Some(encoded_samples) => encoded_samples,
+			None => {
+				errors.push(format!("{}: no runtime sample", name));
+				continue;
+			},
+		};
+		for encoded in encoded_samples {
+			let mut input = &encoded[..];
+			match decoder.decode_type(name, &mut input) {
+				Err(error) => errors.push(format!("{}: {}", name, error)),
+				Ok(()) if !input.is_empty() => errors.push(format!("{}: {} bytes not decoded", name, input.len())),
+				Ok(()) => (),
+			}
+		}
+	}
+	for name in samples.keys().filter(|name| !declared.contains_key(**name)) {
+		errors.push(format!("{}: sample of a type which is not declared", name));
+	}
+
+	if errors.is_empty() {
+		Ok(())
+	} else {
+		Err(format!("Declared types do not match runtime types: {:?}", errors).into())
+	}
+}
+
+/// Decodes SCALE-encoded values following polkadot-js type definitions.
+struct Decoder<'a> {
+	definitions: &'a Map<String, Value>,
+}
+
+impl<'a> Decoder<'a> {
+	fn decode_type(&self, expression: &str, input: &mut &[u8]) -> Result<(), String> {
+		let expression = expression.trim();
+		if expression.starts_with('(') && expression.ends_with(')') {
+			return split_top_level(&expression[1..expression.len() - 1]).iter()
+				.try_for_each(|element| self.decode_type(element, input));
+		}
+		if expression.starts_with('[') && expression.ends_with(']') {
+			let mut parts = expression[1..expression.len() - 1].splitn(2, ';');
+			let element = parts.next().unwrap_or_default();
+			let length: usize = parts.next().and_then(|length| length.trim().parse().ok())
+				.ok_or_else(|| format!("Invalid array type {}", expression))?;
+			return (0..length).try_for_each(|_| self.decode_type(element, input));
+		}
+		match expression.find('<') {
+			Some(open) if expression.ends_with('>') => {
+				let parameters = split_top_level(&expression[open + 1..expression.len() - 1]);
+				match &expression[..open] {
+					"Vec" => {
+						let length = Compact::<u32>::decode(input).map_err(|e| format!("{} length: {}", expression, e))?;
+						(0..length.0).try_for_each(|_| self.decode_type(&parameters[0], input))
+					},
+					"Option" => match take(input, 1)?[0] {
+						0 => Ok(()),
+						1 => self.decode_type(&parameters[0], input),
+						byte => Err(format!("Invalid option byte {} for {}", byte, expression)),
+					},
+					"Compact" => Compact::<u128>::decode(input).map(|_| ()).map_err(|e| format!("{}: {}", expression, e)),
+					"Box" => self.decode_type(&parameters[0], input),
+					name => self.decode_named(name, input),
+				}
+			},
+			_ => self.decode_named(expression, input),
+		}
+	}
+
+	fn decode_named(&self, name: &str, input: &mut &[u8]) -> Result<(), String> {
+		let size = match name {
+			"u8" | "i8" => 1,
+			"u16" | "i16" => 2,
+			"u32" | "i32" => 4,
+			"u64" | "i64" => 8,
+			"u128" | "i128" => 16,
+			"bool" => return match take(input, 1)?[0] {
+				0 | 1 => Ok(()),
+				byte => Err(format!("Invalid bool byte {}", byte)),
+			},
+			_ => {
+				let definition = self.definitions.get(name).ok_or_else(|| format!("Unknown type {}", name))?;
+				return self.decode_definition(name, definition, input);
+			},
+		};
+		take(input, size).map(|_| ())
+	}
+
+	fn decode_definition(&self, name: &str, definition: &Value, input: &mut &[u8]) -> Result<(), String> {
+		match definition {
+			Value::String(expression) => self.decode_type(expression, input),
+			Value::Object(fields) => match fields.get("_enum") {
+				Some(Value::Array(variants)) => {
+					let index = take(input, 1)?[0] as usize;
+					if index < variants.len() {
+						Ok(())
+					} else {
+						Err(format!("Invalid variant index {} of {}", index, name))
+					}
+				},
+				Some(Value::Object(variants)) => {
+					let index = take(input, 1)?[0] as usize;
+					match variants.values().nth(index) {
+						Some(Value::Null) => Ok(()),
+						Some(variant) => self.decode_definition(name, variant, input),
+						None => Err(format!("Invalid variant index {} of {}", index, name)),
+					}
+				},
+				Some(_) => Err(format!("Invalid enum definition of {}", name)),
+				None => fields.values().try_for_each(|field| self.decode_definition(name, field, input)),
+			},
+			_ => Err(format!("Invalid definition of {}", name)),
+		}
+	}
+}
+
+/// Splits a list of type expressions on commas which are not nested in brackets.
+fn split_top_level(list: &str) -> Vec<String> {
+	let mut elements = Vec::new();
+	let mut depth = 0;
+	let mut current = String::new();
+	for c in list.chars() {
+		match c {
+			'<' | '(' | '[' => depth += 1,
+			'>' | ')' | ']' => depth -= 1,
+			',' if depth == 0 => {
+				elements.push(current.trim().to_string());
+				current.clear();
+				continue;
+			},
+			_ => (),
+		}
+		current.push(c);
+	}
+	if !current.trim().is_empty() {
+		elements.push(current.trim().to_string());
+	}
+	elements
+}
+
+fn take<'b>(input: &mut &'b [u8], size: usize) -> Result<&'b [u8], String> {
+	if input.len() < size {
+		return Err(format!("{} bytes expected, {} left", size, input.len()));
+	}
+	let (taken, rest) = input.split_at(size);
+	*input = rest;
+	Ok(taken)
+}
@@ -1,5 +1,6 @@
 use structopt::StructOpt;
 use sc_cli::RunCmd;
+use crate::manual_seal::Sealing;
 
 #[derive(Debug, StructOpt)]
 pub struct Cli {
@@ -8,6 +9,19 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// Seal blocks on demand instead of running Aura and GRANDPA, `instant` or `manual`.
+	/// Development and local chains only.
+	#[structopt(long = "sealing")]
+	pub sealing: Option<Sealing>,
+
+	/// LOCs exported with `export-locs` to import at genesis of the `mvp` chain.
+	#[structopt(long = "loc-dump", parse(from_os_str))]
+	pub loc_dump: Option<std::path::PathBuf>,
+
+	/// Start a live chain even if `check-spec` finds problems in its chain spec.
+	#[structopt(long = "allow-unsafe-spec")]
+	pub allow_unsafe_spec: bool,
 }
 
 #[derive(Debug, StructOpt)]
@@ -35,6 +49,29 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Check the declared polkadot-js custom types against the runtime.
+	#[structopt(name = "check-types")]
+	CheckTypes(crate::check_types::CheckTypesCmd),
+
+	/// Check that a chain spec is safe for a live network.
+	#[structopt(name = "check-spec")]
+	CheckSpec(crate::check_spec::CheckSpecCmd),
+
+	/// Check that a new runtime is backward compatible with an old one.
+	#[structopt(name = "check-compat")]
+	CheckCompat(crate::check_compat::CheckCompatCmd),
+
+	/// Validator management utilities.
+	Validator(crate::validator::ValidatorCmd),
+
+	/// Export the LOCs of a given block for a regenesis.
+	#[structopt(name = "export-locs")]
+	ExportLocs(crate::export_locs::ExportLocsCmd),
+
+	/// Build a development chain spec from the state exported with `export-state`.
+	#[structopt(name = "fork-off")]
+	ForkOff(crate::fork_off::ForkOffCmd),
+
 	/// The custom benchmark subcommmand benchmarking runtime pallets.
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
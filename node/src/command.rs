@@ -15,10 +15,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{chain_spec, service};
+use crate::{chain_spec, check_spec, service};
 use crate::cli::{Cli, Subcommand};
 use sc_cli::{SubstrateCli, RuntimeVersion, Role, ChainSpec};
-use sc_service::PartialComponents;
+use sc_service::{ChainType, PartialComponents};
 use logion_node_runtime::Block;
 
 impl SubstrateCli for Cli {
@@ -48,10 +48,12 @@ impl SubstrateCli for Cli {
 
 	fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
 		Ok(match id {
-			"dev" => Box::new(chain_spec::development_config()?),
-			"mvp" => Box::new(chain_spec::mvp_config()?),
+			"dev" => Box::new(chain_spec::development_config(false)?),
+			"dev-fixtures" => Box::new(chain_spec::development_config(true)?),
+			"mvp" => Box::new(chain_spec::mvp_config(chain_spec::load_loc_dump(self.loc_dump.as_deref())?)?),
 			"test" => Box::new(chain_spec::test_testnet_config()?),
-			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
+			"" | "local" => Box::new(chain_spec::local_testnet_config(false)?),
+			"local-fixtures" => Box::new(chain_spec::local_testnet_config(true)?),
 			path => Box::new(chain_spec::ChainSpec::from_json_file(
 				std::path::PathBuf::from(path),
 			)?),
@@ -69,6 +71,7 @@ pub fn run() -> sc_cli::Result<()> {
 
 	match &cli.subcommand {
 		Some(Subcommand::Key(cmd)) => cmd.run(&cli),
+		Some(Subcommand::Validator(cmd)) => cmd.run(&cli),
 		Some(Subcommand::BuildSpec(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.chain_spec, config.network))
@@ -117,6 +120,20 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, backend), task_manager))
 			})
 		},
+		Some(Subcommand::ExportLocs(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, .. } = service::new_partial(&config)?;
+				cmd.run::<sc_service::TFullBackend<Block>, _>(client)
+			})
+		},
+		Some(Subcommand::CheckTypes(cmd)) => cmd.run(),
+		Some(Subcommand::CheckCompat(cmd)) => cmd.run(),
+		Some(Subcommand::CheckSpec(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run(config.chain_spec))
+		},
+		Some(Subcommand::ForkOff(cmd)) => cmd.run(),
 		Some(Subcommand::Benchmark(cmd)) => {
 			if cfg!(feature = "runtime-benchmarks") {
 				let runner = cli.create_runner(cmd)?;
@@ -129,10 +146,20 @@ pub fn run() -> sc_cli::Result<()> {
 		},
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
+			let sealing = cli.sealing;
+			let allow_unsafe_spec = cli.allow_unsafe_spec;
 			runner.run_node_until_exit(|config| async move {
-				match config.role {
-					Role::Light => service::new_light(config),
-					_ => service::new_full(config),
+				if config.chain_spec.chain_type() == ChainType::Live {
+					check_spec::enforce(&*config.chain_spec, allow_unsafe_spec)?;
+				}
+				match (sealing, config.role.clone()) {
+					(Some(sealing), _) => match config.chain_spec.chain_type() {
+						ChainType::Development | ChainType::Local =>
+							service::new_manual_seal(config, sealing),
+						_ => Err("Sealing on demand is only available on development and local chains".into()),
+					},
+					(None, Role::Light) => service::new_light(config),
+					(None, _) => service::new_full(config),
 				}.map_err(sc_cli::Error::Service)
 			})
 		}
@@ -0,0 +1,104 @@
+//! `export-locs` subcommand: dumps the LOCs of a chain for a regenesis.
+//!
+//! LOCs and collection items are read from the `LogionLoc` storage of a synchronized node and
+//! written in the format of the `LogionLocGenesis` genesis configuration. The dump is passed to
+//! `--loc-dump` when building the chain spec of the new network.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use codec::{Decode, DecodeAll};
+use logion_node_runtime::{Block, Hash, LogionLocGenesisConfig, Runtime};
+use pallet_logion_loc_genesis::GenesisLocOf;
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::generic::BlockId;
+use structopt::StructOpt;
+
+/// Length of a `Blake2_128Concat` hash prefix.
+const BLAKE2_128_LENGTH: usize = 16;
+
+/// The `export-locs` command.
+#[derive(Debug, StructOpt)]
+pub struct ExportLocsCmd {
+	/// Block hash or number of the exported state, the last finalized block by default.
+	#[structopt(long, value_name = "HASH or NUMBER")]
+	pub at: Option<BlockNumberOrHash>,
+
+	/// Write the dump to this file instead of standard output.
+	#[structopt(long, parse(from_os_str))]
+	pub output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ExportLocsCmd {
+	pub fn run<B, C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		B: Backend<Block>,
+		C: StorageProvider<Block, B> + HeaderBackend<Block>,
+	{
+		let at = match &self.at {
+			Some(at) => at.parse::<Block>()?,
+			None => BlockId::Hash(client.info().finalized_hash),
+		};
+
+		let mut dump = LogionLocGenesisConfig::default();
+		for (key, value) in client.storage_pairs(&at, &storage_prefix(b"LocMap"))? {
+			let mut key = &key.0[32 + BLAKE2_128_LENGTH..];
+			let id = u128::decode(&mut key).map_err(|e| format!("Invalid LOC id: {}", e))?;
+			// Trailing bytes would mean that `GenesisLoc` lacks fields of `LegalOfficerCase`.
+			let loc = GenesisLocOf::<Runtime>::decode_all(&value.0)
+				.map_err(|e| format!("Invalid LOC {}: {}", id, e))?;
+			dump.locs.push((id, loc));
+		}
+		for (key, value) in client.storage_pairs(&at, &storage_prefix(b"CollectionItemsMap"))? {
+			let mut key = &key.0[32 + BLAKE2_128_LENGTH..];
+			let collection_id = u128::decode(&mut key).map_err(|e| format!("Invalid LOC id: {}", e))?;
+			let mut key = &key[BLAKE2_128_LENGTH..];
+			let item_id = Hash::decode(&mut key).map_err(|e| format!("Invalid item id: {}", e))?;
+			let description = Vec::<u8>::decode_all(&value.0)
+				.map_err(|e| format!("Invalid item {:?} of LOC {}: {}", item_id, collection_id, e))?;
+			dump.collection_items.push((collection_id, item_id, description));
+		}
+		dump.locs.sort_by_key(|(id, _)| *id);
+
+		let json = serde_json::to_string_pretty(&dump)
+			.map_err(|e| format!("Unable to serialize LOCs: {}", e))?;
+		match &self.output {
+			Some(path) => std::fs::write(path, json + "\n")?,
+			None => println!("{}", json),
+		}
+		eprintln!("{} LOCs and {} collection items exported", dump.locs.len(), dump.collection_items.len());
+		Ok(())
+	}
+}
+
+fn storage_prefix(item: &[u8]) -> StorageKey {
+	StorageKey([twox_128(b"LogionLoc"), twox_128(item)].concat())
+}
+
+impl CliConfiguration for ExportLocsCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,110 @@
+//! `fork-off` subcommand: builds a development chain spec from live state.
+//!
+//! The input is the raw chain spec produced by `export-state`. The storage of consensus and
+//! administration pallets is replaced with the one of the development chain so that the fork
+//! boots with `--alice`, everything else (LOCs, legal officers, balances, recovery, ...) is kept.
+
+use std::path::PathBuf;
+use serde_json::{json, Value};
+use sp_core::{bytes::{from_hex, to_hex}, hashing::twox_128, storage::Storage};
+use sp_runtime::BuildStorage;
+use structopt::StructOpt;
+use crate::chain_spec;
+
+/// Pallets whose storage is replaced with the one of the development chain.
+const REPLACED_PALLETS: &[&str] = &["Aura", "Grandpa", "Session", "ValidatorSet", "NodeAuthorization", "Sudo"];
+
+/// Storage items replaced with the ones of the development chain, they describe replaced state
+/// (e.g. node quotas of legal officers follow `NodeAuthorization`).
+const REPLACED_STORAGE: &[(&str, &str)] = &[("LoNodeAuthorization", "NodeCount")];
+
+/// Well-known keys replaced with the ones of the development chain.
+const REPLACED_KEYS: &[&[u8]] = &[b":grandpa_authorities"];
+
+/// The `fork-off` command.
+#[derive(Debug, StructOpt)]
+pub struct ForkOffCmd {
+	/// The raw chain spec produced by `export-state`.
+	#[structopt(long, parse(from_os_str))]
+	pub input: PathBuf,
+
+	/// Where to write the chain spec of the fork.
+	#[structopt(long, parse(from_os_str))]
+	pub output: PathBuf,
+}
+
+impl ForkOffCmd {
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let content = std::fs::read_to_string(&self.input)?;
+		let mut spec: Value = serde_json::from_str(&content)
+			.map_err(|e| format!("Invalid chain spec {}: {}", self.input.display(), e))?;
+		let dev_storage = chain_spec::development_config(false)?.build_storage()?;
+
+		let top = spec.pointer_mut("/genesis/raw/top")
+			.and_then(Value::as_object_mut)
+			.ok_or_else(|| format!("{} is not a raw chain spec", self.input.display()))?;
+
+		let prefixes: Vec<Vec<u8>> = REPLACED_PALLETS.iter()
+			.map(|pallet| twox_128(pallet.as_bytes()).to_vec())
+			.chain(REPLACED_STORAGE.iter()
+				.map(|(pallet, item)| [twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat()))
+			.collect();
+		let replaced = |key: &[u8]| prefixes.iter().any(|prefix| key.starts_with(prefix))
+			|| REPLACED_KEYS.contains(&key);
+
+		let live_keys: Vec<String> = top.keys().cloned().collect();
+		for key in live_keys {
+			let bytes = from_hex(&key).map_err(|e| format!("Invalid storage key {}: {:?}", key, e))?;
+			if replaced(&bytes) {
+				top.remove(&key);
+			}
+		}
+		for (key, value) in dev_storage.top.iter().filter(|(key, _)| replaced(key)) {
+			top.insert(to_hex(key, false), json!(to_hex(value, false)));
+		}
+		endow_dev_accounts(top, &dev_storage)?;
+
+		spec["name"] = json!("Logion Fork");
+		spec["id"] = json!("logion_fork");
+		spec["chainType"] = json!("Development");
+		spec["bootNodes"] = json!([]);
+		if let Some(spec) = spec.as_object_mut() {
+			spec.remove("forkBlocks");
+			spec.remove("badBlocks");
+		}
+
+		let json = serde_json::to_string_pretty(&spec)
+			.map_err(|e| format!("Unable to serialize chain spec: {}", e))?;
+		std::fs::write(&self.output, json)?;
+		println!("Fork written to {}, run it with `--chain {} --alice`", self.output.display(), self.output.display());
+		Ok(())
+	}
+}
+
+/// Adds the development accounts missing in live state (among which sudo and validators)
+/// and updates total issuance accordingly.
+fn endow_dev_accounts(top: &mut serde_json::Map<String, Value>, dev_storage: &Storage) -> sc_cli::Result<()> {
+	let accounts_prefix = [twox_128(b"System"), twox_128(b"Account")].concat();
+	let total_issuance_key = to_hex(&[twox_128(b"Balances"), twox_128(b"TotalIssuance")].concat(), false);
+
+	let mut total_issuance = match top.get(&total_issuance_key).and_then(Value::as_str) {
+		Some(value) => u128_from_hex(value)?,
+		None => 0,
+	};
+	for (key, value) in dev_storage.top.iter().filter(|(key, _)| key.starts_with(&accounts_prefix)) {
+		let key = to_hex(key, false);
+		if !top.contains_key(&key) {
+			top.insert(key, json!(to_hex(value, false)));
+			total_issuance = total_issuance.saturating_add(chain_spec::INITIAL_BALANCE);
+		}
+	}
+	top.insert(total_issuance_key, json!(to_hex(&total_issuance.to_le_bytes(), false)));
+	Ok(())
+}
+
+fn u128_from_hex(value: &str) -> sc_cli::Result<u128> {
+	let bytes = from_hex(value).map_err(|e| format!("Invalid value {}: {:?}", value, e))?;
+	let mut le_bytes = [0u8; 16];
+	le_bytes[..bytes.len().min(16)].copy_from_slice(&bytes[..bytes.len().min(16)]);
+	Ok(u128::from_le_bytes(le_bytes))
+}
@@ -1,3 +1,13 @@
 pub mod chain_spec;
 pub mod service;
 pub mod rpc;
+pub mod asset_fee_payment;
+pub mod manual_seal;
+pub mod lo_profiles;
+pub mod check_types;
+pub mod type_samples;
+pub mod export_locs;
+pub mod check_compat;
+pub mod check_spec;
+pub mod fork_off;
+pub mod validator;
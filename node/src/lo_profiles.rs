@@ -0,0 +1,76 @@
+//! Discovery of legal officers and their backends.
+//!
+//! `legalOfficers_list` returns the active legal officers with their on-chain profile, text fields
+//! decoded as UTF-8 so that wallets can use them directly.
+
+use std::sync::Arc;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use logion_node_runtime::{opaque::Block, AccountId, Hash};
+use pallet_lo_profiles_runtime_api::{LegalOfficerProfile, LegalOfficerProfilesApi};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::generic::BlockId;
+
+/// A legal officer and its profile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegalOfficer {
+	pub account: AccountId,
+	pub display_name: String,
+	pub region: String,
+	pub base_url: String,
+	pub node_id: Option<String>,
+	pub encryption_key: Bytes,
+}
+
+impl From<(AccountId, LegalOfficerProfile)> for LegalOfficer {
+	fn from((account, profile): (AccountId, LegalOfficerProfile)) -> Self {
+		LegalOfficer {
+			account,
+			display_name: String::from_utf8_lossy(&profile.display_name).into(),
+			region: String::from_utf8_lossy(&profile.region).into(),
+			base_url: String::from_utf8_lossy(&profile.base_url).into(),
+			node_id: profile.node_id.map(|node_id| bs58::encode(node_id.0).into_string()),
+			encryption_key: profile.encryption_key.into(),
+		}
+	}
+}
+
+/// Legal officers discovery.
+#[rpc]
+pub trait LegalOfficersApi<BlockHash> {
+	/// Returns the active legal officers with their profile at given block, the best block by default.
+	#[rpc(name = "legalOfficers_list")]
+	fn list(&self, at: Option<BlockHash>) -> RpcResult<Vec<LegalOfficer>>;
+}
+
+/// Implements `LegalOfficersApi` with the `LegalOfficerProfilesApi` runtime API.
+pub struct LegalOfficers<C> {
+	client: Arc<C>,
+}
+
+impl<C> LegalOfficers<C> {
+	pub fn new(client: Arc<C>) -> Self {
+		LegalOfficers { client }
+	}
+}
+
+impl<C> LegalOfficersApi<Hash> for LegalOfficers<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: LegalOfficerProfilesApi<Block, AccountId>,
+{
+	fn list(&self, at: Option<Hash>) -> RpcResult<Vec<LegalOfficer>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let legal_officers = self.client.runtime_api().legal_officers(&at)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(1),
+				message: "Unable to list legal officers".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+		Ok(legal_officers.into_iter().map(LegalOfficer::from).collect())
+	}
+}
@@ -7,6 +7,16 @@ mod service;
 mod cli;
 mod command;
 mod rpc;
+mod asset_fee_payment;
+mod manual_seal;
+mod lo_profiles;
+mod check_types;
+mod type_samples;
+mod export_locs;
+mod check_compat;
+mod check_spec;
+mod fork_off;
+mod validator;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -0,0 +1,153 @@
+//! Block production on demand for development chains.
+//!
+//! Instead of waiting for Aura slots, blocks are sealed as soon as a transaction enters the pool
+//! (`instant`) or when requested through `engine_createBlock` (`manual`). Blocks sealed instantly
+//! are finalized right away. The timestamp inherent is produced by `DevTimestamp` which advances
+//! by one slot per sealed block and can be moved forward with `dev_increaseTime` so that tests do
+//! not have to wait real time.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use codec::Encode;
+use jsonrpc_core::Result as RpcResult;
+use jsonrpc_derive::rpc;
+use logion_node_runtime::{opaque::Block, SLOT_DURATION};
+use sc_consensus_manual_seal::{ConsensusDataProvider, Error};
+use sp_consensus::BlockImportParams;
+use sp_consensus_aura::AURA_ENGINE_ID;
+use sp_inherents::{InherentData, InherentIdentifier, ProvideInherentData};
+use sp_runtime::{generic::{Digest, DigestItem}, traits::Block as BlockT, DigestFor};
+use sp_timestamp::{InherentType, INHERENT_IDENTIFIER};
+
+/// The sealing method of a development node.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Sealing {
+	/// A block is sealed as soon as a transaction is submitted.
+	Instant,
+	/// A block is sealed on `engine_createBlock` call.
+	Manual,
+}
+
+impl std::str::FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Sealing::Instant),
+			"manual" => Ok(Sealing::Manual),
+			_ => Err(format!("Unknown sealing method '{}', expected 'instant' or 'manual'", s)),
+		}
+	}
+}
+
+/// The timestamp of the next development block.
+pub struct DevTimestamp {
+	next: AtomicU64,
+}
+
+impl DevTimestamp {
+	pub fn new() -> Self {
+		let now = SystemTime::now().duration_since(UNIX_EPOCH)
+			.expect("current time is after unix epoch; qed")
+			.as_millis() as u64;
+		DevTimestamp {
+			next: AtomicU64::new(now - now % SLOT_DURATION),
+		}
+	}
+
+	/// Returns the timestamp of the next block.
+	fn next(&self) -> u64 {
+		self.next.load(Ordering::SeqCst)
+	}
+
+	/// Moves to the slot following the one of a sealed block.
+	fn sealed(&self, timestamp: u64) {
+		self.next.fetch_max(timestamp + SLOT_DURATION, Ordering::SeqCst);
+	}
+
+	/// Moves time forward by given number of milliseconds, rounded up to the next slot.
+	pub fn increase(&self, millis: u64) -> u64 {
+		let slots = (millis + SLOT_DURATION - 1) / SLOT_DURATION;
+		self.next.fetch_add(slots * SLOT_DURATION, Ordering::SeqCst) + slots * SLOT_DURATION
+	}
+}
+
+/// Provides the timestamp inherent from a `DevTimestamp`.
+pub struct DevTimestampProvider(pub Arc<DevTimestamp>);
+
+impl ProvideInherentData for DevTimestampProvider {
+	fn inherent_identifier(&self) -> &'static InherentIdentifier {
+		&INHERENT_IDENTIFIER
+	}
+
+	fn provide_inherent_data(&self, inherent_data: &mut InherentData) -> Result<(), sp_inherents::Error> {
+		let timestamp: InherentType = self.0.next();
+		inherent_data.put_data(INHERENT_IDENTIFIER, &timestamp)
+	}
+
+	fn error_to_string(&self, error: &[u8]) -> Option<String> {
+		Some(String::from_utf8_lossy(error).into())
+	}
+}
+
+/// Adds an Aura pre-runtime digest matching the timestamp inherent so that the runtime
+/// sees consistent slots, and moves `DevTimestamp` to the next slot.
+pub struct AuraSlotDigestProvider<Transaction> {
+	dev_timestamp: Arc<DevTimestamp>,
+	_transaction: std::marker::PhantomData<Transaction>,
+}
+
+impl<Transaction> AuraSlotDigestProvider<Transaction> {
+	pub fn new(dev_timestamp: Arc<DevTimestamp>) -> Self {
+		AuraSlotDigestProvider {
+			dev_timestamp,
+			_transaction: Default::default(),
+		}
+	}
+}
+
+impl<Transaction: Send + Sync + 'static> ConsensusDataProvider<Block> for AuraSlotDigestProvider<Transaction> {
+	type Transaction = Transaction;
+
+	fn create_digest(
+		&self,
+		_parent: &<Block as BlockT>::Header,
+		inherents: &InherentData,
+	) -> Result<DigestFor<Block>, Error> {
+		let timestamp: InherentType = inherents.get_data(&INHERENT_IDENTIFIER)
+			.map_err(|e| Error::StringError(format!("{:?}", e)))?
+			.ok_or_else(|| Error::StringError("Timestamp inherent is missing".into()))?;
+		let slot: u64 = timestamp / SLOT_DURATION;
+		self.dev_timestamp.sealed(timestamp);
+		Ok(Digest {
+			logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, slot.encode())],
+		})
+	}
+
+	fn append_block_import(
+		&self,
+		_parent: &<Block as BlockT>::Header,
+		_params: &mut BlockImportParams<Block, Self::Transaction>,
+		_inherents: &InherentData,
+	) -> Result<(), Error> {
+		Ok(())
+	}
+}
+
+/// Time control of development nodes.
+#[rpc]
+pub trait DevTimeApi {
+	/// Moves time forward by given number of milliseconds and returns the timestamp of next block.
+	#[rpc(name = "dev_increaseTime")]
+	fn increase_time(&self, millis: u64) -> RpcResult<u64>;
+}
+
+/// Implements `DevTimeApi` on top of a `DevTimestamp`.
+pub struct DevTime(pub Arc<DevTimestamp>);
+
+impl DevTimeApi for DevTime {
+	fn increase_time(&self, millis: u64) -> RpcResult<u64> {
+		Ok(self.0.increase(millis))
+	}
+}
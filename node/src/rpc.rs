@@ -7,34 +7,66 @@
 
 use std::sync::Arc;
 
-use logion_node_runtime::{opaque::Block, AccountId, Balance, Index};
+use logion_node_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Index};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{Error as BlockChainError, HeaderMetadata, HeaderBackend};
 use sp_block_builder::BlockBuilder;
 pub use sc_rpc_api::DenyUnsafe;
+use sc_rpc::SubscriptionTaskExecutor;
 use sp_transaction_pool::TransactionPool;
+use sc_finality_grandpa::{
+	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
+};
+use sc_finality_grandpa_rpc::GrandpaRpcHandler;
+use sc_consensus_manual_seal::rpc::{EngineCommand, ManualSeal, ManualSealApi};
+use crate::asset_fee_payment::{AssetFeePayment, AssetFeePaymentApi};
+use crate::lo_profiles::{LegalOfficers, LegalOfficersApi};
+use crate::manual_seal::{DevTime, DevTimeApi, DevTimestamp};
 
+/// Extra dependencies for GRANDPA
+pub struct GrandpaDeps<B> {
+	/// Voting round info.
+	pub shared_voter_state: SharedVoterState,
+	/// Authority set info.
+	pub shared_authority_set: SharedAuthoritySet<Hash, BlockNumber>,
+	/// Receives notifications about justification events from Grandpa.
+	pub justification_stream: GrandpaJustificationStream<Block>,
+	/// Executor to drive the subscription manager in the Grandpa RPC handler.
+	pub subscription_executor: SubscriptionTaskExecutor,
+	/// Finality proof provider.
+	pub finality_provider: Arc<FinalityProofProvider<B, Block>>,
+}
 
 /// Full client dependencies.
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, B> {
 	/// The client instance to use.
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// GRANDPA specific dependencies, none when GRANDPA is not running
+	pub grandpa: Option<GrandpaDeps<B>>,
+	/// Manual seal command sink, development nodes with manual sealing only
+	pub command_sink: Option<futures::channel::mpsc::Sender<EngineCommand<Hash>>>,
+	/// Timestamp of next block, development nodes sealing on demand only
+	pub dev_timestamp: Option<Arc<DevTimestamp>>,
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(
-	deps: FullDeps<C, P>,
+pub fn create_full<C, P, B>(
+	deps: FullDeps<C, P, B>,
 ) -> jsonrpc_core::IoHandler<sc_rpc::Metadata> where
+	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
+	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashFor<Block>>,
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error=BlockChainError> + 'static,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: pallet_asset_fee_payment_runtime_api::AssetFeePaymentApi<Block, u64, Balance>,
+	C::Api: pallet_lo_profiles_runtime_api::LegalOfficerProfilesApi<Block, AccountId>,
 	P: TransactionPool + 'static,
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
@@ -45,6 +77,9 @@ pub fn create_full<C, P>(
 		client,
 		pool,
 		deny_unsafe,
+		grandpa,
+		command_sink,
+		dev_timestamp,
 	} = deps;
 
 	io.extend_with(
@@ -55,6 +90,44 @@ pub fn create_full<C, P>(
 		TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone()))
 	);
 
+	io.extend_with(
+		AssetFeePaymentApi::to_delegate(AssetFeePayment::new(client.clone()))
+	);
+
+	io.extend_with(
+		LegalOfficersApi::to_delegate(LegalOfficers::new(client.clone()))
+	);
+
+	if let Some(GrandpaDeps {
+		shared_voter_state,
+		shared_authority_set,
+		justification_stream,
+		subscription_executor,
+		finality_provider,
+	}) = grandpa {
+		io.extend_with(
+			sc_finality_grandpa_rpc::GrandpaApi::to_delegate(GrandpaRpcHandler::new(
+				shared_authority_set,
+				shared_voter_state,
+				justification_stream,
+				subscription_executor,
+				finality_provider,
+			))
+		);
+	}
+
+	if let Some(command_sink) = command_sink {
+		io.extend_with(
+			ManualSealApi::to_delegate(ManualSeal::new(command_sink))
+		);
+	}
+
+	if let Some(dev_timestamp) = dev_timestamp {
+		io.extend_with(
+			DevTimeApi::to_delegate(DevTime(dev_timestamp))
+		);
+	}
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
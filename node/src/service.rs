@@ -2,8 +2,10 @@
 
 use std::sync::Arc;
 use std::time::Duration;
+use futures::{Stream, StreamExt};
 use sc_client_api::{ExecutorProvider, RemoteBackend};
-use logion_node_runtime::{self, opaque::Block, RuntimeApi};
+use sc_consensus_manual_seal::rpc::EngineCommand;
+use logion_node_runtime::{self, opaque::Block, Hash, RuntimeApi};
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sp_inherents::InherentDataProviders;
 use sc_executor::native_executor_instance;
@@ -11,6 +13,7 @@ pub use sc_executor::NativeExecutor;
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
 use sc_finality_grandpa::SharedVoterState;
 use sc_keystore::LocalKeystore;
+use crate::manual_seal::{AuraSlotDigestProvider, DevTimestamp, DevTimestampProvider, Sealing};
 
 // Our native executor instance.
 native_executor_instance!(
@@ -149,15 +152,33 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	let justification_stream = grandpa_link.justification_stream();
+	let shared_authority_set = grandpa_link.shared_authority_set().clone();
+	let shared_voter_state = SharedVoterState::empty();
+	let finality_proof_provider = sc_finality_grandpa::FinalityProofProvider::new_for_service(
+		backend.clone(),
+		Some(shared_authority_set.clone()),
+	);
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
+		let shared_voter_state = shared_voter_state.clone();
 
-		Box::new(move |deny_unsafe, _| {
+		Box::new(move |deny_unsafe, subscription_executor| {
 			let deps = crate::rpc::FullDeps {
 				client: client.clone(),
 				pool: pool.clone(),
 				deny_unsafe,
+				grandpa: Some(crate::rpc::GrandpaDeps {
+					shared_voter_state: shared_voter_state.clone(),
+					shared_authority_set: shared_authority_set.clone(),
+					justification_stream: justification_stream.clone(),
+					subscription_executor,
+					finality_provider: finality_proof_provider.clone(),
+				}),
+				command_sink: None,
+				dev_timestamp: None,
 			};
 
 			crate::rpc::create_full(deps)
@@ -242,7 +263,7 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 			network,
 			voting_rule: sc_finality_grandpa::VotingRulesBuilder::default().build(),
 			prometheus_registry,
-			shared_voter_state: SharedVoterState::empty(),
+			shared_voter_state,
 			telemetry_on_connect: telemetry_connection_notifier.map(|x| x.on_connect_stream()),
 		};
 
@@ -258,6 +279,124 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	Ok(task_manager)
 }
 
+/// Builds a new service for a full client sealing blocks on demand, development chains only.
+pub fn new_manual_seal(config: Configuration, sealing: Sealing) -> Result<TaskManager, ServiceError> {
+	let dev_timestamp = Arc::new(DevTimestamp::new());
+	let inherent_data_providers = InherentDataProviders::new();
+	inherent_data_providers
+		.register_provider(DevTimestampProvider(dev_timestamp.clone()))
+		.map_err(sp_consensus::Error::InherentData)?;
+
+	let (client, backend, keystore_container, mut task_manager) =
+		sc_service::new_full_parts::<Block, RuntimeApi, Executor>(&config)?;
+	let client = Arc::new(client);
+
+	let select_chain = sc_consensus::LongestChain::new(backend.clone());
+
+	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
+		config.transaction_pool.clone(),
+		config.role.is_authority().into(),
+		config.prometheus_registry(),
+		task_manager.spawn_handle(),
+		client.clone(),
+	);
+
+	let import_queue = sc_consensus_manual_seal::import_queue(
+		Box::new(client.clone()),
+		&task_manager.spawn_handle(),
+		config.prometheus_registry(),
+	);
+
+	let (network, network_status_sinks, system_rpc_tx, network_starter) =
+		sc_service::build_network(sc_service::BuildNetworkParams {
+			config: &config,
+			client: client.clone(),
+			transaction_pool: transaction_pool.clone(),
+			spawn_handle: task_manager.spawn_handle(),
+			import_queue,
+			on_demand: None,
+			block_announce_validator_builder: None,
+		})?;
+
+	let prometheus_registry = config.prometheus_registry().cloned();
+	let (command_sink, commands_stream) = futures::channel::mpsc::channel(1024);
+
+	let rpc_extensions_builder = {
+		let client = client.clone();
+		let pool = transaction_pool.clone();
+		let command_sink = command_sink.clone();
+		let dev_timestamp = dev_timestamp.clone();
+
+		Box::new(move |deny_unsafe, _| {
+			let deps = crate::rpc::FullDeps::<_, _, FullBackend> {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				grandpa: None,
+				command_sink: Some(command_sink.clone()),
+				dev_timestamp: Some(dev_timestamp.clone()),
+			};
+
+			crate::rpc::create_full(deps)
+		})
+	};
+
+	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+		network: network.clone(),
+		client: client.clone(),
+		keystore: keystore_container.sync_keystore(),
+		task_manager: &mut task_manager,
+		transaction_pool: transaction_pool.clone(),
+		rpc_extensions_builder,
+		on_demand: None,
+		remote_blockchain: None,
+		backend,
+		network_status_sinks,
+		system_rpc_tx,
+		config,
+	})?;
+
+	let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+		task_manager.spawn_handle(),
+		client.clone(),
+		transaction_pool.clone(),
+		prometheus_registry.as_ref(),
+	);
+
+	let consensus_data_provider = Some(Box::new(AuraSlotDigestProvider::new(dev_timestamp)) as Box<
+		dyn sc_consensus_manual_seal::ConsensusDataProvider<Block, Transaction = sp_api::TransactionFor<FullClient, Block>>
+	>);
+	let commands_stream: Box<dyn Stream<Item = EngineCommand<Hash>> + Send + Unpin> = match sealing {
+		Sealing::Manual => Box::new(commands_stream),
+		Sealing::Instant => {
+			let imports_stream = transaction_pool.pool().validated_pool().import_notification_stream()
+				.map(|_| EngineCommand::SealNewBlock {
+					create_empty: false,
+					finalize: true,
+					parent_hash: None,
+					sender: None,
+				});
+			Box::new(futures::stream::select(imports_stream, commands_stream))
+		},
+	};
+	let authorship_future = sc_consensus_manual_seal::run_manual_seal(
+		sc_consensus_manual_seal::ManualSealParams {
+			block_import: client.clone(),
+			env: proposer_factory,
+			client,
+			pool: transaction_pool.pool().clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider,
+			inherent_data_providers,
+		}
+	);
+	task_manager.spawn_essential_handle().spawn_blocking("manual-seal", authorship_future);
+
+	network_starter.start_network();
+	Ok(task_manager)
+}
+
 /// Builds a new service for a light client.
 pub fn new_light(mut config: Configuration) -> Result<TaskManager, ServiceError> {
 	let (client, backend, keystore_container, mut task_manager, on_demand) =
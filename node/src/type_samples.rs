@@ -0,0 +1,326 @@
+//! Encoded samples of the runtime types checked by `check-types`.
+//!
+//! Each declared type gets values built from the actual runtime type. `check-types` decodes them
+//! with the declared definition, which fails if a definition does not match the runtime.
+//! Structs are built with struct expressions and enums with `all_variants!`, so a new field or
+//! variant in a runtime type breaks the build until its samples, and then its definition, are
+//! updated.
+
+use codec::Encode;
+use logion_node_runtime::{
+	opaque::SessionKeys, AccountId, Address, Balance, BlockNumber, Hash, Index, Runtime,
+};
+use pallet_lo_fees_runtime_api::{FeeSchedule, LocOperation};
+use pallet_lo_identity::IdentityStorageVersion;
+use pallet_lo_node_authorization::NodeAuthorizationStorageVersion;
+use pallet_lo_profiles::ProfilesStorageVersion;
+use pallet_lo_profiles_runtime_api::LegalOfficerProfile;
+use pallet_loc_deposits::DepositsStorageVersion;
+use pallet_loc_escrow::{Escrow, EscrowAsset, EscrowId, EscrowOf};
+use pallet_logion_loc::{
+	CollectionItem, CollectionSize, File, LegalOfficerCase, LegalOfficerCaseOf, LocLink, LocType,
+	LocVoidInfo, MetadataItem, Requester, StorageVersion,
+};
+use pallet_logion_randomness_runtime_api::{RandomOutput, RandomnessProvenance};
+use pallet_runtime_parameters::{Parameter, ParameterKey};
+use pallet_scheduled_upgrades::{PendingUpgrade, PendingUpgradeOf};
+use sp_core::{ed25519, sr25519, OpaquePeerId as PeerId};
+use sp_runtime::MultiAddress;
+
+type LocId = <Runtime as pallet_logion_loc::Config>::LocId;
+type CollectionItemId = <Runtime as pallet_logion_loc::Config>::CollectionItemId;
+type AssetId = <Runtime as pallet_assets::Config>::AssetId;
+type AssetBalance = <Runtime as pallet_assets::Config>::Balance;
+
+/// Samples of all variants of an enum, fails to compile if a variant is not listed.
+macro_rules! all_variants {
+	($($enum:ident)::+ { $($variant:ident => $sample:expr),+ $(,)? }) => {{
+		let samples = vec![$($sample),+];
+		for sample in samples.iter() {
+			match sample {
+				$( $($enum)::+::$variant { .. } => (), )+
+			}
+		}
+		samples
+	}};
+}
+
+fn encoded<E: Encode>(samples: Vec<E>) -> Vec<Vec<u8>> {
+	samples.iter().map(Encode::encode).collect()
+}
+
+fn account(seed: u8) -> AccountId {
+	AccountId::from([seed; 32])
+}
+
+fn hash(seed: u8) -> Hash {
+	Hash::repeat_byte(seed)
+}
+
+fn addresses() -> Vec<Address> {
+	all_variants!(MultiAddress {
+		Id => MultiAddress::Id(account(1)),
+		Index => MultiAddress::Index(42),
+		Raw => MultiAddress::Raw(vec![1, 2, 3]),
+		Address32 => MultiAddress::Address32([2; 32]),
+		Address20 => MultiAddress::Address20([3; 20]),
+	})
+}
+
+fn requesters() -> Vec<Requester<AccountId, LocId>> {
+	all_variants!(Requester {
+		None => Requester::None,
+		Account => Requester::Account(account(2)),
+		Loc => Requester::Loc(1),
+	})
+}
+
+fn loc_types() -> Vec<LocType> {
+	all_variants!(LocType {
+		Transaction => LocType::Transaction,
+		Identity => LocType::Identity,
+		Collection => LocType::Collection,
+	})
+}
+
+fn metadata_item() -> MetadataItem<AccountId> {
+	MetadataItem {
+		name: b"name".to_vec(),
+		value: b"value".to_vec(),
+		submitter: account(3),
+	}
+}
+
+fn file() -> File<Hash, AccountId> {
+	File {
+		hash: hash(1),
+		nature: b"nature".to_vec(),
+		submitter: account(3),
+	}
+}
+
+fn link() -> LocLink<LocId> {
+	LocLink {
+		id: 2,
+		nature: b"nature".to_vec(),
+	}
+}
+
+fn void_info() -> LocVoidInfo<LocId> {
+	LocVoidInfo {
+		replacer: Some(3),
+	}
+}
+
+fn legal_officer_cases() -> Vec<LegalOfficerCaseOf<Runtime>> {
+	requesters().into_iter().zip(loc_types().into_iter().cycle())
+		.map(|(requester, loc_type)| LegalOfficerCase {
+			owner: account(1),
+			requester,
+			metadata: vec![metadata_item()],
+			files: vec![file()],
+			closed: true,
+			loc_type,
+			links: vec![link()],
+			void_info: Some(void_info()),
+			replacer_of: Some(4),
+			collection_last_block_submission: Some(100),
+			collection_max_size: Some(10),
+		})
+		.collect()
+}
+
+fn storage_versions() -> Vec<StorageVersion> {
+	all_variants!(StorageVersion {
+		V1 => StorageVersion::V1,
+		V2MakeLocVoid => StorageVersion::V2MakeLocVoid,
+		V3RequesterEnum => StorageVersion::V3RequesterEnum,
+		V4ItemSubmitter => StorageVersion::V4ItemSubmitter,
+		V5Collection => StorageVersion::V5Collection,
+	})
+}
+
+fn session_keys() -> SessionKeys {
+	SessionKeys {
+		aura: sr25519::Public::from_raw([1; 32]).into(),
+		grandpa: ed25519::Public::from_raw([2; 32]).into(),
+	}
+}
+
+fn account_info() -> frame_system::AccountInfo<Index, pallet_balances::AccountData<Balance>> {
+	frame_system::AccountInfo {
+		nonce: 1,
+		consumers: 2,
+		providers: 3,
+		data: pallet_balances::AccountData {
+			free: 4,
+			reserved: 5,
+			misc_frozen: 6,
+			fee_frozen: 7,
+		},
+	}
+}
+
+fn randomness_provenance() -> RandomnessProvenance<BlockNumber, AccountId> {
+	RandomnessProvenance {
+		round: 1,
+		closed_at: 10,
+		contributors: vec![account(1), account(2)],
+	}
+}
+
+fn random_output() -> RandomOutput<Hash, BlockNumber, AccountId> {
+	RandomOutput {
+		random: hash(2),
+		provenance: randomness_provenance(),
+	}
+}
+
+fn legal_officer_profile() -> LegalOfficerProfile {
+	LegalOfficerProfile {
+		display_name: b"Patrick".to_vec(),
+		region: b"Belgium".to_vec(),
+		base_url: b"https://logion.network".to_vec(),
+		node_id: Some(PeerId(vec![1, 2, 3])),
+		encryption_key: vec![4, 5, 6],
+	}
+}
+
+fn deposits_storage_versions() -> Vec<DepositsStorageVersion> {
+	all_variants!(DepositsStorageVersion {
+		V0NoDeposit => DepositsStorageVersion::V0NoDeposit,
+		V1Deposits => DepositsStorageVersion::V1Deposits,
+	})
+}
+
+fn profiles_storage_versions() -> Vec<ProfilesStorageVersion> {
+	all_variants!(ProfilesStorageVersion {
+		V0LegalOfficerSet => ProfilesStorageVersion::V0LegalOfficerSet,
+		V1Profiles => ProfilesStorageVersion::V1Profiles,
+	})
+}
+
+fn node_authorization_storage_versions() -> Vec<NodeAuthorizationStorageVersion> {
+	all_variants!(NodeAuthorizationStorageVersion {
+		V0NoNodeCount => NodeAuthorizationStorageVersion::V0NoNodeCount,
+		V1NodeCount => NodeAuthorizationStorageVersion::V1NodeCount,
+	})
+}
+
+fn identity_storage_versions() -> Vec<IdentityStorageVersion> {
+	all_variants!(IdentityStorageVersion {
+		V0NoRegistrar => IdentityStorageVersion::V0NoRegistrar,
+		V1Registrars => IdentityStorageVersion::V1Registrars,
+	})
+}
+
+fn escrow_assets() -> Vec<EscrowAsset<AssetId>> {
+	all_variants!(EscrowAsset {
+		Native => EscrowAsset::Native,
+		Asset => EscrowAsset::Asset(1),
+	})
+}
+
+fn escrows() -> Vec<EscrowOf<Runtime>> {
+	escrow_assets().into_iter()
+		.map(|asset| Escrow {
+			loc_id: 1,
+			depositor: account(1),
+			beneficiary: account(2),
+			asset,
+			remaining: 100,
+			deadline: 1000,
+		})
+		.collect()
+}
+
+fn fee_schedule() -> FeeSchedule<Balance> {
+	FeeSchedule {
+		open: 1,
+		per_item: 2,
+		close: 3,
+	}
+}
+
+fn loc_operations() -> Vec<LocOperation> {
+	all_variants!(LocOperation {
+		Open => LocOperation::Open,
+		AddItem => LocOperation::AddItem,
+		Close => LocOperation::Close,
+	})
+}
+
+fn parameter_keys() -> Vec<ParameterKey> {
+	all_variants!(ParameterKey {
+		MaxMetadataItemValueSize => ParameterKey::MaxMetadataItemValueSize,
+		MaxCollectionItemDescriptionSize => ParameterKey::MaxCollectionItemDescriptionSize,
+		MultiSigDepositBase => ParameterKey::MultiSigDepositBase,
+		RecoveryDeposit => ParameterKey::RecoveryDeposit,
+		TransactionByteFee => ParameterKey::TransactionByteFee,
+	})
+}
+
+fn parameters() -> Vec<Parameter<Balance>> {
+	all_variants!(Parameter {
+		MaxMetadataItemValueSize => Parameter::MaxMetadataItemValueSize(1),
+		MaxCollectionItemDescriptionSize => Parameter::MaxCollectionItemDescriptionSize(2),
+		MultiSigDepositBase => Parameter::MultiSigDepositBase(3),
+		RecoveryDeposit => Parameter::RecoveryDeposit(4),
+		TransactionByteFee => Parameter::TransactionByteFee(5),
+	})
+}
+
+fn pending_upgrade() -> PendingUpgradeOf<Runtime> {
+	PendingUpgrade {
+		code_hash: hash(3),
+		at: 1000,
+	}
+}
+
+/// Encoded samples of each exported type, by type name.
+pub fn samples() -> Vec<(&'static str, Vec<Vec<u8>>)> {
+	let asset_id: AssetId = 1;
+	let asset_balance: AssetBalance = 1;
+	let loc_id: LocId = 1;
+	let collection_size: CollectionSize = 1;
+	let collection_item_id: CollectionItemId = hash(4);
+	let escrow_id: EscrowId = 1;
+	vec![
+		("Address", encoded(addresses())),
+		("LookupSource", encoded(addresses())),
+		("PeerId", encoded(vec![PeerId(vec![1, 2, 3])])),
+		("AccountInfo", encoded(vec![account_info()])),
+		("TAssetBalance", encoded(vec![asset_balance])),
+		("AssetId", encoded(vec![asset_id])),
+		("AssetDetails", encoded(vec![pallet_assets::AssetDetails::<AssetBalance, AccountId, Balance>::default()])),
+		("AssetMetadata", encoded(vec![pallet_assets::AssetMetadata::<Balance>::default()])),
+		("LocId", encoded(vec![loc_id])),
+		("LegalOfficerCaseOf", encoded(legal_officer_cases())),
+		("MetadataItem", encoded(vec![metadata_item()])),
+		("LocType", encoded(loc_types())),
+		("LocLink", encoded(vec![link()])),
+		("File", encoded(vec![file()])),
+		("LocVoidInfo", encoded(vec![void_info()])),
+		("StorageVersion", encoded(storage_versions())),
+		("Requester", encoded(requesters())),
+		("Keys", encoded(vec![session_keys()])),
+		("CollectionSize", encoded(vec![collection_size])),
+		("CollectionItemId", encoded(vec![collection_item_id])),
+		("CollectionItem", encoded(vec![CollectionItem { description: b"description".to_vec() }])),
+		("RandomnessProvenance", encoded(vec![randomness_provenance()])),
+		("RandomOutput", encoded(vec![random_output()])),
+		("LegalOfficerProfile", encoded(vec![legal_officer_profile()])),
+		("DepositsStorageVersion", encoded(deposits_storage_versions())),
+		("EscrowId", encoded(vec![escrow_id])),
+		("EscrowAsset", encoded(escrow_assets())),
+		("EscrowOf", encoded(escrows())),
+		("FeeSchedule", encoded(vec![fee_schedule()])),
+		("LocOperation", encoded(loc_operations())),
+		("ParameterKey", encoded(parameter_keys())),
+		("Parameter", encoded(parameters())),
+		("PendingUpgrade", encoded(vec![pending_upgrade()])),
+		("ProfilesStorageVersion", encoded(profiles_storage_versions())),
+		("NodeAuthorizationStorageVersion", encoded(node_authorization_storage_versions())),
+		("IdentityStorageVersion", encoded(identity_storage_versions())),
+	]
+}
@@ -0,0 +1,224 @@
+//! `validator` subcommands: onboarding of new validators.
+//!
+//! `validator onboard` generates the Aura and GRANDPA keys of a new validator into its keystore,
+//! derives its peer ID from its node key and prints:
+//! * a `session.setKeys` extrinsic and a `loNodeAuthorization.claimNode` extrinsic signed offline
+//!   by the validator account (a legal officer), to be submitted with `author_submitExtrinsic`;
+//! * a proposal for the sudo key adding the account to the validator set and claiming the node on
+//!   behalf of the account, the signed `claimNode` being the alternative for legal officers operating
+//!   the node themselves.
+
+use std::sync::Arc;
+use codec::Encode;
+use logion_node_runtime::{
+	opaque::SessionKeys, AccountId, Address, Call, Hash, Runtime, SignedExtra, SignedPayload,
+	UncheckedExtrinsic,
+};
+use sc_cli::{
+	utils, CliConfiguration, KeystoreParams, NodeKeyParams, SharedParams, SubstrateCli,
+};
+use sc_keystore::LocalKeystore;
+use sc_service::config::{BasePath, KeystoreConfig};
+use serde_json::json;
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_core::{
+	bytes::to_hex, crypto::{key_types, Ss58Codec}, sr25519, OpaquePeerId, Pair, Public,
+};
+use sp_finality_grandpa::AuthorityId as GrandpaId;
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+use sp_runtime::{generic::Era, traits::IdentifyAccount, MultiSigner};
+use structopt::StructOpt;
+
+/// The directory of the network configuration, holding the node key by default.
+const NETWORK_CONFIG_PATH: &str = "network";
+
+/// The `validator` subcommands.
+#[derive(Debug, StructOpt)]
+pub enum ValidatorCmd {
+	/// Generate the keys of a new validator, sign its `session.setKeys` and `claimNode` extrinsics
+	/// and produce the proposal adding it to the validator set.
+	Onboard(OnboardCmd),
+}
+
+impl ValidatorCmd {
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> sc_cli::Result<()> {
+		match self {
+			ValidatorCmd::Onboard(cmd) => cmd.run(cli),
+		}
+	}
+}
+
+/// The `validator onboard` command.
+#[derive(Debug, StructOpt)]
+pub struct OnboardCmd {
+	/// The secret URI of the validator account, signing the extrinsics. Prompted if not given.
+	#[structopt(long)]
+	pub suri: Option<String>,
+
+	/// The hash of the genesis block of the chain (see `chain_getBlockHash(0)`).
+	#[structopt(long)]
+	pub genesis_hash: Hash,
+
+	/// The `specVersion` of the runtime of the chain (see `state_getRuntimeVersion`).
+	#[structopt(long)]
+	pub spec_version: u32,
+
+	/// The `transactionVersion` of the runtime of the chain (see `state_getRuntimeVersion`).
+	#[structopt(long)]
+	pub tx_version: u32,
+
+	/// The nonce of the validator account.
+	#[structopt(long, default_value = "0")]
+	pub nonce: u32,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub keystore_params: KeystoreParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub node_key_params: NodeKeyParams,
+}
+
+impl OnboardCmd {
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> sc_cli::Result<()> {
+		let suri = utils::read_uri(self.suri.as_ref())?;
+		let account_pair = sr25519::Pair::from_string(&suri, None)
+			.map_err(|e| format!("Invalid validator secret URI: {:?}", e))?;
+		let account: AccountId = MultiSigner::from(account_pair.public()).into_account();
+
+		let base_path = self.shared_params.base_path()
+			.unwrap_or_else(|| BasePath::from_project("", "", &C::executable_name()));
+		let chain_id = self.shared_params.chain_id(self.shared_params.is_dev());
+		let chain_spec = cli.load_spec(&chain_id)?;
+		let config_dir = base_path.config_dir(chain_spec.id());
+
+		let keystore: SyncCryptoStorePtr = match self.keystore_params.keystore_config(&config_dir)? {
+			(_, KeystoreConfig::Path { path, password }) => Arc::new(LocalKeystore::open(path, password)?),
+			_ => unreachable!("keystore_config always returns path and password; qed"),
+		};
+		let aura = SyncCryptoStore::sr25519_generate_new(&*keystore, key_types::AURA, None)
+			.map_err(|e| format!("Unable to generate Aura key: {:?}", e))?;
+		let grandpa = SyncCryptoStore::ed25519_generate_new(&*keystore, key_types::GRANDPA, None)
+			.map_err(|e| format!("Unable to generate GRANDPA key: {:?}", e))?;
+		let session_keys = SessionKeys {
+			aura: AuraId::from(aura),
+			grandpa: GrandpaId::from(grandpa),
+		};
+
+		let peer_id = self.node_key_params.node_key(&config_dir.join(NETWORK_CONFIG_PATH))?
+			.into_keypair()?
+			.public()
+			.into_peer_id();
+
+		let set_keys = self.signed_extrinsic(
+			&account_pair,
+			account.clone(),
+			Call::Session(pallet_session::Call::set_keys(session_keys.clone(), Vec::new())),
+			self.nonce,
+		);
+		let claim_node_call = Call::LoNodeAuthorization(
+			pallet_lo_node_authorization::Call::claim_node(OpaquePeerId(peer_id.to_bytes())));
+		let claim_node = self.signed_extrinsic(
+			&account_pair,
+			account.clone(),
+			claim_node_call.clone(),
+			self.nonce + 1,
+		);
+
+		// Root may not add well-known nodes, the node is claimed on behalf of the account instead.
+		// `sudo` and `sudo_as` succeed whatever the result of the wrapped call, a node already claimed
+		// with `claimNode` does not prevent the account from being added to the validator set.
+		let add_validator = Call::ValidatorSet(pallet_validator_set::Call::add_validator(account.clone()));
+		let batch = Call::Utility(pallet_utility::Call::batch_all(vec![
+			Call::Sudo(pallet_sudo::Call::sudo(Box::new(add_validator.clone()))),
+			Call::Sudo(pallet_sudo::Call::sudo_as(Address::Id(account.clone()), Box::new(claim_node_call.clone()))),
+		]));
+		let proposal = json!({
+			"description": format!("Onboard validator {} running node {}", account.to_ss58check(), peer_id),
+			"calls": [
+				{
+					"pallet": "ValidatorSet",
+					"call": "add_validator",
+					"origin": "Root",
+					"args": { "validator_id": account.to_ss58check() },
+					"encoded": to_hex(&add_validator.encode(), false),
+				},
+				{
+					"pallet": "LoNodeAuthorization",
+					"call": "claim_node",
+					"origin": account.to_ss58check(),
+					"args": { "node": peer_id.to_base58() },
+					"encoded": to_hex(&claim_node_call.encode(), false),
+				},
+			],
+			"sudo": to_hex(&batch.encode(), false),
+		});
+
+		let output = json!({
+			"account": account.to_ss58check(),
+			"peerId": peer_id.to_base58(),
+			"sessionKeys": {
+				"aura": to_hex(&session_keys.aura.to_raw_vec(), false),
+				"grandpa": to_hex(&session_keys.grandpa.to_raw_vec(), false),
+			},
+			"setKeys": to_hex(&set_keys.encode(), false),
+			"claimNode": to_hex(&claim_node.encode(), false),
+			"proposal": proposal,
+		});
+		println!("{}", serde_json::to_string_pretty(&output)
+			.map_err(|e| format!("Unable to serialize output: {}", e))?);
+		Ok(())
+	}
+
+	/// Builds an immortal extrinsic signed by the validator account.
+	fn signed_extrinsic(&self, pair: &sr25519::Pair, account: AccountId, call: Call, nonce: u32) -> UncheckedExtrinsic {
+		let extra: SignedExtra = (
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(Era::Immortal),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_asset_fee_payment::ChargeAssetTxPayment::<Runtime>::from(0, None),
+			pallet_lo_fees::ChargeLocFees::<Runtime>::new(),
+			pallet_loc_deposits::ReserveLocDeposits::<Runtime>::new(),
+		);
+		let payload = SignedPayload::from_raw(
+			call,
+			extra,
+			(
+				self.spec_version,
+				self.tx_version,
+				self.genesis_hash,
+				self.genesis_hash,
+				(),
+				(),
+				(),
+				(),
+				(),
+			),
+		);
+		let signature = payload.using_encoded(|payload| pair.sign(payload));
+		let (call, extra, _) = payload.deconstruct();
+		UncheckedExtrinsic::new_signed(call, Address::Id(account), signature.into(), extra)
+	}
+}
+
+impl CliConfiguration for OnboardCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn keystore_params(&self) -> Option<&KeystoreParams> {
+		Some(&self.keystore_params)
+	}
+
+	fn node_key_params(&self) -> Option<&NodeKeyParams> {
+		Some(&self.node_key_params)
+	}
+}
@@ -0,0 +1,19 @@
+//! Runtime API definition for the asset fee payment pallet.
+//!
+//! Kept apart from `TransactionPaymentApi` whose fees are native amounts, see the pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::traits::MaybeDisplay;
+
+sp_api::decl_runtime_apis! {
+	pub trait AssetFeePaymentApi<AssetId, Balance> where
+		AssetId: Codec,
+		Balance: Codec + MaybeDisplay,
+	{
+		/// Returns the fee the sender of given extrinsic would pay in given asset,
+		/// `None` if the asset is not approved for fee payment.
+		fn query_asset_fee(uxt: Block::Extrinsic, len: u32, asset_id: AssetId) -> Option<Balance>;
+	}
+}
@@ -0,0 +1,336 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Transaction fee payment with `pallet_assets` tokens.
+//!
+//! Governance approves assets for fee payment by setting their conversion rate, i.e. the amount
+//! of asset units paying for one native unit. The `ChargeAssetTxPayment` signed extension then
+//! lets a sender pick an approved asset: the fee is computed by `pallet_transaction_payment`,
+//! converted and transferred to `FeeCollector`. When no asset is picked, the extension behaves
+//! exactly like `ChargeTransactionPayment`. Converted fees are rounded up, a transaction is never
+//! free because its fee is worth less than one asset unit.
+//!
+//! `pallet_assets` moves the whole balance of a sender left below the asset's minimum balance.
+//! Such payments are rejected so that paying a fee never reaps the payer.
+//!
+//! Payments with a regulated asset the fee collector is not allowed to receive are rejected as
+//! well, fee transfers not going through the runtime's call filter.
+//!
+//! Assets are only held by existing accounts, the fee collector is provided for by this pallet on
+//! its first asset fee.
+//!
+//! Fees in an asset are estimated by `AssetFeePaymentApi` rather than `TransactionPaymentApi`: the
+//! `partial_fee` returned by the latter is a native amount for wallets and its signature, defined
+//! upstream, does not take the asset to pay with. `payment_queryInfo` is thus left unchanged and
+//! still gives the native fee, which `AssetFeePaymentApi` converts like `ChargeAssetTxPayment` does.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::DispatchResult,
+	storage::with_transaction,
+	traits::Get,
+	weights::{DispatchInfo, PostDispatchInfo},
+};
+use frame_system::RawOrigin;
+use pallet_regulated_assets::AssetRecipients;
+use pallet_transaction_payment::{ChargeTransactionPayment, OnChargeTransaction};
+use sp_runtime::{
+	FixedPointNumber, FixedPointOperand, FixedU128, SaturatedConversion, TransactionOutcome,
+	traits::{CheckedMul, DispatchInfoOf, Dispatchable, PostDispatchInfoOf, Saturating, SignedExtension, StaticLookup, Zero},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError,
+		ValidTransaction,
+	},
+};
+use sp_std::prelude::*;
+
+pub type NativeBalanceOf<T> = <<T as pallet_transaction_payment::Config>::OnChargeTransaction
+	as OnChargeTransaction<T>>::Balance;
+
+pub type AssetBalanceOf<T> = <T as pallet_assets::Config>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_regulated_assets::AssetRecipients;
+	use sp_runtime::FixedU128;
+	use super::AssetBalanceOf;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_transaction_payment::Config + pallet_assets::Config {
+		/// The origin allowed to approve assets and set conversion rates
+		type RateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The account receiving fees paid with assets
+		type FeeCollector: Get<Self::AccountId>;
+
+		/// Recipients allowed for each asset
+		type AssetRecipients: AssetRecipients<Self::AssetId, Self::AccountId>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Approved assets and the number of asset units paying for one native unit
+	#[pallet::storage]
+	#[pallet::getter(fn conversion_rate)]
+	pub type ConversionRates<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, FixedU128>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", T::AssetId = "AssetId", AssetBalanceOf<T> = "Balance")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when the conversion rate of an asset is set. [asset, rate]
+		ConversionRateSet(T::AssetId, FixedU128),
+		/// Issued when an asset is not approved anymore. [asset]
+		ConversionRateRemoved(T::AssetId),
+		/// Issued when a fee is paid with an asset. [payer, asset, amount]
+		AssetFeePaid(T::AccountId, T::AssetId, AssetBalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Conversion rate must not be zero
+		ZeroRate,
+		/// Asset is not approved for fee payment
+		NotApproved,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Approves an asset for fee payment or updates its conversion rate.
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn set_conversion_rate(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			rate: FixedU128,
+		) -> DispatchResultWithPostInfo {
+			T::RateOrigin::ensure_origin(origin)?;
+			ensure!(!rate.is_zero(), Error::<T>::ZeroRate);
+
+			<ConversionRates<T>>::insert(&asset_id, rate);
+			Self::deposit_event(Event::ConversionRateSet(asset_id, rate));
+			Ok(().into())
+		}
+
+		/// Withdraws the approval of an asset for fee payment.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn remove_conversion_rate(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+		) -> DispatchResultWithPostInfo {
+			T::RateOrigin::ensure_origin(origin)?;
+			ensure!(<ConversionRates<T>>::contains_key(&asset_id), Error::<T>::NotApproved);
+
+			<ConversionRates<T>>::remove(&asset_id);
+			Self::deposit_event(Event::ConversionRateRemoved(asset_id));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Converts a native fee into given asset, rounding up, `None` if the asset is not approved.
+	pub fn asset_fee(asset_id: T::AssetId, native_fee: NativeBalanceOf<T>) -> Option<AssetBalanceOf<T>> {
+		let native_fee: u128 = native_fee.saturated_into();
+		Self::conversion_rate(asset_id).map(|rate| {
+			let fee = rate.saturating_mul_int(native_fee);
+			let has_fraction = FixedU128::checked_from_integer(native_fee)
+				.and_then(|native_fee| native_fee.checked_mul(&rate))
+				.map(|exact_fee| !exact_fee.frac().is_zero())
+				.unwrap_or(false);
+			let fee = if has_fraction { fee.saturating_add(1) } else { fee };
+			fee.saturated_into()
+		})
+	}
+
+	/// Transfers a fee from the payer to the fee collector. Nothing is transferred if the payer
+	/// would be left below the minimum balance of the asset.
+	fn pay(who: &T::AccountId, asset_id: T::AssetId, fee: AssetBalanceOf<T>) -> Result<(), TransactionValidityError> {
+		with_transaction(|| {
+			let collector = T::FeeCollector::get();
+			if !frame_system::Module::<T>::account_exists(&collector) {
+				frame_system::Module::<T>::inc_providers(&collector);
+			}
+			let before = pallet_assets::Module::<T>::balance(asset_id, who.clone());
+			let result = Self::transfer(who.clone(), asset_id, collector, fee);
+			let after = pallet_assets::Module::<T>::balance(asset_id, who.clone());
+			if result.is_ok() && before.saturating_sub(after) == fee {
+				TransactionOutcome::Commit(Ok(()))
+			} else {
+				TransactionOutcome::Rollback(Err(InvalidTransaction::Payment.into()))
+			}
+		})
+	}
+
+	fn transfer(from: T::AccountId, asset_id: T::AssetId, to: T::AccountId, amount: AssetBalanceOf<T>) -> DispatchResult {
+		pallet_assets::Module::<T>::transfer(
+			RawOrigin::Signed(from).into(),
+			asset_id,
+			T::Lookup::unlookup(to),
+			amount,
+		)
+	}
+}
+
+/// Requires the sender to pay a fee, either in native currency or in an approved asset,
+/// and optionally a tip.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeAssetTxPayment<T: Config> {
+	#[codec(compact)]
+	tip: NativeBalanceOf<T>,
+	asset_id: Option<T::AssetId>,
+}
+
+impl<T: Config> ChargeAssetTxPayment<T> where
+	NativeBalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+	T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo>,
+{
+	/// Utility constructor. Used only in client/factory code.
+	pub fn from(tip: NativeBalanceOf<T>, asset_id: Option<T::AssetId>) -> Self {
+		Self { tip, asset_id }
+	}
+
+	fn native(&self) -> ChargeTransactionPayment<T> {
+		ChargeTransactionPayment::<T>::from(self.tip)
+	}
+
+	fn asset_fee(&self, asset_id: T::AssetId, info: &DispatchInfoOf<T::Call>, len: usize)
+		-> Result<(NativeBalanceOf<T>, AssetBalanceOf<T>), TransactionValidityError>
+	{
+		let native_fee = pallet_transaction_payment::Module::<T>::compute_fee(len as u32, info, self.tip);
+		let asset_fee = Pallet::<T>::asset_fee(asset_id, native_fee)
+			.ok_or(InvalidTransaction::Custom(ASSET_NOT_APPROVED))?;
+		if !T::AssetRecipients::may_receive(&asset_id, &T::FeeCollector::get()) {
+			Err(InvalidTransaction::Custom(FEE_COLLECTOR_NOT_ALLOWED))?
+		}
+		Ok((native_fee, asset_fee))
+	}
+}
+
+/// Custom validity error: the asset is not approved for fee payment.
+pub const ASSET_NOT_APPROVED: u8 = 100;
+
+/// Custom validity error: the fee collector is not allowed to receive the asset.
+pub const FEE_COLLECTOR_NOT_ALLOWED: u8 = 101;
+
+impl<T: Config> sp_std::fmt::Debug for ChargeAssetTxPayment<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeAssetTxPayment<{:?}, {:?}>", self.tip, self.asset_id)
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+/// What was paid before dispatch: a native payment or an asset payment.
+pub enum InitialPayment<T: Config> where
+	NativeBalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+	T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo>,
+{
+	Native(<ChargeTransactionPayment<T> as SignedExtension>::Pre),
+	Asset(T::AccountId, T::AssetId, AssetBalanceOf<T>, NativeBalanceOf<T>),
+}
+
+impl<T: Config> SignedExtension for ChargeAssetTxPayment<T> where
+	NativeBalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+	T::Call: Dispatchable<Info=DispatchInfo, PostInfo=PostDispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "ChargeAssetTxPayment";
+	type AccountId = T::AccountId;
+	type Call = T::Call;
+	type AdditionalSigned = ();
+	type Pre = InitialPayment<T>;
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> { Ok(()) }
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		match self.asset_id {
+			None => self.native().validate(who, call, info, len),
+			Some(asset_id) => {
+				let (native_fee, asset_fee) = self.asset_fee(asset_id, info, len)?;
+				if !asset_fee.is_zero() {
+					with_transaction(|| TransactionOutcome::Rollback(Pallet::<T>::pay(who, asset_id, asset_fee)))?;
+				}
+				Ok(ValidTransaction {
+					priority: native_fee.saturated_into::<TransactionPriority>(),
+					..Default::default()
+				})
+			},
+		}
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		match self.asset_id {
+			None => self.native().pre_dispatch(who, call, info, len).map(InitialPayment::Native),
+			Some(asset_id) => {
+				let (_, asset_fee) = self.asset_fee(asset_id, info, len)?;
+				if !asset_fee.is_zero() {
+					Pallet::<T>::pay(who, asset_id, asset_fee)?;
+				}
+				Ok(InitialPayment::Asset(who.clone(), asset_id, asset_fee, self.tip))
+			},
+		}
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		match pre {
+			InitialPayment::Native(pre) =>
+				ChargeTransactionPayment::<T>::post_dispatch(pre, info, post_info, len, result),
+			InitialPayment::Asset(who, asset_id, paid, tip) => {
+				let actual_native_fee = pallet_transaction_payment::Module::<T>::compute_actual_fee(
+					len as u32, info, post_info, tip,
+				);
+				let actual_fee = Pallet::<T>::asset_fee(asset_id, actual_native_fee).unwrap_or(paid);
+				// Like `ChargeTransactionPayment`, the whole fee is kept if the refund fails.
+				let kept = if actual_fee < paid
+					&& Pallet::<T>::transfer(T::FeeCollector::get(), asset_id, who.clone(), paid - actual_fee).is_ok() {
+					actual_fee
+				} else {
+					paid
+				};
+				Pallet::<T>::deposit_event(Event::AssetFeePaid(who, asset_id, kept));
+				Ok(())
+			},
+		}
+	}
+}
@@ -0,0 +1,141 @@
+use crate as pallet_asset_fee_payment;
+use sp_core::H256;
+use frame_support::{parameter_types, weights::IdentityFee};
+use frame_system::EnsureRoot;
+use pallet_transaction_payment::CurrencyAdapter;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+};
+use frame_system as system;
+use pallet_regulated_assets::AssetRecipients;
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Module, Storage},
+		Assets: pallet_assets::{Module, Call, Storage, Event<T>},
+		AssetFeePayment: pallet_asset_fee_payment::{Module, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxLocks: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = MaxLocks;
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const TransactionByteFee: u64 = 1;
+}
+
+impl pallet_transaction_payment::Config for Test {
+	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type TransactionByteFee = TransactionByteFee;
+	type WeightToFee = IdentityFee<u64>;
+	type FeeMultiplierUpdate = ();
+}
+
+parameter_types! {
+	pub const AssetDepositBase: u64 = 0;
+	pub const AssetDepositPerZombie: u64 = 0;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: u64 = 0;
+	pub const MetadataDepositPerByte: u64 = 0;
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = u64;
+	type AssetId = u64;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDepositBase = AssetDepositBase;
+	type AssetDepositPerZombie = AssetDepositPerZombie;
+	type StringLimit = StringLimit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type WeightInfo = ();
+}
+
+pub const FEE_COLLECTOR: u64 = 100;
+
+parameter_types! {
+	pub const FeeCollector: u64 = FEE_COLLECTOR;
+}
+
+thread_local! {
+	static RESTRICTED_ASSET: RefCell<Option<u64>> = RefCell::new(None);
+}
+
+/// Forbids any account to receive given asset.
+pub fn restrict_asset(asset_id: u64) {
+	RESTRICTED_ASSET.with(|restricted| *restricted.borrow_mut() = Some(asset_id));
+}
+
+pub struct MockAssetRecipients;
+impl AssetRecipients<u64, u64> for MockAssetRecipients {
+	fn may_receive(asset: &u64, _account: &u64) -> bool {
+		RESTRICTED_ASSET.with(|restricted| *restricted.borrow() != Some(*asset))
+	}
+}
+
+impl pallet_asset_fee_payment::Config for Test {
+	type RateOrigin = EnsureRoot<u64>;
+	type FeeCollector = FeeCollector;
+	type AssetRecipients = MockAssetRecipients;
+	type Event = Event;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,147 @@
+use crate::{mock::*, ChargeAssetTxPayment, ASSET_NOT_APPROVED, FEE_COLLECTOR_NOT_ALLOWED, Event as AssetFeePaymentEvent};
+use frame_support::{assert_ok, weights::{DispatchInfo, Pays, PostDispatchInfo}};
+use frame_system::RawOrigin;
+use sp_runtime::{
+	FixedPointNumber, FixedU128,
+	traits::SignedExtension,
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+
+const ASSET_ID: u64 = 1;
+const OWNER: u64 = 1;
+const PAYER: u64 = 2;
+const MIN_BALANCE: u64 = 10;
+const LEN: usize = 10;
+
+fn setup_asset(payer_balance: u64) {
+	assert_ok!(Assets::force_create(RawOrigin::Root.into(), ASSET_ID, OWNER, 10, MIN_BALANCE));
+	assert_ok!(Assets::mint(Origin::signed(OWNER), ASSET_ID, PAYER, payer_balance));
+	assert_ok!(AssetFeePayment::set_conversion_rate(RawOrigin::Root.into(), ASSET_ID, FixedU128::saturating_from_integer(2)));
+}
+
+fn call() -> Call {
+	Call::System(frame_system::Call::remark(Vec::new()))
+}
+
+fn info() -> DispatchInfo {
+	DispatchInfo { weight: 100, ..Default::default() }
+}
+
+fn expected_fee(weight: u64) -> u64 {
+	let info = DispatchInfo { weight, ..Default::default() };
+	2 * TransactionPayment::compute_fee(LEN as u32, &info, 0)
+}
+
+fn extension() -> ChargeAssetTxPayment<Test> {
+	ChargeAssetTxPayment::from(0, Some(ASSET_ID))
+}
+
+#[test]
+fn it_rejects_unapproved_asset() {
+	new_test_ext().execute_with(|| {
+		let result = ChargeAssetTxPayment::<Test>::from(0, Some(2)).validate(&PAYER, &call(), &info(), LEN);
+		assert_eq!(result, Err(TransactionValidityError::Invalid(InvalidTransaction::Custom(ASSET_NOT_APPROVED))));
+	});
+}
+
+#[test]
+fn it_pays_fee_with_asset_and_refunds_excess() {
+	new_test_ext().execute_with(|| {
+		setup_asset(1_000_000_000_000);
+		let fee = expected_fee(100);
+		assert!(extension().validate(&PAYER, &call(), &info(), LEN).is_ok());
+
+		let pre = extension().pre_dispatch(&PAYER, &call(), &info(), LEN).unwrap();
+		assert_eq!(Assets::balance(ASSET_ID, FEE_COLLECTOR), fee);
+
+		let post_info = PostDispatchInfo { actual_weight: Some(50), pays_fee: Pays::Yes };
+		assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(pre, &info(), &post_info, LEN, &Ok(())));
+		let actual_fee = expected_fee(50);
+		assert_eq!(Assets::balance(ASSET_ID, FEE_COLLECTOR), actual_fee);
+		assert_eq!(Assets::balance(ASSET_ID, PAYER), 1_000_000_000_000 - actual_fee);
+		assert_eq!(System::events().pop().unwrap().event,
+			Event::pallet_asset_fee_payment(AssetFeePaymentEvent::AssetFeePaid(PAYER, ASSET_ID, actual_fee)));
+	});
+}
+
+#[test]
+fn it_rejects_insufficient_balance() {
+	new_test_ext().execute_with(|| {
+		setup_asset(MIN_BALANCE);
+		let result = extension().validate(&PAYER, &call(), &info(), LEN);
+		assert_eq!(result, Err(TransactionValidityError::Invalid(InvalidTransaction::Payment)));
+	});
+}
+
+#[test]
+fn it_does_not_reap_payer() {
+	new_test_ext().execute_with(|| {
+		setup_asset(1_000_000_000_000);
+		let fee = expected_fee(100);
+		let balance = fee + MIN_BALANCE - 1;
+		assert_ok!(Assets::burn(Origin::signed(OWNER), ASSET_ID, PAYER, 1_000_000_000_000 - balance));
+
+		let result = extension().validate(&PAYER, &call(), &info(), LEN);
+		assert_eq!(result, Err(TransactionValidityError::Invalid(InvalidTransaction::Payment)));
+		assert!(extension().pre_dispatch(&PAYER, &call(), &info(), LEN).is_err());
+		assert_eq!(Assets::balance(ASSET_ID, PAYER), balance);
+		assert_eq!(Assets::balance(ASSET_ID, FEE_COLLECTOR), 0);
+	});
+}
+
+#[test]
+fn it_rejects_asset_fee_collector_may_not_receive() {
+	new_test_ext().execute_with(|| {
+		setup_asset(1_000_000_000_000);
+		restrict_asset(ASSET_ID);
+
+		let expected = Err(TransactionValidityError::Invalid(InvalidTransaction::Custom(FEE_COLLECTOR_NOT_ALLOWED)));
+		assert_eq!(extension().validate(&PAYER, &call(), &info(), LEN), expected);
+		assert!(extension().pre_dispatch(&PAYER, &call(), &info(), LEN).is_err());
+		assert_eq!(Assets::balance(ASSET_ID, PAYER), 1_000_000_000_000);
+		assert_eq!(Assets::balance(ASSET_ID, FEE_COLLECTOR), 0);
+	});
+}
+
+#[test]
+fn it_rounds_converted_fee_up() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetFeePayment::set_conversion_rate(RawOrigin::Root.into(), ASSET_ID, FixedU128::saturating_from_rational(1, 3)));
+		assert_eq!(AssetFeePayment::asset_fee(ASSET_ID, 99), Some(33));
+		assert_eq!(AssetFeePayment::asset_fee(ASSET_ID, 100), Some(34));
+		assert_eq!(AssetFeePayment::asset_fee(ASSET_ID, 1), Some(1));
+		assert_eq!(AssetFeePayment::asset_fee(ASSET_ID, 0), Some(0));
+	});
+}
+
+#[test]
+fn it_keeps_whole_fee_if_refund_fails() {
+	new_test_ext().execute_with(|| {
+		setup_asset(1_000_000_000_000);
+		let fee = expected_fee(100);
+		let pre = extension().pre_dispatch(&PAYER, &call(), &info(), LEN).unwrap();
+		assert_ok!(Assets::freeze(Origin::signed(OWNER), ASSET_ID, FEE_COLLECTOR));
+
+		let post_info = PostDispatchInfo { actual_weight: Some(50), pays_fee: Pays::Yes };
+		assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(pre, &info(), &post_info, LEN, &Ok(())));
+		assert_eq!(Assets::balance(ASSET_ID, FEE_COLLECTOR), fee);
+		assert_eq!(Assets::balance(ASSET_ID, PAYER), 1_000_000_000_000 - fee);
+		assert_eq!(System::events().pop().unwrap().event,
+			Event::pallet_asset_fee_payment(AssetFeePaymentEvent::AssetFeePaid(PAYER, ASSET_ID, fee)));
+	});
+}
+
+#[test]
+fn it_provides_fee_collector_for_account_based_asset() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RawOrigin::Root.into(), ASSET_ID, OWNER, 0, MIN_BALANCE));
+		System::inc_providers(&PAYER);
+		assert_ok!(Assets::mint(Origin::signed(OWNER), ASSET_ID, PAYER, 1_000_000_000_000));
+		assert_ok!(AssetFeePayment::set_conversion_rate(RawOrigin::Root.into(), ASSET_ID, FixedU128::saturating_from_integer(2)));
+		assert!(!System::account_exists(&FEE_COLLECTOR));
+
+		assert!(extension().pre_dispatch(&PAYER, &call(), &info(), LEN).is_ok());
+		assert!(System::account_exists(&FEE_COLLECTOR));
+		assert_eq!(Assets::balance(ASSET_ID, FEE_COLLECTOR), expected_fee(100));
+	});
+}
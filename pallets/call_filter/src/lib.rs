@@ -0,0 +1,180 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! On-chain call filter.
+//!
+//! Governance may pause a whole pallet or a single call of a pallet. Pallets and calls are
+//! identified by the names exposed through `GetCallMetadata`. This pallet only holds the state,
+//! the runtime consults it from its `BaseCallFilter`.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		traits::{CallMetadata, GetCallMetadata},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The runtime call type, giving the names of existing pallets and calls
+		type Call: GetCallMetadata;
+
+		/// The origin allowed to pause and unpause pallets and calls
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Pallets which must never be paused
+		type UnpausablePallets: Get<Vec<Vec<u8>>>;
+
+		/// The maximum length of a pallet or call name
+		type MaxNameLength: Get<u32>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Paused pallets
+	#[pallet::storage]
+	#[pallet::getter(fn paused_pallet)]
+	pub type PausedPallets<T> = StorageMap<_, Blake2_128Concat, Vec<u8>, (), OptionQuery>;
+
+	/// Paused calls, identified by pallet name and call name
+	#[pallet::storage]
+	#[pallet::getter(fn paused_call)]
+	pub type PausedCalls<T> = StorageDoubleMap<_, Blake2_128Concat, Vec<u8>, Blake2_128Concat, Vec<u8>, (), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when a pallet is paused. [pallet]
+		PalletPaused(Vec<u8>),
+		/// Issued when a pallet is unpaused. [pallet]
+		PalletUnpaused(Vec<u8>),
+		/// Issued when a call is paused. [pallet, call]
+		CallPaused(Vec<u8>, Vec<u8>),
+		/// Issued when a call is unpaused. [pallet, call]
+		CallUnpaused(Vec<u8>, Vec<u8>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The pallet is not allowed to be paused
+		Unpausable,
+		/// A name exceeds the maximum length
+		NameTooLong,
+		/// The pallet or call is already paused
+		AlreadyPaused,
+		/// The pallet or call is not paused
+		NotPaused,
+		/// No pallet with this name exists in the runtime
+		UnknownPallet,
+		/// No call with this name exists in the pallet
+		UnknownCall,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Pauses all calls of a pallet.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn pause_pallet(
+			origin: OriginFor<T>,
+			pallet: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			Self::ensure_pausable(&pallet)?;
+			ensure!(!<PausedPallets<T>>::contains_key(&pallet), Error::<T>::AlreadyPaused);
+
+			<PausedPallets<T>>::insert(&pallet, ());
+			Self::deposit_event(Event::PalletPaused(pallet));
+			Ok(().into())
+		}
+
+		/// Unpauses a pallet.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn unpause_pallet(
+			origin: OriginFor<T>,
+			pallet: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(<PausedPallets<T>>::contains_key(&pallet), Error::<T>::NotPaused);
+
+			<PausedPallets<T>>::remove(&pallet);
+			Self::deposit_event(Event::PalletUnpaused(pallet));
+			Ok(().into())
+		}
+
+		/// Pauses a single call of a pallet.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn pause_call(
+			origin: OriginFor<T>,
+			pallet: Vec<u8>,
+			call: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			let pallet_name = Self::ensure_pausable(&pallet)?;
+			ensure!(call.len() <= T::MaxNameLength::get() as usize, Error::<T>::NameTooLong);
+			ensure!(<T as Config>::Call::get_call_names(pallet_name).iter().any(|name| name.as_bytes() == call.as_slice()),
+				Error::<T>::UnknownCall);
+			ensure!(!<PausedCalls<T>>::contains_key(&pallet, &call), Error::<T>::AlreadyPaused);
+
+			<PausedCalls<T>>::insert(&pallet, &call, ());
+			Self::deposit_event(Event::CallPaused(pallet, call));
+			Ok(().into())
+		}
+
+		/// Unpauses a single call of a pallet.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn unpause_call(
+			origin: OriginFor<T>,
+			pallet: Vec<u8>,
+			call: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(<PausedCalls<T>>::contains_key(&pallet, &call), Error::<T>::NotPaused);
+
+			<PausedCalls<T>>::remove(&pallet, &call);
+			Self::deposit_event(Event::CallUnpaused(pallet, call));
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Checks that given pallet exists and may be paused, returns its name as exposed by the runtime.
+		fn ensure_pausable(pallet: &Vec<u8>) -> Result<&'static str, Error<T>> {
+			ensure!(pallet.len() <= T::MaxNameLength::get() as usize, Error::<T>::NameTooLong);
+			let pallet_name = <T as Config>::Call::get_module_names().iter()
+				.find(|name| name.as_bytes() == pallet.as_slice())
+				.copied()
+				.ok_or(Error::<T>::UnknownPallet)?;
+			ensure!(!T::UnpausablePallets::get().contains(pallet), Error::<T>::Unpausable);
+			Ok(pallet_name)
+		}
+
+		/// Tells if the call described by given metadata was paused by governance.
+		pub fn is_paused(metadata: &CallMetadata) -> bool {
+			let pallet = metadata.pallet_name.as_bytes();
+			if T::UnpausablePallets::get().iter().any(|name| name.as_slice() == pallet) {
+				return false;
+			}
+			<PausedPallets<T>>::contains_key(pallet)
+				|| <PausedCalls<T>>::contains_key(pallet, metadata.function_name.as_bytes())
+		}
+	}
+}
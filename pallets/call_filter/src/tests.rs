@@ -0,0 +1,123 @@
+use crate::{mock::*, Error, Event as CallFilterEvent};
+use frame_support::{assert_err, assert_ok, traits::{Get, GetCallMetadata}};
+use sp_runtime::DispatchError;
+
+const SYSTEM: &[u8] = b"System";
+const REMARK: &[u8] = b"remark";
+const USER: u64 = 1;
+
+fn last_event() -> Event {
+	System::events().pop().unwrap().event
+}
+
+fn remark_paused() -> bool {
+	CallFilter::is_paused(&Call::System(frame_system::Call::remark(Vec::new())).get_call_metadata())
+}
+
+fn set_heap_pages_paused() -> bool {
+	CallFilter::is_paused(&Call::System(frame_system::Call::set_heap_pages(0)).get_call_metadata())
+}
+
+#[test]
+fn it_pauses_and_unpauses_pallet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CallFilter::pause_pallet(Origin::root(), SYSTEM.to_vec()));
+		assert_eq!(last_event(), Event::pallet_call_filter(CallFilterEvent::PalletPaused(SYSTEM.to_vec())));
+		assert!(remark_paused());
+		assert!(set_heap_pages_paused());
+
+		assert_ok!(CallFilter::unpause_pallet(Origin::root(), SYSTEM.to_vec()));
+		assert_eq!(last_event(), Event::pallet_call_filter(CallFilterEvent::PalletUnpaused(SYSTEM.to_vec())));
+		assert!(!remark_paused());
+		assert!(!set_heap_pages_paused());
+	});
+}
+
+#[test]
+fn it_pauses_and_unpauses_call() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CallFilter::pause_call(Origin::root(), SYSTEM.to_vec(), REMARK.to_vec()));
+		assert_eq!(last_event(), Event::pallet_call_filter(CallFilterEvent::CallPaused(SYSTEM.to_vec(), REMARK.to_vec())));
+		assert!(remark_paused());
+		assert!(!set_heap_pages_paused());
+
+		assert_ok!(CallFilter::unpause_call(Origin::root(), SYSTEM.to_vec(), REMARK.to_vec()));
+		assert_eq!(last_event(), Event::pallet_call_filter(CallFilterEvent::CallUnpaused(SYSTEM.to_vec(), REMARK.to_vec())));
+		assert!(!remark_paused());
+	});
+}
+
+#[test]
+fn it_fails_pausing_twice() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CallFilter::pause_pallet(Origin::root(), SYSTEM.to_vec()));
+		assert_err!(CallFilter::pause_pallet(Origin::root(), SYSTEM.to_vec()), Error::<Test>::AlreadyPaused);
+
+		assert_ok!(CallFilter::pause_call(Origin::root(), SYSTEM.to_vec(), REMARK.to_vec()));
+		assert_err!(CallFilter::pause_call(Origin::root(), SYSTEM.to_vec(), REMARK.to_vec()), Error::<Test>::AlreadyPaused);
+	});
+}
+
+#[test]
+fn it_fails_unpausing_if_not_paused() {
+	new_test_ext().execute_with(|| {
+		assert_err!(CallFilter::unpause_pallet(Origin::root(), SYSTEM.to_vec()), Error::<Test>::NotPaused);
+		assert_err!(CallFilter::unpause_call(Origin::root(), SYSTEM.to_vec(), REMARK.to_vec()), Error::<Test>::NotPaused);
+	});
+}
+
+#[test]
+fn it_rejects_unknown_pallet() {
+	new_test_ext().execute_with(|| {
+		assert_err!(CallFilter::pause_pallet(Origin::root(), b"Unknown".to_vec()), Error::<Test>::UnknownPallet);
+		assert_err!(CallFilter::pause_call(Origin::root(), b"Unknown".to_vec(), REMARK.to_vec()), Error::<Test>::UnknownPallet);
+		assert_err!(CallFilter::pause_pallet(Origin::root(), b"system".to_vec()), Error::<Test>::UnknownPallet);
+	});
+}
+
+#[test]
+fn it_rejects_unknown_call() {
+	new_test_ext().execute_with(|| {
+		assert_err!(CallFilter::pause_call(Origin::root(), SYSTEM.to_vec(), b"unknown".to_vec()), Error::<Test>::UnknownCall);
+		assert_err!(CallFilter::pause_call(Origin::root(), SYSTEM.to_vec(), b"pause_pallet".to_vec()), Error::<Test>::UnknownCall);
+		assert!(!remark_paused());
+	});
+}
+
+#[test]
+fn it_rejects_too_long_names() {
+	new_test_ext().execute_with(|| {
+		let name = vec![b'a'; MaxNameLength::get() as usize + 1];
+		assert_err!(CallFilter::pause_pallet(Origin::root(), name.clone()), Error::<Test>::NameTooLong);
+		assert_err!(CallFilter::pause_call(Origin::root(), SYSTEM.to_vec(), name), Error::<Test>::NameTooLong);
+	});
+}
+
+#[test]
+fn it_refuses_pausing_unpausable_pallet() {
+	new_test_ext().execute_with(|| {
+		assert_err!(CallFilter::pause_pallet(Origin::root(), b"CallFilter".to_vec()), Error::<Test>::Unpausable);
+		assert_err!(CallFilter::pause_call(Origin::root(), b"CallFilter".to_vec(), b"unpause_pallet".to_vec()), Error::<Test>::Unpausable);
+	});
+}
+
+#[test]
+fn it_never_reports_unpausable_pallet_as_paused() {
+	new_test_ext().execute_with(|| {
+		crate::PausedPallets::<Test>::insert(b"CallFilter".to_vec(), ());
+		let unpause = Call::CallFilter(crate::Call::unpause_pallet(SYSTEM.to_vec()));
+		assert!(!CallFilter::is_paused(&unpause.get_call_metadata()));
+	});
+}
+
+#[test]
+fn it_fails_if_not_pause_origin() {
+	new_test_ext().execute_with(|| {
+		assert_err!(CallFilter::pause_pallet(Origin::signed(USER), SYSTEM.to_vec()), DispatchError::BadOrigin);
+		assert_err!(CallFilter::pause_call(Origin::signed(USER), SYSTEM.to_vec(), REMARK.to_vec()), DispatchError::BadOrigin);
+
+		assert_ok!(CallFilter::pause_pallet(Origin::root(), SYSTEM.to_vec()));
+		assert_err!(CallFilter::unpause_pallet(Origin::signed(USER), SYSTEM.to_vec()), DispatchError::BadOrigin);
+		assert!(remark_paused());
+	});
+}
@@ -0,0 +1,98 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Development fixtures created at genesis.
+//!
+//! Vault operations waiting for approval and recovery configurations cannot be put in genesis
+//! storage without reserving the related deposits. This pallet creates them by dispatching the
+//! calls a wallet would submit so that development chains start with realistic state. Those calls
+//! are dispatched directly, without going through the runtime's call filter, so the runtime only
+//! includes this pallet when built with the `dev-fixtures` feature.
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use sp_std::prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_multisig::Config + pallet_recovery::Config {}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Vault transfers waiting for approval: requester, legal officers and hash of the call,
+		/// the requester being the first approver of a 2-of-3 multisig. The vault accounts must
+		/// be funded, see `vault_account`
+		pub vault_calls: Vec<(T::AccountId, Vec<T::AccountId>, [u8; 32])>,
+		/// Recovery configurations: account, legal officers, threshold and delay
+		pub recoveries: Vec<(T::AccountId, Vec<T::AccountId>, u16, T::BlockNumber)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				vault_calls: Vec::new(),
+				recoveries: Vec::new(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (requester, legal_officers, call_hash) in self.vault_calls.iter() {
+				let mut other_signatories = legal_officers.clone();
+				other_signatories.sort();
+				created("vault call", pallet_multisig::Module::<T>::approve_as_multi(
+					RawOrigin::Signed(requester.clone()).into(),
+					VAULT_THRESHOLD,
+					other_signatories,
+					None,
+					*call_hash,
+					0,
+				));
+			}
+			for (account, legal_officers, threshold, delay_period) in self.recoveries.iter() {
+				let mut friends = legal_officers.clone();
+				friends.sort();
+				created("recovery", pallet_recovery::Module::<T>::create_recovery(
+					RawOrigin::Signed(account.clone()).into(),
+					friends,
+					*threshold,
+					*delay_period,
+				));
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+
+	/// The number of approvals of a vault operation.
+	pub const VAULT_THRESHOLD: u16 = 2;
+
+	impl<T: Config> Pallet<T> {
+		/// The account of the vault of a requester, to be funded at genesis.
+		pub fn vault_account(requester: &T::AccountId, legal_officers: &[T::AccountId]) -> T::AccountId {
+			let mut signatories = legal_officers.to_vec();
+			signatories.push(requester.clone());
+			signatories.sort();
+			pallet_multisig::Module::<T>::multi_account_id(&signatories, VAULT_THRESHOLD)
+		}
+	}
+
+	fn created<R, E: sp_std::fmt::Debug>(fixture: &str, result: Result<R, E>) {
+		if let Err(error) = result {
+			panic!("Unable to create {} fixture: {:?}", fixture, error);
+		}
+	}
+}
@@ -0,0 +1,40 @@
+//! Runtime API definition for the legal officer fees pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::traits::MaybeDisplay;
+
+/// The fees a legal officer charges to requesters of its LOCs.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FeeSchedule<Balance> {
+	pub open: Balance,
+	pub per_item: Balance,
+	pub close: Balance,
+}
+
+/// A LOC operation bearing a fee.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum LocOperation {
+	Open,
+	AddItem,
+	Close,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait LocFeesApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec + MaybeDisplay,
+	{
+		/// Returns the fee schedule of given legal officer.
+		fn fee_schedule(legal_officer: AccountId) -> FeeSchedule<Balance>;
+
+		/// Returns the LOC fees requesters would pay if given signed extrinsic were dispatched,
+		/// `None` if the extrinsic is not signed or its LOC operations are not valid.
+		fn query_loc_fee(uxt: Block::Extrinsic) -> Option<Balance>;
+	}
+}
@@ -0,0 +1,363 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Legal officer service fees charged on LOC operations.
+//!
+//! Each legal officer publishes a fee schedule: a fee to open a LOC, a fee per item (metadata,
+//! file, link or collection item) and a fee to close it. Fees are paid by the requester of the
+//! LOC, if it is an account, to the owner of the LOC.
+//!
+//! A requester consents to fees either by signing the call, or by approving with `approve_fees` a
+//! cap on the fees charged for a LOC by calls signed by others, e.g. its legal officer. The cap
+//! decreases with each fee charged. Calls charging fees that are not approved are rejected.
+//!
+//! `pallet_logion_loc` has no hook, fees are charged by the `ChargeLocFees` signed extension: the
+//! runtime tells which LOC operations a call contains (`LocFeeCalls`), fees are reserved from
+//! requesters before dispatch and paid to legal officers if the dispatch succeeds.
+
+pub use pallet::*;
+pub use pallet_lo_fees_runtime_api::{FeeSchedule, LocOperation};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{BalanceStatus, Currency, ReservableCurrency},
+};
+use pallet_logion_loc::{LocMap, Requester};
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{DispatchInfoOf, PostDispatchInfoOf, Saturating, SignedExtension, Zero},
+	transaction_validity::{InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction},
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// A fee-bearing LOC operation contained in a call.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum LocFeeCall<LocId, AccountId> {
+	/// Creation of a LOC requested by an account
+	Open { loc_id: LocId, requester: AccountId },
+	/// Addition of items to a LOC
+	AddItems { loc_id: LocId, count: u32 },
+	/// Closing of a LOC
+	Close { loc_id: LocId },
+}
+
+/// Tells which fee-bearing LOC operations a call contains.
+pub trait LocFeeCalls<Call, LocId, AccountId> {
+	fn fee_calls(call: &Call) -> Vec<LocFeeCall<LocId, AccountId>>;
+}
+
+/// A fee due by a requester to a legal officer.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LocFee<LocId, AccountId, Balance> {
+	pub loc_id: LocId,
+	pub operation: LocOperation,
+	pub payer: AccountId,
+	pub legal_officer: AccountId,
+	pub amount: Balance,
+}
+
+pub type LocFeeOf<T> = LocFee<
+	<T as pallet_logion_loc::Config>::LocId,
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		traits::ReservableCurrency,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Zero;
+	use super::{BalanceOf, FeeSchedule, LocFeeCalls, LocOperation};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_logion_loc::Config {
+		/// The currency fees are paid with
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The origin of legal officers
+		type LegalOfficerOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin, Success = Self::AccountId>;
+
+		/// The fee-bearing LOC operations of calls
+		type LocFeeCalls: LocFeeCalls<<Self as frame_system::Config>::Call, Self::LocId, Self::AccountId>;
+
+		/// The maximum fee of an operation
+		type MaxFee: Get<BalanceOf<Self>>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Fee schedule of each legal officer
+	#[pallet::storage]
+	#[pallet::getter(fn fee_schedule)]
+	pub type FeeSchedules<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, FeeSchedule<BalanceOf<T>>, ValueQuery>;
+
+	/// Fees each requester still allows to charge for a LOC with calls signed by others
+	#[pallet::storage]
+	#[pallet::getter(fn fee_allowance)]
+	pub type FeeAllowances<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::LocId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", T::LocId = "LocId", BalanceOf<T> = "Balance")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when a legal officer sets its fee schedule. [legal officer, schedule]
+		FeeScheduleSet(T::AccountId, FeeSchedule<BalanceOf<T>>),
+		/// Issued when a LOC fee is paid. [LOC, operation, requester, legal officer, amount]
+		LocFeePaid(T::LocId, LocOperation, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Issued when a requester approves fees for a LOC. [requester, LOC, cap]
+		FeesApproved(T::AccountId, T::LocId, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A fee exceeds the maximum fee
+		FeeTooHigh,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Sets the fee schedule of the calling legal officer.
+		#[pallet::weight(T::DbWeight::get().writes(1) + 10_000_000)]
+		pub fn set_fee_schedule(
+			origin: OriginFor<T>,
+			schedule: FeeSchedule<BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let who = T::LegalOfficerOrigin::ensure_origin(origin)?;
+			let max_fee = T::MaxFee::get();
+			ensure!(
+				schedule.open <= max_fee && schedule.per_item <= max_fee && schedule.close <= max_fee,
+				Error::<T>::FeeTooHigh
+			);
+
+			<FeeSchedules<T>>::insert(&who, schedule);
+			Self::deposit_event(Event::FeeScheduleSet(who, schedule));
+			Ok(().into())
+		}
+
+		/// Sets the fees the calling requester allows to charge for a LOC with calls signed by
+		/// others. A zero cap withdraws the approval.
+		#[pallet::weight(T::DbWeight::get().writes(1) + 10_000_000)]
+		pub fn approve_fees(
+			origin: OriginFor<T>,
+			loc_id: T::LocId,
+			cap: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			if cap.is_zero() {
+				<FeeAllowances<T>>::remove(&who, &loc_id);
+			} else {
+				<FeeAllowances<T>>::insert(&who, &loc_id, cap);
+			}
+			Self::deposit_event(Event::FeesApproved(who, loc_id, cap));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the LOC fees due if given call is dispatched by given account.
+	pub fn loc_fees(who: &T::AccountId, call: &<T as frame_system::Config>::Call) -> Vec<LocFeeOf<T>> {
+		let mut opened: Vec<(T::LocId, T::AccountId)> = Vec::new();
+		let mut fees = Vec::new();
+		for fee_call in T::LocFeeCalls::fee_calls(call) {
+			let (loc_id, operation, count) = match fee_call {
+				LocFeeCall::Open { loc_id, requester } => {
+					opened.push((loc_id.clone(), requester));
+					(loc_id, LocOperation::Open, 1u32)
+				},
+				LocFeeCall::AddItems { loc_id, count } => (loc_id, LocOperation::AddItem, count),
+				LocFeeCall::Close { loc_id } => (loc_id, LocOperation::Close, 1u32),
+			};
+			let (payer, legal_officer) = match Self::parties(&loc_id, &opened, who) {
+				Some(parties) => parties,
+				None => continue,
+			};
+			let schedule = Self::fee_schedule(&legal_officer);
+			let amount = match operation {
+				LocOperation::Open => schedule.open,
+				LocOperation::AddItem => schedule.per_item.saturating_mul(count.into()),
+				LocOperation::Close => schedule.close,
+			};
+			if !amount.is_zero() {
+				fees.push(LocFeeOf::<T> { loc_id, operation, payer, legal_officer, amount });
+			}
+		}
+		fees
+	}
+
+	/// The requester account and the owner of a LOC, including LOCs opened earlier in the same call.
+	fn parties(loc_id: &T::LocId, opened: &[(T::LocId, T::AccountId)], who: &T::AccountId) -> Option<(T::AccountId, T::AccountId)> {
+		match <LocMap<T>>::get(loc_id) {
+			Some(loc) => match loc.requester {
+				Requester::Account(requester) => Some((requester, loc.owner)),
+				_ => None,
+			},
+			None => opened.iter()
+				.find(|(id, _)| id == loc_id)
+				.map(|(_, requester)| (requester.clone(), who.clone())),
+		}
+	}
+
+	/// Total fees due by each payer.
+	fn totals(fees: &[LocFeeOf<T>]) -> Vec<(T::AccountId, BalanceOf<T>)> {
+		let mut totals: Vec<(T::AccountId, BalanceOf<T>)> = Vec::new();
+		for fee in fees.iter() {
+			match totals.iter_mut().find(|(payer, _)| payer == &fee.payer) {
+				Some((_, total)) => *total = total.saturating_add(fee.amount),
+				None => totals.push((fee.payer.clone(), fee.amount)),
+			}
+		}
+		totals
+	}
+
+	/// Total fees due by each payer for each LOC, for fees charged by calls the payer did not sign.
+	fn unsigned_totals(who: &T::AccountId, fees: &[LocFeeOf<T>]) -> Vec<(T::AccountId, T::LocId, BalanceOf<T>)> {
+		let mut totals: Vec<(T::AccountId, T::LocId, BalanceOf<T>)> = Vec::new();
+		for fee in fees.iter().filter(|fee| &fee.payer != who) {
+			match totals.iter_mut().find(|(payer, loc_id, _)| payer == &fee.payer && loc_id == &fee.loc_id) {
+				Some((_, _, total)) => *total = total.saturating_add(fee.amount),
+				None => totals.push((fee.payer.clone(), fee.loc_id.clone(), fee.amount)),
+			}
+		}
+		totals
+	}
+
+	/// Gives back a reserved fee, and its allowance if the payer did not sign the call.
+	fn refund(who: &T::AccountId, fee: &LocFeeOf<T>) {
+		T::Currency::unreserve(&fee.payer, fee.amount);
+		if &fee.payer != who {
+			<FeeAllowances<T>>::mutate(&fee.payer, &fee.loc_id, |cap| *cap = cap.saturating_add(fee.amount));
+		}
+	}
+}
+
+/// Custom validity error: a requester is not able to pay LOC fees.
+pub const LOC_FEE_NOT_PAYABLE: u8 = 110;
+
+/// Custom validity error: a requester did not approve LOC fees charged by a call it did not sign.
+pub const LOC_FEE_NOT_APPROVED: u8 = 111;
+
+/// Reserves LOC fees from requesters before dispatch and pays them to legal officers if the
+/// dispatch succeeds.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeLocFees<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> ChargeLocFees<T> {
+	/// Utility constructor. Used only in client/factory code.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+
+	fn payable_fees(who: &T::AccountId, call: &<T as frame_system::Config>::Call) -> Result<Vec<LocFeeOf<T>>, TransactionValidityError> {
+		let fees = Pallet::<T>::loc_fees(who, call);
+		for (payer, loc_id, total) in Pallet::<T>::unsigned_totals(who, &fees).iter() {
+			if Pallet::<T>::fee_allowance(payer, loc_id) < *total {
+				return Err(InvalidTransaction::Custom(LOC_FEE_NOT_APPROVED).into());
+			}
+		}
+		for (payer, total) in Pallet::<T>::totals(&fees).iter() {
+			if !T::Currency::can_reserve(payer, *total) {
+				return Err(InvalidTransaction::Custom(LOC_FEE_NOT_PAYABLE).into());
+			}
+		}
+		Ok(fees)
+	}
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for ChargeLocFees<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeLocFees")
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for ChargeLocFees<T> {
+	const IDENTIFIER: &'static str = "ChargeLocFees";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = (T::AccountId, Vec<LocFeeOf<T>>);
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> { Ok(()) }
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		Self::payable_fees(who, call)?;
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let fees = Self::payable_fees(who, call)?;
+		for fee in fees.iter() {
+			T::Currency::reserve(&fee.payer, fee.amount)
+				.map_err(|_| InvalidTransaction::Custom(LOC_FEE_NOT_PAYABLE))?;
+			if &fee.payer != who {
+				<FeeAllowances<T>>::mutate(&fee.payer, &fee.loc_id, |cap| *cap = cap.saturating_sub(fee.amount));
+			}
+		}
+		Ok((who.clone(), fees))
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		_info: &DispatchInfoOf<Self::Call>,
+		_post_info: &PostDispatchInfoOf<Self::Call>,
+		_len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		let (who, fees) = pre;
+		for fee in fees.into_iter() {
+			if result.is_err() {
+				Pallet::<T>::refund(&who, &fee);
+				continue;
+			}
+			match T::Currency::repatriate_reserved(&fee.payer, &fee.legal_officer, fee.amount, BalanceStatus::Free) {
+				Ok(missing) => {
+					let paid = fee.amount.saturating_sub(missing);
+					if !paid.is_zero() {
+						Pallet::<T>::deposit_event(Event::LocFeePaid(fee.loc_id, fee.operation, fee.payer, fee.legal_officer, paid));
+					}
+				},
+				Err(_) => Pallet::<T>::refund(&who, &fee),
+			}
+		}
+		Ok(())
+	}
+}
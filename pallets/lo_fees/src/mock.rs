@@ -0,0 +1,160 @@
+use crate as pallet_lo_fees;
+use sp_core::H256;
+use frame_support::{parameter_types, traits::EnsureOrigin};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header, BuildStorage,
+};
+use frame_system as system;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		Utility: pallet_utility::{Module, Call, Event},
+		LogionLoc: pallet_logion_loc::{Module, Call, Storage, Event<T>},
+		LoFees: pallet_lo_fees::{Module, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxLocks: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = MaxLocks;
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_utility::Config for Test {
+	type Event = Event;
+	type Call = Call;
+	type WeightInfo = ();
+}
+
+pub const LEGAL_OFFICER_1: u64 = 1;
+pub const LEGAL_OFFICER_2: u64 = 2;
+pub const REQUESTER: u64 = 3;
+
+pub struct EnsureLegalOfficer;
+impl EnsureOrigin<Origin> for EnsureLegalOfficer {
+	type Success = u64;
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		let origin: Result<system::RawOrigin<u64>, Origin> = o.into();
+		match origin {
+			Ok(system::RawOrigin::Signed(who)) if who == LEGAL_OFFICER_1 || who == LEGAL_OFFICER_2 => Ok(who),
+			Ok(other) => Err(Origin::from(other)),
+			Err(origin) => Err(origin),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::signed(LEGAL_OFFICER_1)
+	}
+}
+
+parameter_types! {
+	pub const MaxMetadataItemNameSize: usize = 255;
+	pub const MaxMetadataItemValueSize: usize = 255;
+	pub const MaxFileNatureSize: usize = 255;
+	pub const MaxLinkNatureSize: usize = 255;
+	pub const MaxCollectionItemDescriptionSize: usize = 255;
+}
+
+impl pallet_logion_loc::Config for Test {
+	type LocId = u32;
+	type Event = Event;
+	type Hash = H256;
+	type CreateOrigin = EnsureLegalOfficer;
+	type MaxMetadataItemNameSize = MaxMetadataItemNameSize;
+	type MaxMetadataItemValueSize = MaxMetadataItemValueSize;
+	type MaxFileNatureSize = MaxFileNatureSize;
+	type MaxLinkNatureSize = MaxLinkNatureSize;
+	type CollectionItemId = H256;
+	type MaxCollectionItemDescriptionSize = MaxCollectionItemDescriptionSize;
+	type WeightInfo = ();
+}
+
+pub struct LocFeeCalls;
+impl pallet_lo_fees::LocFeeCalls<Call, u32, u64> for LocFeeCalls {
+	fn fee_calls(call: &Call) -> Vec<pallet_lo_fees::LocFeeCall<u32, u64>> {
+		use pallet_lo_fees::LocFeeCall::*;
+		use pallet_logion_loc::Call as LocCall;
+		match call {
+			Call::Utility(pallet_utility::Call::batch_all(calls)) =>
+				calls.iter().flat_map(Self::fee_calls).collect(),
+			Call::LogionLoc(LocCall::create_polkadot_transaction_loc(loc_id, requester)) =>
+				vec![Open { loc_id: *loc_id, requester: *requester }],
+			Call::LogionLoc(LocCall::add_metadata(loc_id, ..)) => vec![AddItems { loc_id: *loc_id, count: 1 }],
+			Call::LogionLoc(LocCall::close(loc_id)) => vec![Close { loc_id: *loc_id }],
+			_ => Vec::new(),
+		}
+	}
+}
+
+parameter_types! {
+	pub const MaxFee: u64 = 50;
+}
+
+impl pallet_lo_fees::Config for Test {
+	type Currency = Balances;
+	type LegalOfficerOrigin = EnsureLegalOfficer;
+	type LocFeeCalls = LocFeeCalls;
+	type MaxFee = MaxFee;
+	type Event = Event;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = GenesisConfig {
+		frame_system: Some(Default::default()),
+		pallet_balances: Some(pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(LEGAL_OFFICER_1, 100), (REQUESTER, 100)],
+		}),
+	}.build_storage().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
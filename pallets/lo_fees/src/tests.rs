@@ -0,0 +1,184 @@
+use crate::{mock::*, ChargeLocFees, Event as LoFeesEvent, FeeSchedule, LocOperation, LOC_FEE_NOT_APPROVED};
+use frame_support::{assert_ok, dispatch::DispatchResult, weights::GetDispatchInfo};
+use pallet_logion_loc::MetadataItem;
+use sp_runtime::{
+	traits::{Dispatchable, SignedExtension},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+
+const LOC_ID: u32 = 1;
+const OPEN_FEE: u64 = 10;
+const ITEM_FEE: u64 = 5;
+const CLOSE_FEE: u64 = 20;
+const LEN: usize = 10;
+
+fn set_fee_schedule(legal_officer: u64) {
+	assert_ok!(LoFees::set_fee_schedule(Origin::signed(legal_officer), FeeSchedule { open: OPEN_FEE, per_item: ITEM_FEE, close: CLOSE_FEE }));
+}
+
+fn create_loc() -> Call {
+	Call::LogionLoc(pallet_logion_loc::Call::create_polkadot_transaction_loc(LOC_ID, REQUESTER))
+}
+
+fn add_metadata() -> Call {
+	Call::LogionLoc(pallet_logion_loc::Call::add_metadata(LOC_ID, MetadataItem {
+		name: b"name".to_vec(),
+		value: b"value".to_vec(),
+		submitter: LEGAL_OFFICER_1,
+	}))
+}
+
+fn close() -> Call {
+	Call::LogionLoc(pallet_logion_loc::Call::close(LOC_ID))
+}
+
+/// Charges fees and dispatches given call like the executive does.
+fn apply(who: u64, call: Call) -> Result<DispatchResult, TransactionValidityError> {
+	let info = call.get_dispatch_info();
+	ChargeLocFees::<Test>::new().validate(&who, &call, &info, LEN)?;
+	let pre = ChargeLocFees::<Test>::new().pre_dispatch(&who, &call, &info, LEN)?;
+	let (post_info, result) = match call.dispatch(Origin::signed(who)) {
+		Ok(post_info) => (post_info, Ok(())),
+		Err(error) => (error.post_info, Err(error.error)),
+	};
+	assert_ok!(ChargeLocFees::<Test>::post_dispatch(pre, &info, &post_info, LEN, &result));
+	Ok(result)
+}
+
+fn not_approved<T>() -> Result<T, TransactionValidityError> {
+	Err(TransactionValidityError::Invalid(InvalidTransaction::Custom(LOC_FEE_NOT_APPROVED)))
+}
+
+fn paid_fees() -> Vec<(LocOperation, u64, u64)> {
+	System::events().into_iter()
+		.filter_map(|record| match record.event {
+			Event::pallet_lo_fees(LoFeesEvent::LocFeePaid(_, operation, _, legal_officer, amount)) => Some((operation, legal_officer, amount)),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn it_rejects_fees_not_approved_by_requester() {
+	new_test_ext().execute_with(|| {
+		set_fee_schedule(LEGAL_OFFICER_1);
+
+		assert_eq!(apply(LEGAL_OFFICER_1, create_loc()), not_approved());
+		assert_eq!(Balances::free_balance(REQUESTER), 100);
+		assert!(paid_fees().is_empty());
+	});
+}
+
+#[test]
+fn it_rejects_fees_on_existing_loc_not_approved_by_requester() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(apply(LEGAL_OFFICER_1, create_loc()).unwrap());
+		set_fee_schedule(LEGAL_OFFICER_1);
+
+		assert_eq!(apply(LEGAL_OFFICER_1, add_metadata()), not_approved());
+		assert_eq!(apply(LEGAL_OFFICER_1, close()), not_approved());
+		assert_eq!(Balances::free_balance(REQUESTER), 100);
+	});
+}
+
+#[test]
+fn it_charges_approved_fees() {
+	new_test_ext().execute_with(|| {
+		set_fee_schedule(LEGAL_OFFICER_1);
+		assert_ok!(LoFees::approve_fees(Origin::signed(REQUESTER), LOC_ID, 30));
+
+		assert_ok!(apply(LEGAL_OFFICER_1, create_loc()).unwrap());
+		assert_eq!(Balances::free_balance(REQUESTER), 100 - OPEN_FEE);
+		assert_eq!(Balances::reserved_balance(REQUESTER), 0);
+		assert_eq!(Balances::free_balance(LEGAL_OFFICER_1), 100 + OPEN_FEE);
+		assert_eq!(LoFees::fee_allowance(REQUESTER, LOC_ID), 30 - OPEN_FEE);
+		assert_eq!(paid_fees(), vec![(LocOperation::Open, LEGAL_OFFICER_1, OPEN_FEE)]);
+
+		assert_eq!(apply(LEGAL_OFFICER_1, close()), not_approved());
+	});
+}
+
+#[test]
+fn it_withdraws_approval_with_zero_cap() {
+	new_test_ext().execute_with(|| {
+		set_fee_schedule(LEGAL_OFFICER_1);
+		assert_ok!(LoFees::approve_fees(Origin::signed(REQUESTER), LOC_ID, 30));
+		assert_ok!(LoFees::approve_fees(Origin::signed(REQUESTER), LOC_ID, 0));
+
+		assert!(!crate::FeeAllowances::<Test>::contains_key(REQUESTER, LOC_ID));
+		assert_eq!(apply(LEGAL_OFFICER_1, create_loc()), not_approved());
+	});
+}
+
+#[test]
+fn it_charges_signing_requester_without_approval() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(apply(LEGAL_OFFICER_1, create_loc()).unwrap());
+		set_fee_schedule(LEGAL_OFFICER_1);
+
+		let call = close();
+		let info = call.get_dispatch_info();
+		assert!(ChargeLocFees::<Test>::new().pre_dispatch(&REQUESTER, &call, &info, LEN).is_ok());
+		assert_eq!(Balances::reserved_balance(REQUESTER), CLOSE_FEE);
+	});
+}
+
+#[test]
+fn it_refunds_fees_of_failed_dispatch() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(apply(LEGAL_OFFICER_1, create_loc()).unwrap());
+		set_fee_schedule(LEGAL_OFFICER_1);
+		assert_ok!(LoFees::approve_fees(Origin::signed(REQUESTER), LOC_ID, 30));
+
+		// Only the owner may close the LOC
+		assert!(apply(REQUESTER, close()).unwrap().is_err());
+		assert_eq!(Balances::free_balance(REQUESTER), 100);
+		assert_eq!(Balances::reserved_balance(REQUESTER), 0);
+
+		assert_ok!(apply(LEGAL_OFFICER_1, close()).unwrap());
+		// Already closed
+		assert!(apply(LEGAL_OFFICER_1, add_metadata()).unwrap().is_err());
+		assert_eq!(Balances::free_balance(REQUESTER), 100 - CLOSE_FEE);
+		assert_eq!(Balances::reserved_balance(REQUESTER), 0);
+		assert_eq!(LoFees::fee_allowance(REQUESTER, LOC_ID), 30 - CLOSE_FEE);
+		assert_eq!(paid_fees(), vec![(LocOperation::Close, LEGAL_OFFICER_1, CLOSE_FEE)]);
+	});
+}
+
+#[test]
+fn it_refunds_fees_not_paid_to_legal_officer() {
+	new_test_ext().execute_with(|| {
+		set_fee_schedule(LEGAL_OFFICER_2);
+		assert_ok!(LoFees::approve_fees(Origin::signed(REQUESTER), LOC_ID, 30));
+
+		// Legal officer 2 has no account, the fee cannot be paid to it
+		assert_ok!(apply(LEGAL_OFFICER_2, create_loc()).unwrap());
+		assert_eq!(Balances::free_balance(REQUESTER), 100);
+		assert_eq!(Balances::reserved_balance(REQUESTER), 0);
+		assert_eq!(LoFees::fee_allowance(REQUESTER, LOC_ID), 30);
+		assert!(paid_fees().is_empty());
+	});
+}
+
+#[test]
+fn it_charges_batched_calls_within_cap() {
+	new_test_ext().execute_with(|| {
+		set_fee_schedule(LEGAL_OFFICER_1);
+		let batch = || Call::Utility(pallet_utility::Call::batch_all(vec![create_loc(), add_metadata(), close()]));
+		let total = OPEN_FEE + ITEM_FEE + CLOSE_FEE;
+		assert_ok!(LoFees::approve_fees(Origin::signed(REQUESTER), LOC_ID, total - 1));
+
+		assert_eq!(apply(LEGAL_OFFICER_1, batch()), not_approved());
+
+		assert_ok!(LoFees::approve_fees(Origin::signed(REQUESTER), LOC_ID, total));
+		assert_ok!(apply(LEGAL_OFFICER_1, batch()).unwrap());
+		assert_eq!(Balances::free_balance(REQUESTER), 100 - total);
+		assert_eq!(Balances::free_balance(LEGAL_OFFICER_1), 100 + total);
+		assert_eq!(LoFees::fee_allowance(REQUESTER, LOC_ID), 0);
+		assert_eq!(paid_fees(), vec![
+			(LocOperation::Open, LEGAL_OFFICER_1, OPEN_FEE),
+			(LocOperation::AddItem, LEGAL_OFFICER_1, ITEM_FEE),
+			(LocOperation::Close, LEGAL_OFFICER_1, CLOSE_FEE),
+		]);
+	});
+}
@@ -0,0 +1,327 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Legal officers as registrars of `pallet_identity`.
+//!
+//! Each legal officer is registered as an identity registrar. Once a legal officer has closed an
+//! Identity LOC requested by an account, it judges the identity of this account `KnownGood` with
+//! a reference to the LOC. Identity LOCs backing a judgement are voided through this pallet, which
+//! downgrades the judgement to `OutOfDate`. If such a LOC is voided otherwise, anyone may downgrade
+//! the judgement.
+//!
+//! Registrars are added with this pallet's `RegistrarManager` origin, the runtime accepts it
+//! through `EnsureRegistrarManager`. Legal officers existing at genesis or at the upgrade are
+//! registered automatically, those added later call `become_registrar` once. Judgements must go through this pallet so that they always
+//! reference a LOC.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use frame_support::traits::EnsureOrigin;
+use sp_runtime::RuntimeDebug;
+
+/// The origin of calls dispatched by this pallet.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode)]
+pub enum RawOrigin {
+	/// Registrar changes checked by this pallet
+	RegistrarManager,
+}
+
+/// Storage version of the pallet.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum IdentityStorageVersion {
+	V0NoRegistrar,
+	V1Registrars,
+}
+
+impl Default for IdentityStorageVersion {
+	fn default() -> IdentityStorageVersion {
+		IdentityStorageVersion::V1Registrars
+	}
+}
+
+/// Ensures that the origin is `RawOrigin::RegistrarManager`.
+pub struct EnsureRegistrarManager;
+impl<O: Into<Result<RawOrigin, O>> + From<RawOrigin>> EnsureOrigin<O> for EnsureRegistrarManager {
+	type Success = ();
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().map(|RawOrigin::RegistrarManager| ())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(RawOrigin::RegistrarManager)
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		transactional,
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_identity::{Judgement, RegistrarIndex};
+	use pallet_logion_loc::{LocMap, LocType, Requester};
+	use sp_runtime::traits::StaticLookup;
+	use sp_std::prelude::*;
+	use super::{IdentityStorageVersion, RawOrigin};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_identity::Config + pallet_logion_loc::Config {
+		/// The origin of legal officers
+		type LegalOfficerOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin, Success = Self::AccountId>;
+
+		/// The outer origin type, built from this pallet's origin
+		type RegistrarOrigin: From<RawOrigin> + Into<<Self as frame_system::Config>::Origin>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::origin]
+	pub type Origin = RawOrigin;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Registrar index of each legal officer
+	#[pallet::storage]
+	#[pallet::getter(fn registrar_index)]
+	pub type Registrars<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, RegistrarIndex>;
+
+	/// Identity LOC backing the judgement of an account by a legal officer
+	#[pallet::storage]
+	#[pallet::getter(fn judgement_loc)]
+	pub type JudgementLocs<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, T::LocId>;
+
+	/// Judged account and legal officer of each Identity LOC backing a judgement
+	#[pallet::storage]
+	#[pallet::getter(fn loc_judgement)]
+	pub type LocJudgements<T: Config> = StorageMap<_, Blake2_128Concat, T::LocId, (T::AccountId, T::AccountId)>;
+
+	/// Storage version of the pallet, absent before legal officers were registered
+	#[pallet::storage]
+	#[pallet::getter(fn pallet_storage_version)]
+	pub type PalletStorageVersion<T> = StorageValue<_, IdentityStorageVersion, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Legal officers registered as registrars at genesis
+		pub registrars: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				registrars: Vec::new(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for legal_officer in self.registrars.iter() {
+				if let Err(error) = Pallet::<T>::register(legal_officer) {
+					panic!("Unable to register {:?} as registrar: {:?}", legal_officer, error.error);
+				}
+			}
+			<PalletStorageVersion<T>>::put(IdentityStorageVersion::V1Registrars);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", T::LocId = "LocId")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when a legal officer becomes a registrar. [legal officer, registrar index]
+		RegistrarAdded(T::AccountId, RegistrarIndex),
+		/// Issued when a legal officer judges an identity. [legal officer, account, LOC]
+		IdentityJudged(T::AccountId, T::AccountId, T::LocId),
+		/// Issued when a judgement is downgraded because its LOC was voided. [legal officer, account, LOC]
+		JudgementDowngraded(T::AccountId, T::AccountId, T::LocId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The legal officer is already a registrar
+		AlreadyRegistrar,
+		/// The legal officer is not a registrar
+		NotRegistrar,
+		/// The LOC does not exist
+		NotFound,
+		/// The LOC is not owned by the legal officer
+		NotOwner,
+		/// The LOC is not an Identity LOC
+		NotIdentityLoc,
+		/// The LOC was not requested by an account
+		NoRequesterAccount,
+		/// The LOC is not closed
+		NotClosed,
+		/// The LOC is void
+		Void,
+		/// The account was not judged by the legal officer
+		NoJudgement,
+		/// The LOC backing the judgement is not void
+		NotVoid,
+		/// The LOC does not back a judgement
+		NoJudgementLoc,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Registers the calling legal officer as an identity registrar.
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2) + 50_000_000)]
+		pub fn become_registrar(
+			origin: OriginFor<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = T::LegalOfficerOrigin::ensure_origin(origin)?;
+			Self::register(&who)
+		}
+
+		/// Judges `KnownGood` the identity of the requester of a closed Identity LOC owned by the
+		/// calling legal officer.
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 2) + 100_000_000)]
+		pub fn judge(
+			origin: OriginFor<T>,
+			loc_id: T::LocId,
+		) -> DispatchResultWithPostInfo {
+			let who = T::LegalOfficerOrigin::ensure_origin(origin)?;
+			let index = <Registrars<T>>::get(&who).ok_or(Error::<T>::NotRegistrar)?;
+			let loc = <LocMap<T>>::get(&loc_id).ok_or(Error::<T>::NotFound)?;
+			ensure!(loc.owner == who, Error::<T>::NotOwner);
+			ensure!(loc.loc_type == LocType::Identity, Error::<T>::NotIdentityLoc);
+			ensure!(loc.closed, Error::<T>::NotClosed);
+			ensure!(loc.void_info.is_none(), Error::<T>::Void);
+			let target = match loc.requester {
+				Requester::Account(account) => account,
+				_ => Err(Error::<T>::NoRequesterAccount)?,
+			};
+
+			pallet_identity::Module::<T>::provide_judgement(
+				frame_system::RawOrigin::Signed(who.clone()).into(),
+				index,
+				T::Lookup::unlookup(target.clone()),
+				Judgement::KnownGood,
+			)?;
+			if let Some(previous) = <JudgementLocs<T>>::get(&target, &who) {
+				<LocJudgements<T>>::remove(previous);
+			}
+			<JudgementLocs<T>>::insert(&target, &who, loc_id.clone());
+			<LocJudgements<T>>::insert(&loc_id, (target.clone(), who.clone()));
+			Self::deposit_event(Event::IdentityJudged(who, target, loc_id));
+			Ok(().into())
+		}
+
+		/// Downgrades to `OutOfDate` a judgement whose Identity LOC was voided.
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 2) + 100_000_000)]
+		pub fn downgrade(
+			origin: OriginFor<T>,
+			account: T::AccountId,
+			legal_officer: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let loc_id = <JudgementLocs<T>>::get(&account, &legal_officer).ok_or(Error::<T>::NoJudgement)?;
+			let void = <LocMap<T>>::get(&loc_id).map(|loc| loc.void_info.is_some()).unwrap_or(true);
+			ensure!(void, Error::<T>::NotVoid);
+			Self::downgrade_judgement(account, legal_officer)
+		}
+
+		/// Voids an Identity LOC backing a judgement of the calling legal officer, optionally
+		/// replaced by another LOC, and downgrades the judgement.
+		#[pallet::weight(T::DbWeight::get().reads_writes(6, 5) + 200_000_000)]
+		#[transactional]
+		pub fn make_void(
+			origin: OriginFor<T>,
+			loc_id: T::LocId,
+			replacer: Option<T::LocId>,
+		) -> DispatchResultWithPostInfo {
+			let who = T::LegalOfficerOrigin::ensure_origin(origin)?;
+			let (account, legal_officer) = <LocJudgements<T>>::get(&loc_id).ok_or(Error::<T>::NoJudgementLoc)?;
+			ensure!(legal_officer == who, Error::<T>::NotOwner);
+
+			let loc_origin: OriginFor<T> = frame_system::RawOrigin::Signed(who).into();
+			match replacer {
+				Some(replacer) =>
+					pallet_logion_loc::Module::<T>::make_void_and_replace(loc_origin, loc_id, replacer)?,
+				None => pallet_logion_loc::Module::<T>::make_void(loc_origin, loc_id)?,
+			};
+			Self::downgrade_judgement(account, legal_officer)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Tells if a LOC backs a judgement, it must then be voided through this pallet.
+		pub fn backs_judgement(loc_id: &T::LocId) -> bool {
+			<LocJudgements<T>>::contains_key(loc_id)
+		}
+
+		fn downgrade_judgement(account: T::AccountId, legal_officer: T::AccountId) -> DispatchResultWithPostInfo {
+			let loc_id = <JudgementLocs<T>>::get(&account, &legal_officer).ok_or(Error::<T>::NoJudgement)?;
+			let index = <Registrars<T>>::get(&legal_officer).ok_or(Error::<T>::NotRegistrar)?;
+
+			// Judgements are dropped with the identity they judge.
+			if pallet_identity::Module::<T>::identity(&account).is_some() {
+				pallet_identity::Module::<T>::provide_judgement(
+					frame_system::RawOrigin::Signed(legal_officer.clone()).into(),
+					index,
+					T::Lookup::unlookup(account.clone()),
+					Judgement::OutOfDate,
+				)?;
+			}
+			<JudgementLocs<T>>::remove(&account, &legal_officer);
+			<LocJudgements<T>>::remove(&loc_id);
+			Self::deposit_event(Event::JudgementDowngraded(legal_officer, account, loc_id));
+			Ok(().into())
+		}
+
+		fn register(legal_officer: &T::AccountId) -> DispatchResultWithPostInfo {
+			ensure!(!<Registrars<T>>::contains_key(legal_officer), Error::<T>::AlreadyRegistrar);
+
+			pallet_identity::Module::<T>::add_registrar(Self::registrar_manager(), legal_officer.clone())?;
+			let index = pallet_identity::Module::<T>::registrars().iter()
+				.rposition(|registrar| registrar.as_ref().map(|info| &info.account) == Some(legal_officer))
+				.ok_or(Error::<T>::NotRegistrar)? as RegistrarIndex;
+			<Registrars<T>>::insert(legal_officer, index);
+			Self::deposit_event(Event::RegistrarAdded(legal_officer.clone(), index));
+			Ok(().into())
+		}
+
+		fn registrar_manager() -> <T as frame_system::Config>::Origin {
+			T::RegistrarOrigin::from(RawOrigin::RegistrarManager).into()
+		}
+	}
+
+	/// Registers as registrars the legal officers which are not registrars yet.
+	pub fn migrate<T: Config>(legal_officers: Vec<T::AccountId>) -> Weight {
+		if <PalletStorageVersion<T>>::exists() {
+			return T::DbWeight::get().reads(1);
+		}
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+		for legal_officer in legal_officers.iter() {
+			weight += T::DbWeight::get().reads(1);
+			if !<Registrars<T>>::contains_key(legal_officer) {
+				if let Err(error) = Pallet::<T>::register(legal_officer) {
+					frame_support::debug::error!("Unable to register {:?} as registrar: {:?}", legal_officer, error.error);
+				}
+				weight += T::DbWeight::get().reads_writes(2, 2);
+			}
+		}
+		<PalletStorageVersion<T>>::put(IdentityStorageVersion::V1Registrars);
+		weight
+	}
+}
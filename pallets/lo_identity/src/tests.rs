@@ -0,0 +1,193 @@
+use crate::{mock::*, migrate, Error, Event as LoIdentityEvent, IdentityStorageVersion, PalletStorageVersion, Registrars};
+use frame_support::{assert_err, assert_ok};
+use pallet_identity::{IdentityInfo, Judgement};
+use pallet_logion_loc::LocMap;
+
+const LOC_ID: u32 = 1;
+const REPLACER_LOC_ID: u32 = 2;
+
+fn judged_identity() {
+	assert_ok!(Identity::set_identity(Origin::signed(REQUESTER), IdentityInfo::default()));
+	assert_ok!(LogionLoc::create_polkadot_identity_loc(Origin::signed(LEGAL_OFFICER_1), LOC_ID, REQUESTER));
+	assert_ok!(LogionLoc::close(Origin::signed(LEGAL_OFFICER_1), LOC_ID));
+	assert_ok!(LoIdentity::judge(Origin::signed(LEGAL_OFFICER_1), LOC_ID));
+}
+
+fn judgement() -> Option<Judgement<u64>> {
+	let index = LoIdentity::registrar_index(LEGAL_OFFICER_1).unwrap();
+	Identity::identity(REQUESTER)?.judgements.into_iter()
+		.find(|(registrar, _)| *registrar == index)
+		.map(|(_, judgement)| judgement)
+}
+
+fn is_void(loc_id: u32) -> bool {
+	LocMap::<Test>::get(loc_id).unwrap().void_info.is_some()
+}
+
+#[test]
+fn it_registers_genesis_registrars() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(LoIdentity::registrar_index(LEGAL_OFFICER_1), Some(0));
+		assert_eq!(LoIdentity::registrar_index(LEGAL_OFFICER_2), Some(1));
+		assert_eq!(LoIdentity::pallet_storage_version(), IdentityStorageVersion::V1Registrars);
+	});
+}
+
+#[test]
+fn it_reads_registrar_index_from_storage() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LoIdentity::become_registrar(Origin::signed(LEGAL_OFFICER_3)));
+
+		let index = LoIdentity::registrar_index(LEGAL_OFFICER_3).unwrap();
+		let registrars = Identity::registrars();
+		assert_eq!(registrars[index as usize].as_ref().map(|info| info.account), Some(LEGAL_OFFICER_3));
+		assert_eq!(System::events().pop().unwrap().event, Event::pallet_lo_identity(LoIdentityEvent::RegistrarAdded(LEGAL_OFFICER_3, index)));
+	});
+}
+
+#[test]
+fn it_judges_after_becoming_registrar() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_identity(Origin::signed(REQUESTER), IdentityInfo::default()));
+		assert_ok!(LogionLoc::create_polkadot_identity_loc(Origin::signed(LEGAL_OFFICER_3), LOC_ID, REQUESTER));
+		assert_ok!(LogionLoc::close(Origin::signed(LEGAL_OFFICER_3), LOC_ID));
+		assert_err!(LoIdentity::judge(Origin::signed(LEGAL_OFFICER_3), LOC_ID), Error::<Test>::NotRegistrar);
+
+		assert_ok!(LoIdentity::become_registrar(Origin::signed(LEGAL_OFFICER_3)));
+		assert_ok!(LoIdentity::judge(Origin::signed(LEGAL_OFFICER_3), LOC_ID));
+
+		assert_eq!(LoIdentity::judgement_loc(REQUESTER, LEGAL_OFFICER_3), Some(LOC_ID));
+	});
+}
+
+#[test]
+fn it_migrates_once() {
+	new_test_ext().execute_with(|| {
+		<PalletStorageVersion<Test>>::kill();
+
+		migrate::<Test>(vec![LEGAL_OFFICER_1, LEGAL_OFFICER_3]);
+
+		assert_eq!(LoIdentity::registrar_index(LEGAL_OFFICER_3), Some(2));
+		assert_eq!(LoIdentity::pallet_storage_version(), IdentityStorageVersion::V1Registrars);
+		assert!(<PalletStorageVersion<Test>>::exists());
+
+		<Registrars<Test>>::remove(LEGAL_OFFICER_3);
+		migrate::<Test>(vec![LEGAL_OFFICER_3]);
+		assert_eq!(LoIdentity::registrar_index(LEGAL_OFFICER_3), None);
+	});
+}
+
+#[test]
+fn it_completes_migration_despite_failed_registration() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LoIdentity::become_registrar(Origin::signed(LEGAL_OFFICER_3)));
+		<Registrars<Test>>::remove(LEGAL_OFFICER_3);
+		<PalletStorageVersion<Test>>::kill();
+
+		// The maximum number of registrars is reached.
+		migrate::<Test>(vec![LEGAL_OFFICER_3]);
+
+		assert_eq!(LoIdentity::registrar_index(LEGAL_OFFICER_3), None);
+		assert!(<PalletStorageVersion<Test>>::exists());
+	});
+}
+
+#[test]
+fn it_judges_identity() {
+	new_test_ext().execute_with(|| {
+		judged_identity();
+
+		assert_eq!(judgement(), Some(Judgement::KnownGood));
+		assert_eq!(LoIdentity::judgement_loc(REQUESTER, LEGAL_OFFICER_1), Some(LOC_ID));
+		assert!(LoIdentity::backs_judgement(&LOC_ID));
+	});
+}
+
+#[test]
+fn it_downgrades_judgement_when_voiding_loc() {
+	new_test_ext().execute_with(|| {
+		judged_identity();
+
+		assert_ok!(LoIdentity::make_void(Origin::signed(LEGAL_OFFICER_1), LOC_ID, None));
+
+		assert!(is_void(LOC_ID));
+		assert_eq!(judgement(), Some(Judgement::OutOfDate));
+		assert_eq!(LoIdentity::judgement_loc(REQUESTER, LEGAL_OFFICER_1), None);
+		assert!(!LoIdentity::backs_judgement(&LOC_ID));
+		assert_eq!(System::events().pop().unwrap().event, Event::pallet_lo_identity(LoIdentityEvent::JudgementDowngraded(LEGAL_OFFICER_1, REQUESTER, LOC_ID)));
+	});
+}
+
+#[test]
+fn it_downgrades_judgement_when_replacing_loc() {
+	new_test_ext().execute_with(|| {
+		judged_identity();
+		assert_ok!(LogionLoc::create_polkadot_identity_loc(Origin::signed(LEGAL_OFFICER_1), REPLACER_LOC_ID, REQUESTER));
+
+		assert_ok!(LoIdentity::make_void(Origin::signed(LEGAL_OFFICER_1), LOC_ID, Some(REPLACER_LOC_ID)));
+
+		assert!(is_void(LOC_ID));
+		assert_eq!(LocMap::<Test>::get(REPLACER_LOC_ID).unwrap().replacer_of, Some(LOC_ID));
+		assert_eq!(judgement(), Some(Judgement::OutOfDate));
+	});
+}
+
+#[test]
+fn it_voids_loc_of_cleared_identity() {
+	new_test_ext().execute_with(|| {
+		judged_identity();
+		assert_ok!(Identity::clear_identity(Origin::signed(REQUESTER)));
+
+		assert_ok!(LoIdentity::make_void(Origin::signed(LEGAL_OFFICER_1), LOC_ID, None));
+
+		assert!(is_void(LOC_ID));
+		assert!(!LoIdentity::backs_judgement(&LOC_ID));
+	});
+}
+
+#[test]
+fn it_fails_voiding_loc_of_other_legal_officer() {
+	new_test_ext().execute_with(|| {
+		judged_identity();
+
+		assert_err!(LoIdentity::make_void(Origin::signed(LEGAL_OFFICER_2), LOC_ID, None), Error::<Test>::NotOwner);
+		assert!(!is_void(LOC_ID));
+	});
+}
+
+#[test]
+fn it_fails_voiding_loc_without_judgement() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LogionLoc::create_polkadot_identity_loc(Origin::signed(LEGAL_OFFICER_1), LOC_ID, REQUESTER));
+
+		assert_err!(LoIdentity::make_void(Origin::signed(LEGAL_OFFICER_1), LOC_ID, None), Error::<Test>::NoJudgementLoc);
+	});
+}
+
+#[test]
+fn it_downgrades_judgement_of_loc_voided_otherwise() {
+	new_test_ext().execute_with(|| {
+		judged_identity();
+		assert_err!(LoIdentity::downgrade(Origin::signed(REQUESTER), REQUESTER, LEGAL_OFFICER_1), Error::<Test>::NotVoid);
+		assert_ok!(LogionLoc::make_void(Origin::signed(LEGAL_OFFICER_1), LOC_ID));
+
+		assert_ok!(LoIdentity::downgrade(Origin::signed(REQUESTER), REQUESTER, LEGAL_OFFICER_1));
+
+		assert_eq!(judgement(), Some(Judgement::OutOfDate));
+		assert!(!LoIdentity::backs_judgement(&LOC_ID));
+	});
+}
+
+#[test]
+fn it_replaces_judgement_loc() {
+	new_test_ext().execute_with(|| {
+		judged_identity();
+		assert_ok!(LogionLoc::create_polkadot_identity_loc(Origin::signed(LEGAL_OFFICER_1), REPLACER_LOC_ID, REQUESTER));
+		assert_ok!(LogionLoc::close(Origin::signed(LEGAL_OFFICER_1), REPLACER_LOC_ID));
+
+		assert_ok!(LoIdentity::judge(Origin::signed(LEGAL_OFFICER_1), REPLACER_LOC_ID));
+
+		assert!(!LoIdentity::backs_judgement(&LOC_ID));
+		assert_eq!(LoIdentity::loc_judgement(REPLACER_LOC_ID), Some((REQUESTER, LEGAL_OFFICER_1)));
+	});
+}
@@ -0,0 +1,279 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Self-service management of legal officer nodes.
+//!
+//! Legal officers add their own nodes to the well-known nodes of `pallet_node_authorization`,
+//! within a quota, transfer them to other legal officers and manage their connections without
+//! going through root. Root only keeps the power to revoke a node.
+//!
+//! Calls of `pallet_node_authorization` requiring a privileged origin are dispatched with this
+//! pallet's `NodeManager` origin, the runtime accepts it through `EnsureNodeManager`. Root may
+//! still remove nodes with `pallet_node_authorization` directly in an emergency, node counts are
+//! then recomputed with `sync_node_counts`.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use frame_support::traits::EnsureOrigin;
+use sp_runtime::RuntimeDebug;
+
+/// The origin of calls dispatched by this pallet.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode)]
+pub enum RawOrigin {
+	/// Node changes checked by this pallet
+	NodeManager,
+}
+
+/// Storage version of the pallet.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum NodeAuthorizationStorageVersion {
+	V0NoNodeCount,
+	V1NodeCount,
+}
+
+impl Default for NodeAuthorizationStorageVersion {
+	fn default() -> NodeAuthorizationStorageVersion {
+		NodeAuthorizationStorageVersion::V1NodeCount
+	}
+}
+
+/// Ensures that the origin is `RawOrigin::NodeManager`.
+pub struct EnsureNodeManager;
+impl<O: Into<Result<RawOrigin, O>> + From<RawOrigin>> EnsureOrigin<O> for EnsureNodeManager {
+	type Success = ();
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().map(|RawOrigin::NodeManager| ())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(RawOrigin::NodeManager)
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		storage::StoragePrefixedMap,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_core::OpaquePeerId as PeerId;
+	use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+	use super::{NodeAuthorizationStorageVersion, RawOrigin};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_node_authorization::Config {
+		/// The origin of legal officers
+		type LegalOfficerOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+		/// The origin allowed to revoke nodes
+		type RevokeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of nodes a legal officer may own
+		type MaxNodesPerLegalOfficer: Get<u32>;
+
+		/// The outer origin type, built from this pallet's origin
+		type NodeOrigin: From<RawOrigin> + Into<<Self as frame_system::Config>::Origin>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::origin]
+	pub type Origin = RawOrigin;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Number of well-known nodes claimed by each legal officer
+	#[pallet::storage]
+	#[pallet::getter(fn node_count)]
+	pub type NodeCount<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Storage version, absent before node counts were introduced
+	#[pallet::storage]
+	#[pallet::getter(fn pallet_storage_version)]
+	pub type PalletStorageVersion<T> = StorageValue<_, NodeAuthorizationStorageVersion, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(Default)]
+	pub struct GenesisConfig {}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			// Initial nodes are set by the genesis of `pallet_node_authorization`, built before.
+			recount_nodes::<T>();
+			<PalletStorageVersion<T>>::put(NodeAuthorizationStorageVersion::V1NodeCount);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when a legal officer claims a node. [legal officer, node]
+		NodeClaimed(T::AccountId, PeerId),
+		/// Issued when a node is transferred to another legal officer. [from, to, node]
+		NodeTransferred(T::AccountId, T::AccountId, PeerId),
+		/// Issued when a node is revoked. [owner, node]
+		NodeRevoked(T::AccountId, PeerId),
+		/// Issued when node counts are recomputed.
+		NodeCountsSynced,
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The legal officer already owns the maximum number of nodes
+		TooManyNodes,
+		/// The node is not a well-known node
+		UnknownNode,
+		/// The recipient of a node is not a legal officer
+		NotLegalOfficer,
+		/// The recipient of a node is its current owner
+		SelfTransfer,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Adds a node owned by the calling legal officer to the well-known nodes.
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3) + 50_000_000)]
+		pub fn claim_node(
+			origin: OriginFor<T>,
+			node: PeerId,
+		) -> DispatchResultWithPostInfo {
+			let who = T::LegalOfficerOrigin::ensure_origin(origin)?;
+			let count = <NodeCount<T>>::get(&who);
+			ensure!(count < T::MaxNodesPerLegalOfficer::get(), Error::<T>::TooManyNodes);
+
+			pallet_node_authorization::Module::<T>::add_well_known_node(Self::node_manager(), node.clone(), who.clone())?;
+			<NodeCount<T>>::insert(&who, count + 1);
+			Self::deposit_event(Event::NodeClaimed(who, node));
+			Ok(().into())
+		}
+
+		/// Adds connections to a node owned by the calling legal officer.
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 1) + 50_000_000)]
+		pub fn add_connections(
+			origin: OriginFor<T>,
+			node: PeerId,
+			connections: Vec<PeerId>,
+		) -> DispatchResultWithPostInfo {
+			let who = T::LegalOfficerOrigin::ensure_origin(origin)?;
+			pallet_node_authorization::Module::<T>::add_connections(
+				frame_system::RawOrigin::Signed(who).into(), node, connections)?;
+			Ok(().into())
+		}
+
+		/// Removes connections of a node owned by the calling legal officer.
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 1) + 50_000_000)]
+		pub fn remove_connections(
+			origin: OriginFor<T>,
+			node: PeerId,
+			connections: Vec<PeerId>,
+		) -> DispatchResultWithPostInfo {
+			let who = T::LegalOfficerOrigin::ensure_origin(origin)?;
+			pallet_node_authorization::Module::<T>::remove_connections(
+				frame_system::RawOrigin::Signed(who).into(), node, connections)?;
+			Ok(().into())
+		}
+
+		/// Transfers a node owned by the calling legal officer to another legal officer.
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 3) + 50_000_000)]
+		pub fn transfer_node(
+			origin: OriginFor<T>,
+			node: PeerId,
+			legal_officer: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			let who = T::LegalOfficerOrigin::ensure_origin(origin)?;
+			ensure!(legal_officer != who, Error::<T>::SelfTransfer);
+			T::LegalOfficerOrigin::ensure_origin(frame_system::RawOrigin::Signed(legal_officer.clone()).into())
+				.map_err(|_| Error::<T>::NotLegalOfficer)?;
+			let count = <NodeCount<T>>::get(&legal_officer);
+			ensure!(count < T::MaxNodesPerLegalOfficer::get(), Error::<T>::TooManyNodes);
+
+			pallet_node_authorization::Module::<T>::transfer_node(
+				frame_system::RawOrigin::Signed(who.clone()).into(), node.clone(), legal_officer.clone())?;
+			<NodeCount<T>>::mutate(&who, |count| *count = count.saturating_sub(1));
+			<NodeCount<T>>::insert(&legal_officer, count + 1);
+			Self::deposit_event(Event::NodeTransferred(who, legal_officer, node));
+			Ok(().into())
+		}
+
+		/// Removes a node from the well-known nodes.
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3) + 50_000_000)]
+		pub fn revoke_node(
+			origin: OriginFor<T>,
+			node: PeerId,
+		) -> DispatchResultWithPostInfo {
+			T::RevokeOrigin::ensure_origin(origin)?;
+			ensure!(pallet_node_authorization::Module::<T>::well_known_nodes().contains(&node), Error::<T>::UnknownNode);
+			let owner = pallet_node_authorization::Module::<T>::owners(&node);
+
+			pallet_node_authorization::Module::<T>::remove_well_known_node(Self::node_manager(), node.clone())?;
+			<NodeCount<T>>::mutate(&owner, |count| *count = count.saturating_sub(1));
+			Self::deposit_event(Event::NodeRevoked(owner, node));
+			Ok(().into())
+		}
+
+		/// Recomputes node counts after root changed well-known nodes directly.
+		#[pallet::weight(T::DbWeight::get().reads_writes(
+			1 + T::MaxWellKnownNodes::get() as Weight,
+			1 + T::MaxWellKnownNodes::get() as Weight,
+		) + 50_000_000)]
+		pub fn sync_node_counts(
+			origin: OriginFor<T>,
+		) -> DispatchResultWithPostInfo {
+			T::RevokeOrigin::ensure_origin(origin)?;
+			recount_nodes::<T>();
+			Self::deposit_event(Event::NodeCountsSynced);
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn node_manager() -> <T as frame_system::Config>::Origin {
+			T::NodeOrigin::from(RawOrigin::NodeManager).into()
+		}
+	}
+
+	/// Sets the node count of each owner of well-known nodes, returns the number of reads and
+	/// writes.
+	pub fn recount_nodes<T: Config>() -> (Weight, Weight) {
+		<NodeCount<T>>::remove_all();
+		let nodes = pallet_node_authorization::Module::<T>::well_known_nodes();
+		let mut counts: BTreeMap<T::AccountId, u32> = BTreeMap::new();
+		for node in nodes.iter() {
+			*counts.entry(pallet_node_authorization::Module::<T>::owners(node)).or_default() += 1;
+		}
+		let writes = counts.len() as Weight;
+		for (owner, count) in counts.into_iter() {
+			<NodeCount<T>>::insert(owner, count);
+		}
+		(1 + nodes.len() as Weight, 1 + writes)
+	}
+
+	/// Counts the nodes of legal officers who owned nodes before node counts existed.
+	pub fn migrate<T: Config>() -> Weight {
+		if <PalletStorageVersion<T>>::exists() {
+			return T::DbWeight::get().reads(1);
+		}
+		let (reads, writes) = recount_nodes::<T>();
+		<PalletStorageVersion<T>>::put(NodeAuthorizationStorageVersion::V1NodeCount);
+		T::DbWeight::get().reads_writes(reads + 1, writes + 1)
+	}
+}
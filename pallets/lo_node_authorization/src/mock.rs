@@ -0,0 +1,126 @@
+use crate as pallet_lo_node_authorization;
+use sp_core::{H256, OpaquePeerId as PeerId};
+use frame_support::{parameter_types, traits::EnsureOrigin};
+use frame_system::{EnsureOneOf, EnsureRoot};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header, BuildStorage,
+};
+use frame_system as system;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		NodeAuthorization: pallet_node_authorization::{Module, Call, Storage, Event<T>, Config<T>},
+		LoNodeAuthorization: pallet_lo_node_authorization::{Module, Call, Storage, Event<T>, Config, Origin},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+}
+
+parameter_types! {
+	pub const MaxWellKnownNodes: u32 = 10;
+	pub const MaxPeerIdLength: u32 = 128;
+}
+
+type NodeManagerOrRoot = EnsureOneOf<u64, EnsureRoot<u64>, pallet_lo_node_authorization::EnsureNodeManager>;
+
+impl pallet_node_authorization::Config for Test {
+	type Event = Event;
+	type MaxWellKnownNodes = MaxWellKnownNodes;
+	type MaxPeerIdLength = MaxPeerIdLength;
+	type AddOrigin = pallet_lo_node_authorization::EnsureNodeManager;
+	type RemoveOrigin = NodeManagerOrRoot;
+	type SwapOrigin = pallet_lo_node_authorization::EnsureNodeManager;
+	type ResetOrigin = pallet_lo_node_authorization::EnsureNodeManager;
+	type WeightInfo = ();
+}
+
+pub const LEGAL_OFFICER_1: u64 = 1;
+pub const LEGAL_OFFICER_2: u64 = 2;
+pub const NOT_LEGAL_OFFICER: u64 = 3;
+
+pub struct EnsureLegalOfficer;
+impl EnsureOrigin<Origin> for EnsureLegalOfficer {
+	type Success = u64;
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		let origin: Result<system::RawOrigin<u64>, Origin> = o.into();
+		match origin {
+			Ok(system::RawOrigin::Signed(who)) if who == LEGAL_OFFICER_1 || who == LEGAL_OFFICER_2 => Ok(who),
+			Ok(other) => Err(Origin::from(other)),
+			Err(origin) => Err(origin),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::signed(LEGAL_OFFICER_1)
+	}
+}
+
+parameter_types! {
+	pub const MaxNodesPerLegalOfficer: u32 = 2;
+}
+
+impl pallet_lo_node_authorization::Config for Test {
+	type LegalOfficerOrigin = EnsureLegalOfficer;
+	type RevokeOrigin = EnsureRoot<u64>;
+	type MaxNodesPerLegalOfficer = MaxNodesPerLegalOfficer;
+	type NodeOrigin = Origin;
+	type Event = Event;
+}
+
+pub fn peer(id: u8) -> PeerId {
+	PeerId(vec![id])
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = GenesisConfig {
+		frame_system: Some(Default::default()),
+		pallet_node_authorization: Some(pallet_node_authorization::GenesisConfig::<Test> {
+			nodes: vec![
+				(peer(1), LEGAL_OFFICER_1),
+				(peer(2), LEGAL_OFFICER_1),
+				(peer(3), LEGAL_OFFICER_2),
+			],
+		}),
+		pallet_lo_node_authorization: Some(Default::default()),
+	}.build_storage().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
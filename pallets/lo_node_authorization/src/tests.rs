@@ -0,0 +1,118 @@
+use crate::{mock::*, migrate, Error, NodeAuthorizationStorageVersion, NodeCount, PalletStorageVersion};
+use frame_support::{assert_err, assert_ok, storage::StoragePrefixedMap};
+use frame_system::RawOrigin;
+use sp_runtime::DispatchError;
+
+#[test]
+fn it_counts_genesis_nodes() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(LoNodeAuthorization::node_count(LEGAL_OFFICER_1), 2);
+		assert_eq!(LoNodeAuthorization::node_count(LEGAL_OFFICER_2), 1);
+		assert_eq!(LoNodeAuthorization::pallet_storage_version(), NodeAuthorizationStorageVersion::V1NodeCount);
+	});
+}
+
+#[test]
+fn it_counts_existing_nodes_on_migration() {
+	new_test_ext().execute_with(|| {
+		<NodeCount<Test>>::remove_all();
+		<PalletStorageVersion<Test>>::kill();
+
+		migrate::<Test>();
+
+		assert_eq!(LoNodeAuthorization::node_count(LEGAL_OFFICER_1), 2);
+		assert_eq!(LoNodeAuthorization::node_count(LEGAL_OFFICER_2), 1);
+		assert_eq!(LoNodeAuthorization::pallet_storage_version(), NodeAuthorizationStorageVersion::V1NodeCount);
+	});
+}
+
+#[test]
+fn it_migrates_once() {
+	new_test_ext().execute_with(|| {
+		<NodeCount<Test>>::remove(LEGAL_OFFICER_1);
+
+		migrate::<Test>();
+
+		assert_eq!(LoNodeAuthorization::node_count(LEGAL_OFFICER_1), 0);
+	});
+}
+
+#[test]
+fn it_enforces_quota_on_genesis_nodes() {
+	new_test_ext().execute_with(|| {
+		assert_err!(LoNodeAuthorization::claim_node(Origin::signed(LEGAL_OFFICER_1), peer(4)), Error::<Test>::TooManyNodes);
+		assert_ok!(LoNodeAuthorization::claim_node(Origin::signed(LEGAL_OFFICER_2), peer(4)));
+		assert_eq!(LoNodeAuthorization::node_count(LEGAL_OFFICER_2), 2);
+	});
+}
+
+#[test]
+fn it_fails_claiming_if_not_legal_officer() {
+	new_test_ext().execute_with(|| {
+		assert_err!(LoNodeAuthorization::claim_node(Origin::signed(NOT_LEGAL_OFFICER), peer(4)), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn it_transfers_node() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LoNodeAuthorization::transfer_node(Origin::signed(LEGAL_OFFICER_1), peer(1), LEGAL_OFFICER_2));
+		assert_eq!(LoNodeAuthorization::node_count(LEGAL_OFFICER_1), 1);
+		assert_eq!(LoNodeAuthorization::node_count(LEGAL_OFFICER_2), 2);
+		assert_eq!(NodeAuthorization::owners(peer(1)), LEGAL_OFFICER_2);
+	});
+}
+
+#[test]
+fn it_revokes_node() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LoNodeAuthorization::revoke_node(RawOrigin::Root.into(), peer(1)));
+		assert_eq!(LoNodeAuthorization::node_count(LEGAL_OFFICER_1), 1);
+		assert!(!NodeAuthorization::well_known_nodes().contains(&peer(1)));
+	});
+}
+
+#[test]
+fn it_rejects_self_transfer() {
+	new_test_ext().execute_with(|| {
+		assert_err!(
+			LoNodeAuthorization::transfer_node(Origin::signed(LEGAL_OFFICER_2), peer(3), LEGAL_OFFICER_2),
+			Error::<Test>::SelfTransfer
+		);
+		assert_eq!(LoNodeAuthorization::node_count(LEGAL_OFFICER_2), 1);
+	});
+}
+
+#[test]
+fn root_only_removes_nodes_directly() {
+	new_test_ext().execute_with(|| {
+		assert_err!(
+			NodeAuthorization::add_well_known_node(RawOrigin::Root.into(), peer(4), LEGAL_OFFICER_2),
+			DispatchError::BadOrigin
+		);
+		assert_err!(
+			NodeAuthorization::swap_well_known_node(RawOrigin::Root.into(), peer(1), peer(4)),
+			DispatchError::BadOrigin
+		);
+		assert_err!(
+			NodeAuthorization::reset_well_known_nodes(RawOrigin::Root.into(), vec![(peer(4), LEGAL_OFFICER_2)]),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(NodeAuthorization::remove_well_known_node(RawOrigin::Root.into(), peer(1)));
+		assert_eq!(LoNodeAuthorization::node_count(LEGAL_OFFICER_1), 2);
+		assert_ok!(LoNodeAuthorization::sync_node_counts(RawOrigin::Root.into()));
+		assert_eq!(LoNodeAuthorization::node_count(LEGAL_OFFICER_1), 1);
+	});
+}
+
+#[test]
+fn signed_origins_do_not_change_nodes_directly() {
+	new_test_ext().execute_with(|| {
+		assert_err!(
+			NodeAuthorization::add_well_known_node(Origin::signed(LEGAL_OFFICER_1), peer(4), LEGAL_OFFICER_1),
+			DispatchError::BadOrigin
+		);
+		assert_err!(LoNodeAuthorization::sync_node_counts(Origin::signed(LEGAL_OFFICER_1)), DispatchError::BadOrigin);
+	});
+}
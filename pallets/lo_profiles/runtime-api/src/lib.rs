@@ -0,0 +1,30 @@
+//! Runtime API definition for the legal officer profiles pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::OpaquePeerId as PeerId;
+use sp_std::prelude::*;
+
+/// The public profile of a legal officer: how it is presented to users and how its backend,
+/// node and encryption key are found.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct LegalOfficerProfile {
+	pub display_name: Vec<u8>,
+	pub region: Vec<u8>,
+	pub base_url: Vec<u8>,
+	pub node_id: Option<PeerId>,
+	pub encryption_key: Vec<u8>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait LegalOfficerProfilesApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// Returns the active legal officers along with their profile.
+		fn legal_officers() -> Vec<(AccountId, LegalOfficerProfile)>;
+	}
+}
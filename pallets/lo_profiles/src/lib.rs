@@ -0,0 +1,197 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! On-chain profiles of legal officers.
+//!
+//! Each legal officer publishes a profile giving its display name, its region, the base URL of
+//! its backend, the peer ID of its node and its public encryption key. Wallets discover active
+//! legal officers and their endpoints through the `LegalOfficerProfilesApi` runtime API instead
+//! of hardcoding them. The profile of an account which is not a legal officer anymore may be removed
+//! by anyone.
+
+pub use pallet::*;
+pub use pallet_lo_profiles_runtime_api::LegalOfficerProfile;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+
+/// Storage version of the pallet.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ProfilesStorageVersion {
+	V0LegalOfficerSet,
+	V1Profiles,
+}
+
+impl Default for ProfilesStorageVersion {
+	fn default() -> ProfilesStorageVersion {
+		ProfilesStorageVersion::V1Profiles
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+	use super::{LegalOfficerProfile, ProfilesStorageVersion};
+
+	/// Lists the accounts currently registered as legal officers.
+	pub trait LegalOfficers<AccountId> {
+		fn legal_officers() -> Vec<AccountId>;
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The origin of legal officers
+		type LegalOfficerOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+		/// The current legal officers
+		type LegalOfficers: LegalOfficers<Self::AccountId>;
+
+		/// The maximum size of each field of a profile
+		type MaxProfileFieldSize: Get<u32>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// The profile of each legal officer
+	#[pallet::storage]
+	#[pallet::getter(fn profile)]
+	pub type Profiles<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, LegalOfficerProfile>;
+
+	/// Storage version of the pallet, absent before profiles existed
+	#[pallet::storage]
+	#[pallet::getter(fn pallet_storage_version)]
+	pub type PalletStorageVersion<T> = StorageValue<_, ProfilesStorageVersion, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Initial profiles of legal officers
+		pub profiles: Vec<(T::AccountId, LegalOfficerProfile)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				profiles: Vec::new(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (legal_officer, profile) in self.profiles.iter() {
+				<Profiles<T>>::insert(legal_officer, profile);
+			}
+			<PalletStorageVersion<T>>::put(ProfilesStorageVersion::V1Profiles);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when a legal officer updates its profile. [legal officer]
+		ProfileUpdated(T::AccountId),
+		/// Issued when the profile of a former legal officer is removed. [account]
+		ProfileRemoved(T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A field of the profile exceeds the maximum size
+		FieldTooLong,
+		/// The account has no profile
+		NoProfile,
+		/// The account is still a legal officer
+		StillLegalOfficer,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Sets the profile of the calling legal officer.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + 10_000_000)]
+		pub fn set_profile(
+			origin: OriginFor<T>,
+			profile: LegalOfficerProfile,
+		) -> DispatchResultWithPostInfo {
+			let who = T::LegalOfficerOrigin::ensure_origin(origin)?;
+			let max_size = T::MaxProfileFieldSize::get() as usize;
+			ensure!(
+				profile.display_name.len() <= max_size
+					&& profile.region.len() <= max_size
+					&& profile.base_url.len() <= max_size
+					&& profile.node_id.as_ref().map(|node_id| node_id.0.len()).unwrap_or(0) <= max_size
+					&& profile.encryption_key.len() <= max_size,
+				Error::<T>::FieldTooLong
+			);
+
+			<Profiles<T>>::insert(&who, profile);
+			Self::deposit_event(Event::ProfileUpdated(who));
+			Ok(().into())
+		}
+
+		/// Removes the profile of an account which left the legal officers.
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 1) + 10_000_000)]
+		pub fn remove_profile(
+			origin: OriginFor<T>,
+			account: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(<Profiles<T>>::contains_key(&account), Error::<T>::NoProfile);
+			ensure!(!T::LegalOfficers::legal_officers().contains(&account), Error::<T>::StillLegalOfficer);
+
+			<Profiles<T>>::remove(&account);
+			Self::deposit_event(Event::ProfileRemoved(account));
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Returns the current legal officers with their profile, an empty one if they did not
+		/// publish any.
+		pub fn legal_officers() -> Vec<(T::AccountId, LegalOfficerProfile)> {
+			T::LegalOfficers::legal_officers().into_iter()
+				.map(|legal_officer| {
+					let profile = <Profiles<T>>::get(&legal_officer).unwrap_or_default();
+					(legal_officer, profile)
+				})
+				.collect()
+		}
+	}
+
+	/// Creates an empty profile for each legal officer registered before profiles existed.
+	pub fn migrate<T: Config>() -> Weight {
+		if <PalletStorageVersion<T>>::exists() {
+			return T::DbWeight::get().reads(1);
+		}
+		let legal_officers = T::LegalOfficers::legal_officers();
+		let count = legal_officers.len() as Weight;
+		for legal_officer in legal_officers.into_iter() {
+			if !<Profiles<T>>::contains_key(&legal_officer) {
+				<Profiles<T>>::insert(&legal_officer, LegalOfficerProfile::default());
+			}
+		}
+		<PalletStorageVersion<T>>::put(ProfilesStorageVersion::V1Profiles);
+		T::DbWeight::get().reads_writes(1 + 2 * count, 1 + count)
+	}
+}
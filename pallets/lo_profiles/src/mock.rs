@@ -0,0 +1,123 @@
+use crate as pallet_lo_profiles;
+use crate::LegalOfficerProfile;
+use sp_core::H256;
+use frame_support::{parameter_types, traits::{EnsureOrigin, GenesisBuild}};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+};
+use frame_system as system;
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		LoProfiles: pallet_lo_profiles::{Module, Call, Storage, Event<T>, Config<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+}
+
+pub const LEGAL_OFFICER_1: u64 = 1;
+pub const LEGAL_OFFICER_2: u64 = 2;
+pub const NOT_LEGAL_OFFICER: u64 = 3;
+
+thread_local! {
+	static LEGAL_OFFICERS: RefCell<Vec<u64>> = RefCell::new(vec![LEGAL_OFFICER_1, LEGAL_OFFICER_2]);
+}
+
+/// Removes given account from the set of legal officers.
+pub fn remove_legal_officer(account: u64) {
+	LEGAL_OFFICERS.with(|legal_officers| legal_officers.borrow_mut().retain(|legal_officer| *legal_officer != account));
+}
+
+pub struct LegalOfficers;
+impl pallet_lo_profiles::LegalOfficers<u64> for LegalOfficers {
+	fn legal_officers() -> Vec<u64> {
+		LEGAL_OFFICERS.with(|legal_officers| legal_officers.borrow().clone())
+	}
+}
+
+pub struct EnsureLegalOfficer;
+impl EnsureOrigin<Origin> for EnsureLegalOfficer {
+	type Success = u64;
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		let origin: Result<system::RawOrigin<u64>, Origin> = o.into();
+		match origin {
+			Ok(system::RawOrigin::Signed(who)) if LEGAL_OFFICERS.with(|legal_officers| legal_officers.borrow().contains(&who)) => Ok(who),
+			Ok(other) => Err(Origin::from(other)),
+			Err(origin) => Err(origin),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::signed(LEGAL_OFFICER_1)
+	}
+}
+
+parameter_types! {
+	pub const MaxProfileFieldSize: u32 = 32;
+}
+
+impl pallet_lo_profiles::Config for Test {
+	type LegalOfficerOrigin = EnsureLegalOfficer;
+	type LegalOfficers = LegalOfficers;
+	type MaxProfileFieldSize = MaxProfileFieldSize;
+	type Event = Event;
+}
+
+pub fn genesis_profile() -> LegalOfficerProfile {
+	LegalOfficerProfile {
+		display_name: b"Legal Officer 1".to_vec(),
+		region: b"Europe".to_vec(),
+		base_url: b"https://lo1.logion.network".to_vec(),
+		node_id: None,
+		encryption_key: b"key1".to_vec(),
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_lo_profiles::GenesisConfig::<Test> {
+		profiles: vec![(LEGAL_OFFICER_1, genesis_profile())],
+	}.assimilate_storage(&mut storage).unwrap();
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
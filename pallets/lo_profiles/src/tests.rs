@@ -0,0 +1,128 @@
+use crate::{mock::*, migrate, Error, Event as LoProfilesEvent, LegalOfficerProfile, PalletStorageVersion, Profiles, ProfilesStorageVersion};
+use frame_support::{assert_err, assert_ok, traits::Get};
+use sp_core::OpaquePeerId;
+use sp_runtime::DispatchError;
+
+fn profile() -> LegalOfficerProfile {
+	LegalOfficerProfile {
+		display_name: b"Legal Officer 2".to_vec(),
+		region: b"America".to_vec(),
+		base_url: b"https://lo2.logion.network".to_vec(),
+		node_id: Some(OpaquePeerId(b"node2".to_vec())),
+		encryption_key: b"key2".to_vec(),
+	}
+}
+
+fn last_event() -> Event {
+	System::events().pop().unwrap().event
+}
+
+#[test]
+fn it_sets_profile() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LoProfiles::set_profile(Origin::signed(LEGAL_OFFICER_2), profile()));
+		assert_eq!(LoProfiles::profile(LEGAL_OFFICER_2), Some(profile()));
+		assert_eq!(last_event(), Event::pallet_lo_profiles(LoProfilesEvent::ProfileUpdated(LEGAL_OFFICER_2)));
+	});
+}
+
+#[test]
+fn it_fails_setting_profile_if_not_legal_officer() {
+	new_test_ext().execute_with(|| {
+		assert_err!(LoProfiles::set_profile(Origin::signed(NOT_LEGAL_OFFICER), profile()), DispatchError::BadOrigin);
+		assert_err!(LoProfiles::set_profile(Origin::root(), profile()), DispatchError::BadOrigin);
+		assert_eq!(LoProfiles::profile(NOT_LEGAL_OFFICER), None);
+	});
+}
+
+#[test]
+fn it_fails_setting_profile_with_too_long_field() {
+	new_test_ext().execute_with(|| {
+		let too_long = vec![b'a'; MaxProfileFieldSize::get() as usize + 1];
+		let profiles = vec![
+			LegalOfficerProfile { display_name: too_long.clone(), ..profile() },
+			LegalOfficerProfile { base_url: too_long.clone(), ..profile() },
+			LegalOfficerProfile { node_id: Some(OpaquePeerId(too_long.clone())), ..profile() },
+		];
+		for profile in profiles {
+			assert_err!(LoProfiles::set_profile(Origin::signed(LEGAL_OFFICER_2), profile), Error::<Test>::FieldTooLong);
+		}
+		assert_eq!(LoProfiles::profile(LEGAL_OFFICER_2), None);
+	});
+}
+
+#[test]
+fn it_removes_profile_of_former_legal_officer() {
+	new_test_ext().execute_with(|| {
+		remove_legal_officer(LEGAL_OFFICER_1);
+		assert_ok!(LoProfiles::remove_profile(Origin::signed(NOT_LEGAL_OFFICER), LEGAL_OFFICER_1));
+		assert_eq!(LoProfiles::profile(LEGAL_OFFICER_1), None);
+		assert_eq!(last_event(), Event::pallet_lo_profiles(LoProfilesEvent::ProfileRemoved(LEGAL_OFFICER_1)));
+	});
+}
+
+#[test]
+fn it_fails_removing_profile_of_legal_officer() {
+	new_test_ext().execute_with(|| {
+		assert_err!(LoProfiles::remove_profile(Origin::signed(NOT_LEGAL_OFFICER), LEGAL_OFFICER_1), Error::<Test>::StillLegalOfficer);
+		assert_eq!(LoProfiles::profile(LEGAL_OFFICER_1), Some(genesis_profile()));
+	});
+}
+
+#[test]
+fn it_fails_removing_missing_profile() {
+	new_test_ext().execute_with(|| {
+		assert_err!(LoProfiles::remove_profile(Origin::signed(NOT_LEGAL_OFFICER), NOT_LEGAL_OFFICER), Error::<Test>::NoProfile);
+	});
+}
+
+#[test]
+fn it_lists_legal_officers_with_profiles() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(LoProfiles::legal_officers(), vec![
+			(LEGAL_OFFICER_1, genesis_profile()),
+			(LEGAL_OFFICER_2, LegalOfficerProfile::default()),
+		]);
+
+		assert_ok!(LoProfiles::set_profile(Origin::signed(LEGAL_OFFICER_2), profile()));
+		assert_eq!(LoProfiles::legal_officers(), vec![
+			(LEGAL_OFFICER_1, genesis_profile()),
+			(LEGAL_OFFICER_2, profile()),
+		]);
+	});
+}
+
+#[test]
+fn it_lists_only_current_legal_officers() {
+	new_test_ext().execute_with(|| {
+		remove_legal_officer(LEGAL_OFFICER_1);
+		assert_eq!(LoProfiles::legal_officers(), vec![(LEGAL_OFFICER_2, LegalOfficerProfile::default())]);
+		assert!(LoProfiles::profile(LEGAL_OFFICER_1).is_some());
+	});
+}
+
+#[test]
+fn it_migrates_once() {
+	new_test_ext().execute_with(|| {
+		<PalletStorageVersion<Test>>::kill();
+
+		migrate::<Test>();
+		assert_eq!(LoProfiles::profile(LEGAL_OFFICER_1), Some(genesis_profile()));
+		assert_eq!(LoProfiles::profile(LEGAL_OFFICER_2), Some(LegalOfficerProfile::default()));
+		assert!(<PalletStorageVersion<Test>>::exists());
+		assert_eq!(LoProfiles::pallet_storage_version(), ProfilesStorageVersion::V1Profiles);
+
+		<Profiles<Test>>::remove(LEGAL_OFFICER_2);
+		migrate::<Test>();
+		assert_eq!(LoProfiles::profile(LEGAL_OFFICER_2), None);
+	});
+}
+
+#[test]
+fn it_does_not_migrate_genesis_storage() {
+	new_test_ext().execute_with(|| {
+		assert!(<PalletStorageVersion<Test>>::exists());
+		migrate::<Test>();
+		assert_eq!(LoProfiles::profile(LEGAL_OFFICER_2), None);
+	});
+}
@@ -0,0 +1,383 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Storage deposits for LOC data.
+//!
+//! A deposit proportional to its encoded size is reserved from the account adding each item to a
+//! LOC: metadata, files, links (added by the owner) and collection items (added by the requester).
+//! Deposits of a LOC are released once it is voided or does not exist anymore.
+//!
+//! `pallet_logion_loc` has no hook, deposits are reserved by the `ReserveLocDeposits` signed
+//! extension: the runtime tells which items a call adds (`LocDepositCalls`), deposits are reserved
+//! before dispatch and kept if the dispatch succeeds.
+//!
+//! Items added before deposits were introduced are charged by a lazy migration: each block
+//! reserves the deposits of a batch of LOCs, until all LOCs are migrated.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::{Currency, ReservableCurrency},
+};
+use sp_runtime::{
+	RuntimeDebug,
+	traits::{DispatchInfoOf, PostDispatchInfoOf, Saturating, SignedExtension},
+	transaction_validity::{InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction},
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// An item added to a LOC: the account paying its deposit, i.e. the signer of the call adding it,
+/// and its encoded size in bytes.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AddedItem<LocId, AccountId> {
+	pub loc_id: LocId,
+	pub submitter: AccountId,
+	pub size: u32,
+}
+
+/// Tells which items a call adds to LOCs.
+pub trait LocDepositCalls<Call, LocId, AccountId> {
+	fn added_items(who: &AccountId, call: &Call) -> Vec<AddedItem<LocId, AccountId>>;
+}
+
+/// Storage version of the pallet.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum DepositsStorageVersion {
+	V0NoDeposit,
+	V1Deposits,
+}
+
+impl Default for DepositsStorageVersion {
+	fn default() -> DepositsStorageVersion {
+		DepositsStorageVersion::V1Deposits
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use codec::{Decode, Encode};
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		storage::StoragePrefixedMap,
+		traits::{Currency, ReservableCurrency},
+		Blake2_128Concat, ReversibleStorageHasher,
+	};
+	use frame_system::pallet_prelude::*;
+	use pallet_logion_loc::{CollectionItemsMap, LocMap, Requester};
+	use sp_runtime::traits::{Saturating, Zero};
+	use sp_std::prelude::*;
+	use super::{BalanceOf, DepositsStorageVersion, LocDepositCalls};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_logion_loc::Config {
+		/// The currency deposits are reserved from
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit of an item, whatever its size
+		type DepositBase: Get<BalanceOf<Self>>;
+
+		/// The deposit of each byte of an item
+		type DepositPerByte: Get<BalanceOf<Self>>;
+
+		/// The items added to LOCs by calls
+		type LocDepositCalls: LocDepositCalls<<Self as frame_system::Config>::Call, Self::LocId, Self::AccountId>;
+
+		/// The maximum number of LOCs migrated in a block
+		type MigrationBatchSize: Get<u32>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Deposits reserved from each submitter of a LOC's items
+	#[pallet::storage]
+	#[pallet::getter(fn deposit_of)]
+	pub type Deposits<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::LocId, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// Storage version of the pallet
+	#[pallet::storage]
+	#[pallet::getter(fn pallet_storage_version)]
+	pub type PalletStorageVersion<T> = StorageValue<_, DepositsStorageVersion, ValueQuery>;
+
+	/// The raw `LocMap` key of the last migrated LOC, set while the migration is in progress
+	#[pallet::storage]
+	#[pallet::getter(fn migration_cursor)]
+	pub type MigrationCursor<T> = StorageValue<_, Vec<u8>>;
+
+	#[pallet::genesis_config]
+	#[derive(Default)]
+	pub struct GenesisConfig {}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			// LOCs imported at genesis were added without deposit.
+			migrate_batch::<T>(<LocMap<T>>::final_prefix().to_vec(), u32::MAX);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", T::LocId = "LocId", BalanceOf<T> = "Balance")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when a deposit is reserved for an item. [LOC, submitter, amount]
+		DepositReserved(T::LocId, T::AccountId, BalanceOf<T>),
+		/// Issued when the deposits of a LOC are released. [LOC, total]
+		DepositsReleased(T::LocId, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The LOC is neither void nor removed
+		LocNotVoid,
+		/// The LOC has no deposit
+		NoDeposit,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			match <MigrationCursor<T>>::get() {
+				Some(cursor) => migrate_batch::<T>(cursor, T::MigrationBatchSize::get()),
+				None => T::DbWeight::get().reads(1),
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Releases the deposits of a void or removed LOC.
+		#[pallet::weight(T::DbWeight::get().reads_writes(11, 20) + 100_000_000)]
+		pub fn release(
+			origin: OriginFor<T>,
+			loc_id: T::LocId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let void = <LocMap<T>>::get(&loc_id).map(|loc| loc.void_info.is_some()).unwrap_or(true);
+			ensure!(void, Error::<T>::LocNotVoid);
+			ensure!(<Deposits<T>>::iter_prefix(&loc_id).next().is_some(), Error::<T>::NoDeposit);
+
+			let mut total: BalanceOf<T> = Zero::zero();
+			for (submitter, amount) in <Deposits<T>>::drain_prefix(&loc_id) {
+				T::Currency::unreserve(&submitter, amount);
+				total = total.saturating_add(amount);
+			}
+			Self::deposit_event(Event::DepositsReleased(loc_id, total));
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The deposit of an item of given size.
+		pub fn deposit(size: u32) -> BalanceOf<T> {
+			T::DepositBase::get().saturating_add(T::DepositPerByte::get().saturating_mul(size.into()))
+		}
+
+		pub(super) fn record(loc_id: &T::LocId, submitter: &T::AccountId, amount: BalanceOf<T>) {
+			<Deposits<T>>::mutate(loc_id, submitter, |deposit| *deposit = deposit.saturating_add(amount));
+			Self::deposit_event(Event::DepositReserved(loc_id.clone(), submitter.clone(), amount));
+		}
+
+		/// Reserves as much of given amount as possible.
+		fn reserve_affordable(who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+			if T::Currency::reserve(who, amount).is_ok() {
+				return amount;
+			}
+			let affordable = T::Currency::free_balance(who).saturating_sub(T::Currency::minimum_balance()).min(amount);
+			if !affordable.is_zero() && T::Currency::reserve(who, affordable).is_ok() {
+				affordable
+			} else {
+				Zero::zero()
+			}
+		}
+	}
+
+	/// Starts the migration of LOCs created before deposits were introduced, LOCs being then
+	/// migrated by batches at the beginning of each block.
+	pub fn migrate<T: Config>() -> Weight {
+		let v0 = !<PalletStorageVersion<T>>::exists()
+			|| <PalletStorageVersion<T>>::get() == DepositsStorageVersion::V0NoDeposit;
+		if !v0 || <MigrationCursor<T>>::exists() {
+			return T::DbWeight::get().reads(2);
+		}
+		<MigrationCursor<T>>::put(<LocMap<T>>::final_prefix().to_vec());
+		T::DbWeight::get().reads_writes(2, 1)
+	}
+
+	/// Migrates at most `limit` LOCs following given raw key of `LocMap`, then records where to
+	/// resume or completes the migration.
+	fn migrate_batch<T: Config>(mut cursor: Vec<u8>, limit: u32) -> Weight {
+		let prefix = <LocMap<T>>::final_prefix();
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+		for _ in 0..limit {
+			let key = match sp_io::storage::next_key(&cursor).filter(|key| key.starts_with(&prefix)) {
+				Some(key) => key,
+				None => {
+					<MigrationCursor<T>>::kill();
+					<PalletStorageVersion<T>>::put(DepositsStorageVersion::V1Deposits);
+					return weight.saturating_add(T::DbWeight::get().writes(1));
+				},
+			};
+			weight = weight.saturating_add(migrate_loc::<T>(&key[prefix.len()..]));
+			cursor = key;
+		}
+		<MigrationCursor<T>>::put(cursor);
+		weight
+	}
+
+	/// Reserves deposits for the items of a LOC, as much as their signers are able to pay. Deposits
+	/// already reserved for items added since the upgrade are deducted.
+	fn migrate_loc<T: Config>(hashed_key: &[u8]) -> Weight {
+		let mut reads: Weight = 1;
+		let mut writes: Weight = 0;
+		let loc = T::LocId::decode(&mut Blake2_128Concat::reverse(hashed_key)).ok()
+			.and_then(|loc_id| <LocMap<T>>::get(&loc_id).map(|loc| (loc_id, loc)));
+		let (loc_id, loc) = match loc {
+			Some((loc_id, loc)) if loc.void_info.is_none() => (loc_id, loc),
+			_ => return T::DbWeight::get().reads(reads),
+		};
+		let mut totals: Vec<(T::AccountId, BalanceOf<T>)> = Vec::new();
+		let mut add = |payer: &T::AccountId, size: usize| {
+			let amount = Pallet::<T>::deposit(size as u32);
+			match totals.iter_mut().find(|(candidate, _)| candidate == payer) {
+				Some((_, total)) => *total = total.saturating_add(amount),
+				None => totals.push((payer.clone(), amount)),
+			}
+		};
+		loc.metadata.iter().for_each(|item| add(&loc.owner, item.encoded_size()));
+		loc.files.iter().for_each(|file| add(&loc.owner, file.encoded_size()));
+		loc.links.iter().for_each(|link| add(&loc.owner, link.encoded_size()));
+		if let Requester::Account(requester) = &loc.requester {
+			for (item_id, item) in <CollectionItemsMap<T>>::iter_prefix(&loc_id) {
+				reads += 1;
+				add(requester, item_id.encoded_size() + item.encoded_size());
+			}
+		}
+		for (payer, total) in totals.into_iter() {
+			reads += 1;
+			let due = total.saturating_sub(<Deposits<T>>::get(&loc_id, &payer));
+			let reserved = Pallet::<T>::reserve_affordable(&payer, due);
+			if !reserved.is_zero() {
+				Pallet::<T>::record(&loc_id, &payer, reserved);
+				reads += 2;
+				writes += 3;
+			}
+		}
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+}
+
+/// Custom validity error: a submitter is not able to pay LOC deposits.
+pub const LOC_DEPOSIT_NOT_PAYABLE: u8 = 120;
+
+/// Reserves the deposits of items added to LOCs before dispatch and releases them if the dispatch
+/// fails.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ReserveLocDeposits<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> ReserveLocDeposits<T> {
+	/// Utility constructor. Used only in client/factory code.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+
+	fn payable_deposits(who: &T::AccountId, call: &<T as frame_system::Config>::Call)
+		-> Result<Vec<(T::LocId, T::AccountId, BalanceOf<T>)>, TransactionValidityError>
+	{
+		let deposits: Vec<(T::LocId, T::AccountId, BalanceOf<T>)> = T::LocDepositCalls::added_items(who, call).into_iter()
+			.map(|item| (item.loc_id, item.submitter, Pallet::<T>::deposit(item.size)))
+			.collect();
+		let mut totals: Vec<(&T::AccountId, BalanceOf<T>)> = Vec::new();
+		for (_, submitter, amount) in deposits.iter() {
+			match totals.iter_mut().find(|(candidate, _)| *candidate == submitter) {
+				Some((_, total)) => *total = total.saturating_add(*amount),
+				None => totals.push((submitter, *amount)),
+			}
+		}
+		for (submitter, total) in totals.into_iter() {
+			if !T::Currency::can_reserve(submitter, total) {
+				return Err(InvalidTransaction::Custom(LOC_DEPOSIT_NOT_PAYABLE).into());
+			}
+		}
+		Ok(deposits)
+	}
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for ReserveLocDeposits<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ReserveLocDeposits")
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for ReserveLocDeposits<T> {
+	const IDENTIFIER: &'static str = "ReserveLocDeposits";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = Vec<(T::LocId, T::AccountId, BalanceOf<T>)>;
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> { Ok(()) }
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		Self::payable_deposits(who, call)?;
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let deposits = Self::payable_deposits(who, call)?;
+		for (_, submitter, amount) in deposits.iter() {
+			T::Currency::reserve(submitter, *amount)
+				.map_err(|_| InvalidTransaction::Custom(LOC_DEPOSIT_NOT_PAYABLE))?;
+		}
+		Ok(deposits)
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		_info: &DispatchInfoOf<Self::Call>,
+		_post_info: &PostDispatchInfoOf<Self::Call>,
+		_len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		for (loc_id, submitter, amount) in pre.into_iter() {
+			if result.is_ok() {
+				Pallet::<T>::record(&loc_id, &submitter, amount);
+			} else {
+				T::Currency::unreserve(&submitter, amount);
+			}
+		}
+		Ok(())
+	}
+}
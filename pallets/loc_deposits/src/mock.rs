@@ -0,0 +1,151 @@
+use crate as pallet_loc_deposits;
+use sp_core::H256;
+use codec::Encode;
+use frame_support::{parameter_types, traits::EnsureOrigin};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header, BuildStorage,
+};
+use frame_system as system;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		LogionLoc: pallet_logion_loc::{Module, Call, Storage, Event<T>},
+		LocDeposits: pallet_loc_deposits::{Module, Call, Storage, Event<T>, Config},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxLocks: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = MaxLocks;
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+pub const LEGAL_OFFICER: u64 = 1;
+pub const REQUESTER: u64 = 2;
+
+pub struct EnsureLegalOfficer;
+impl EnsureOrigin<Origin> for EnsureLegalOfficer {
+	type Success = u64;
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		let origin: Result<system::RawOrigin<u64>, Origin> = o.into();
+		match origin {
+			Ok(system::RawOrigin::Signed(who)) if who == LEGAL_OFFICER => Ok(who),
+			Ok(other) => Err(Origin::from(other)),
+			Err(origin) => Err(origin),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::signed(LEGAL_OFFICER)
+	}
+}
+
+parameter_types! {
+	pub const MaxMetadataItemNameSize: usize = 255;
+	pub const MaxMetadataItemValueSize: usize = 255;
+	pub const MaxFileNatureSize: usize = 255;
+	pub const MaxLinkNatureSize: usize = 255;
+	pub const MaxCollectionItemDescriptionSize: usize = 255;
+}
+
+impl pallet_logion_loc::Config for Test {
+	type LocId = u32;
+	type Event = Event;
+	type Hash = H256;
+	type CreateOrigin = EnsureLegalOfficer;
+	type MaxMetadataItemNameSize = MaxMetadataItemNameSize;
+	type MaxMetadataItemValueSize = MaxMetadataItemValueSize;
+	type MaxFileNatureSize = MaxFileNatureSize;
+	type MaxLinkNatureSize = MaxLinkNatureSize;
+	type CollectionItemId = H256;
+	type MaxCollectionItemDescriptionSize = MaxCollectionItemDescriptionSize;
+	type WeightInfo = ();
+}
+
+pub struct LocDepositCalls;
+impl pallet_loc_deposits::LocDepositCalls<Call, u32, u64> for LocDepositCalls {
+	fn added_items(who: &u64, call: &Call) -> Vec<pallet_loc_deposits::AddedItem<u32, u64>> {
+		match call {
+			Call::LogionLoc(pallet_logion_loc::Call::add_metadata(loc_id, item)) =>
+				vec![pallet_loc_deposits::AddedItem { loc_id: *loc_id, submitter: *who, size: item.encoded_size() as u32 }],
+			_ => Vec::new(),
+		}
+	}
+}
+
+parameter_types! {
+	pub const DepositBase: u64 = 1;
+	pub const DepositPerByte: u64 = 1;
+	pub const MigrationBatchSize: u32 = 2;
+}
+
+impl pallet_loc_deposits::Config for Test {
+	type Currency = Balances;
+	type DepositBase = DepositBase;
+	type DepositPerByte = DepositPerByte;
+	type LocDepositCalls = LocDepositCalls;
+	type MigrationBatchSize = MigrationBatchSize;
+	type Event = Event;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = GenesisConfig {
+		frame_system: Some(Default::default()),
+		pallet_balances: Some(pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(LEGAL_OFFICER, 1000), (REQUESTER, 1000)],
+		}),
+		pallet_loc_deposits: Some(Default::default()),
+	}.build_storage().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,194 @@
+use crate::{mock::*, migrate, Deposits, DepositsStorageVersion, Error, Event as LocDepositsEvent, MigrationCursor, PalletStorageVersion, ReserveLocDeposits};
+use codec::Encode;
+use frame_support::{assert_err, assert_ok, dispatch::DispatchResult, traits::{Hooks, ReservableCurrency}, weights::GetDispatchInfo};
+use pallet_logion_loc::MetadataItem;
+use sp_runtime::traits::{Dispatchable, SignedExtension};
+
+const LOC_ID: u32 = 1;
+const OTHER_LOC_ID: u32 = 2;
+const THIRD_LOC_ID: u32 = 3;
+const LEN: usize = 10;
+
+fn metadata() -> MetadataItem<u64> {
+	MetadataItem {
+		name: b"name".to_vec(),
+		value: b"value".to_vec(),
+		submitter: LEGAL_OFFICER,
+	}
+}
+
+fn item_deposit() -> u64 {
+	LocDeposits::deposit(metadata().encoded_size() as u32)
+}
+
+/// Creates a LOC with an item added without deposit, like before deposits were introduced.
+fn create_loc_with_item(loc_id: u32) {
+	assert_ok!(LogionLoc::create_polkadot_transaction_loc(Origin::signed(LEGAL_OFFICER), loc_id, REQUESTER));
+	assert_ok!(LogionLoc::add_metadata(Origin::signed(LEGAL_OFFICER), loc_id, metadata()));
+}
+
+fn add_metadata(loc_id: u32) -> Call {
+	Call::LogionLoc(pallet_logion_loc::Call::add_metadata(loc_id, metadata()))
+}
+
+/// Reserves deposits and dispatches given call like the executive does.
+fn apply(who: u64, call: Call) -> DispatchResult {
+	let info = call.get_dispatch_info();
+	assert_ok!(ReserveLocDeposits::<Test>::new().validate(&who, &call, &info, LEN));
+	let pre = ReserveLocDeposits::<Test>::new().pre_dispatch(&who, &call, &info, LEN).unwrap();
+	let (post_info, result) = match call.dispatch(Origin::signed(who)) {
+		Ok(post_info) => (post_info, Ok(())),
+		Err(error) => (error.post_info, Err(error.error)),
+	};
+	assert_ok!(ReserveLocDeposits::<Test>::post_dispatch(pre, &info, &post_info, LEN, &result));
+	result
+}
+
+/// Starts the migration as if the pallet was upgraded from a version without deposits.
+fn start_migration() {
+	PalletStorageVersion::<Test>::kill();
+	migrate::<Test>();
+}
+
+fn run_block(n: u64) {
+	System::set_block_number(n);
+	LocDeposits::on_initialize(n);
+}
+
+fn migrated_locs() -> usize {
+	Deposits::<Test>::iter().count()
+}
+
+#[test]
+fn it_reserves_deposit_of_added_item() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LogionLoc::create_polkadot_transaction_loc(Origin::signed(LEGAL_OFFICER), LOC_ID, REQUESTER));
+		assert_ok!(apply(LEGAL_OFFICER, add_metadata(LOC_ID)));
+		assert_eq!(Balances::reserved_balance(LEGAL_OFFICER), item_deposit());
+		assert_eq!(LocDeposits::deposit_of(LOC_ID, LEGAL_OFFICER), item_deposit());
+	});
+}
+
+#[test]
+fn it_releases_deposit_of_failed_dispatch() {
+	new_test_ext().execute_with(|| {
+		assert!(apply(LEGAL_OFFICER, add_metadata(LOC_ID)).is_err());
+		assert_eq!(Balances::reserved_balance(LEGAL_OFFICER), 0);
+		assert_eq!(LocDeposits::deposit_of(LOC_ID, LEGAL_OFFICER), 0);
+	});
+}
+
+#[test]
+fn it_migrates_locs_by_batches() {
+	new_test_ext().execute_with(|| {
+		create_loc_with_item(LOC_ID);
+		create_loc_with_item(OTHER_LOC_ID);
+		create_loc_with_item(THIRD_LOC_ID);
+		start_migration();
+		assert!(MigrationCursor::<Test>::exists());
+
+		run_block(2);
+		assert_eq!(migrated_locs(), 2);
+		assert!(MigrationCursor::<Test>::exists());
+		assert!(!PalletStorageVersion::<Test>::exists());
+
+		run_block(3);
+		assert_eq!(migrated_locs(), 3);
+		assert_eq!(Balances::reserved_balance(LEGAL_OFFICER), 3 * item_deposit());
+	});
+}
+
+#[test]
+fn it_completes_migration() {
+	new_test_ext().execute_with(|| {
+		create_loc_with_item(LOC_ID);
+		start_migration();
+
+		run_block(2);
+		assert!(!MigrationCursor::<Test>::exists());
+		assert!(PalletStorageVersion::<Test>::exists());
+		assert_eq!(LocDeposits::pallet_storage_version(), DepositsStorageVersion::V1Deposits);
+
+		run_block(3);
+		assert_eq!(Balances::reserved_balance(LEGAL_OFFICER), item_deposit());
+	});
+}
+
+#[test]
+fn it_does_not_restart_completed_migration() {
+	new_test_ext().execute_with(|| {
+		migrate::<Test>();
+		assert!(!MigrationCursor::<Test>::exists());
+	});
+}
+
+#[test]
+fn it_deducts_deposits_recorded_since_upgrade() {
+	new_test_ext().execute_with(|| {
+		create_loc_with_item(LOC_ID);
+		start_migration();
+		assert_ok!(apply(LEGAL_OFFICER, add_metadata(LOC_ID)));
+
+		run_block(2);
+		assert_eq!(Balances::reserved_balance(LEGAL_OFFICER), 2 * item_deposit());
+		assert_eq!(LocDeposits::deposit_of(LOC_ID, LEGAL_OFFICER), 2 * item_deposit());
+	});
+}
+
+#[test]
+fn it_skips_void_locs_on_migration() {
+	new_test_ext().execute_with(|| {
+		create_loc_with_item(LOC_ID);
+		assert_ok!(LogionLoc::make_void(Origin::signed(LEGAL_OFFICER), LOC_ID));
+		start_migration();
+
+		run_block(2);
+		assert_eq!(migrated_locs(), 0);
+		assert_eq!(Balances::reserved_balance(LEGAL_OFFICER), 0);
+		assert!(!MigrationCursor::<Test>::exists());
+	});
+}
+
+#[test]
+fn it_releases_deposits_of_void_loc() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LogionLoc::create_polkadot_transaction_loc(Origin::signed(LEGAL_OFFICER), LOC_ID, REQUESTER));
+		assert_ok!(apply(LEGAL_OFFICER, add_metadata(LOC_ID)));
+		assert_ok!(LogionLoc::make_void(Origin::signed(LEGAL_OFFICER), LOC_ID));
+
+		assert_ok!(LocDeposits::release(Origin::signed(REQUESTER), LOC_ID));
+		assert_eq!(Balances::reserved_balance(LEGAL_OFFICER), 0);
+		assert_eq!(LocDeposits::deposit_of(LOC_ID, LEGAL_OFFICER), 0);
+		assert_eq!(System::events().pop().unwrap().event, Event::pallet_loc_deposits(LocDepositsEvent::DepositsReleased(LOC_ID, item_deposit())));
+	});
+}
+
+#[test]
+fn it_releases_deposits_of_removed_loc() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::reserve(&REQUESTER, 10));
+		Deposits::<Test>::insert(LOC_ID, REQUESTER, 10);
+
+		assert_ok!(LocDeposits::release(Origin::signed(LEGAL_OFFICER), LOC_ID));
+		assert_eq!(Balances::reserved_balance(REQUESTER), 0);
+		assert_eq!(Deposits::<Test>::iter_prefix(LOC_ID).count(), 0);
+	});
+}
+
+#[test]
+fn it_fails_releasing_deposits_of_loc_not_void() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LogionLoc::create_polkadot_transaction_loc(Origin::signed(LEGAL_OFFICER), LOC_ID, REQUESTER));
+		assert_ok!(apply(LEGAL_OFFICER, add_metadata(LOC_ID)));
+
+		assert_err!(LocDeposits::release(Origin::signed(REQUESTER), LOC_ID), Error::<Test>::LocNotVoid);
+		assert_eq!(Balances::reserved_balance(LEGAL_OFFICER), item_deposit());
+	});
+}
+
+#[test]
+fn it_fails_releasing_without_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_err!(LocDeposits::release(Origin::signed(REQUESTER), LOC_ID), Error::<Test>::NoDeposit);
+	});
+}
@@ -0,0 +1,334 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Escrow bound to a Transaction LOC.
+//!
+//! The requester of an open Transaction LOC locks LGNT or an asset of `pallet_assets` for a
+//! beneficiary. Funds are held by an account of the escrow, derived from the pallet's id, until
+//! the escrow is settled:
+//! * once the owner of the LOC closed it, remaining funds are paid to the beneficiary;
+//! * if the LOC is voided, or if it is still open after the escrow's deadline, remaining funds are
+//!   refunded to the requester.
+//!
+//! Settlement is permissionless. Before that, the requester or the owner of the LOC may release
+//! part of the funds to the beneficiary, e.g. when a milestone of the deal is reached. A release
+//! leaving less than the existential deposit (LGNT) or the minimum balance (asset) pays all
+//! remaining funds, so that no dust is left in the escrow's account.
+//!
+//! Payouts of a regulated asset require the beneficiary to be allowed to receive it at payout
+//! time. If a closed LOC's beneficiary is not allowed anymore, funds are refunded to the
+//! requester once the deadline is passed.
+//!
+//! Assets are only held by existing accounts, an escrow's account holding an asset is therefore
+//! provided for until the escrow is removed.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+
+/// The asset locked by an escrow.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum EscrowAsset<AssetId> {
+	/// The native currency (LGNT)
+	Native,
+	/// An asset of `pallet_assets`
+	Asset(AssetId),
+}
+
+/// An escrow and its remaining funds.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Escrow<AccountId, LocId, AssetId, Balance, BlockNumber> {
+	pub loc_id: LocId,
+	pub depositor: AccountId,
+	pub beneficiary: AccountId,
+	pub asset: EscrowAsset<AssetId>,
+	pub remaining: Balance,
+	pub deadline: BlockNumber,
+}
+
+pub type EscrowOf<T> = Escrow<
+	<T as frame_system::Config>::AccountId,
+	<T as pallet_logion_loc::Config>::LocId,
+	<T as pallet_assets::Config>::AssetId,
+	<T as pallet_assets::Config>::Balance,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+pub type EscrowId = u64;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement::{self, AllowDeath, KeepAlive}},
+		transactional,
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use pallet_logion_loc::{LocMap, LocType, Requester};
+	use pallet_regulated_assets::AssetRecipients;
+	use sp_runtime::{ModuleId, traits::{AccountIdConversion, Saturating, StaticLookup, Zero}};
+	use super::{EscrowAsset, EscrowId, EscrowOf};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_assets::Config + pallet_logion_loc::Config {
+		/// The native currency, sharing the balance type of assets
+		type NativeCurrency: Currency<Self::AccountId, Balance = <Self as pallet_assets::Config>::Balance>;
+
+		/// Recipients allowed for each asset
+		type AssetRecipients: AssetRecipients<Self::AssetId, Self::AccountId>;
+
+		/// The id from which accounts holding escrowed funds are derived
+		type EscrowModuleId: Get<ModuleId>;
+
+		/// The maximum number of blocks before the deadline of an escrow
+		type MaxTimeout: Get<Self::BlockNumber>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Open escrows
+	#[pallet::storage]
+	#[pallet::getter(fn escrow)]
+	pub type Escrows<T: Config> = StorageMap<_, Twox64Concat, EscrowId, EscrowOf<T>>;
+
+	/// Open escrows of each LOC
+	#[pallet::storage]
+	#[pallet::getter(fn loc_escrows)]
+	pub type LocEscrows<T: Config> = StorageDoubleMap<_, Blake2_128Concat, T::LocId, Twox64Concat, EscrowId, ()>;
+
+	/// The id of the next escrow
+	#[pallet::storage]
+	#[pallet::getter(fn next_escrow_id)]
+	pub type NextEscrowId<T> = StorageValue<_, EscrowId, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", T::LocId = "LocId", T::Balance = "Balance", T::BlockNumber = "BlockNumber")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when funds are locked. [escrow, LOC, depositor, beneficiary, amount, deadline]
+		EscrowCreated(EscrowId, T::LocId, T::AccountId, T::AccountId, T::Balance, T::BlockNumber),
+		/// Issued when funds are paid to the beneficiary. [escrow, beneficiary, amount, remaining]
+		EscrowReleased(EscrowId, T::AccountId, T::Balance, T::Balance),
+		/// Issued when remaining funds are refunded to the depositor. [escrow, depositor, amount]
+		EscrowRefunded(EscrowId, T::AccountId, T::Balance),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The LOC does not exist
+		LocNotFound,
+		/// The LOC is not a Transaction LOC
+		NotTransactionLoc,
+		/// The LOC is closed or void
+		LocNotOpen,
+		/// The caller is not the requester of the LOC
+		NotRequester,
+		/// The beneficiary is not allowed to receive the asset
+		BeneficiaryNotAllowed,
+		/// The amount is zero or exceeds the remaining funds
+		InvalidAmount,
+		/// The amount of LGNT is below the existential deposit
+		AmountBelowMinimum,
+		/// The timeout is zero or too long
+		InvalidTimeout,
+		/// The escrow does not exist
+		EscrowNotFound,
+		/// The caller is neither the depositor nor the owner of the LOC
+		NotAllowed,
+		/// The LOC is still open and the deadline is not reached
+		NotSettleable,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Locks funds of the requester of an open Transaction LOC for a beneficiary.
+		#[pallet::weight(T::DbWeight::get().reads_writes(5, 6) + 100_000_000)]
+		#[transactional]
+		pub fn create(
+			origin: OriginFor<T>,
+			loc_id: T::LocId,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			asset: EscrowAsset<T::AssetId>,
+			amount: T::Balance,
+			timeout: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			let loc = <LocMap<T>>::get(&loc_id).ok_or(Error::<T>::LocNotFound)?;
+			ensure!(loc.loc_type == LocType::Transaction, Error::<T>::NotTransactionLoc);
+			ensure!(!loc.closed && loc.void_info.is_none(), Error::<T>::LocNotOpen);
+			ensure!(loc.requester == Requester::Account(who.clone()), Error::<T>::NotRequester);
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+			ensure!(!timeout.is_zero() && timeout <= T::MaxTimeout::get(), Error::<T>::InvalidTimeout);
+			ensure!(Self::may_receive(&asset, &beneficiary), Error::<T>::BeneficiaryNotAllowed);
+			ensure!(
+				asset != EscrowAsset::Native || amount >= T::NativeCurrency::minimum_balance(),
+				Error::<T>::AmountBelowMinimum
+			);
+
+			let id = <NextEscrowId<T>>::get();
+			let account = Self::escrow_account(id);
+			if let EscrowAsset::Asset(_) = asset {
+				frame_system::Module::<T>::inc_providers(&account);
+			}
+			Self::transfer(&asset, &who, &account, amount, KeepAlive)?;
+			// Assets may sweep the depositor's dust into the escrow
+			let amount = Self::balance(&asset, &account);
+			let deadline = frame_system::Module::<T>::block_number().saturating_add(timeout);
+			<Escrows<T>>::insert(id, EscrowOf::<T> {
+				loc_id: loc_id.clone(),
+				depositor: who.clone(),
+				beneficiary: beneficiary.clone(),
+				asset,
+				remaining: amount,
+				deadline,
+			});
+			<LocEscrows<T>>::insert(&loc_id, id, ());
+			<NextEscrowId<T>>::put(id + 1);
+			Self::deposit_event(Event::EscrowCreated(id, loc_id, who, beneficiary, amount, deadline));
+			Ok(().into())
+		}
+
+		/// Pays part of the remaining funds to the beneficiary, the caller being the depositor or
+		/// the owner of the LOC.
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 5) + 100_000_000)]
+		pub fn release(
+			origin: OriginFor<T>,
+			escrow_id: EscrowId,
+			amount: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let escrow = <Escrows<T>>::get(escrow_id).ok_or(Error::<T>::EscrowNotFound)?;
+			let owner = <LocMap<T>>::get(&escrow.loc_id).map(|loc| loc.owner);
+			ensure!(who == escrow.depositor || Some(&who) == owner.as_ref(), Error::<T>::NotAllowed);
+			ensure!(!amount.is_zero() && amount <= escrow.remaining, Error::<T>::InvalidAmount);
+
+			Self::pay_beneficiary(escrow_id, escrow, amount)?;
+			Ok(().into())
+		}
+
+		/// Pays remaining funds to the beneficiary if the LOC is closed, refunds them to the
+		/// depositor if the LOC is void or the deadline is passed. Funds the beneficiary is not
+		/// allowed to receive anymore are refunded once the deadline is passed.
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 5) + 100_000_000)]
+		pub fn settle(
+			origin: OriginFor<T>,
+			escrow_id: EscrowId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let escrow = <Escrows<T>>::get(escrow_id).ok_or(Error::<T>::EscrowNotFound)?;
+			let (closed, void) = <LocMap<T>>::get(&escrow.loc_id)
+				.map(|loc| (loc.closed, loc.void_info.is_some()))
+				.unwrap_or((false, true));
+
+			if void {
+				Self::refund_depositor(escrow_id, escrow)?;
+			} else if closed && Self::may_receive(&escrow.asset, &escrow.beneficiary) {
+				let remaining = escrow.remaining;
+				Self::pay_beneficiary(escrow_id, escrow, remaining)?;
+			} else if frame_system::Module::<T>::block_number() > escrow.deadline {
+				Self::refund_depositor(escrow_id, escrow)?;
+			} else if closed {
+				Err(Error::<T>::BeneficiaryNotAllowed)?
+			} else {
+				Err(Error::<T>::NotSettleable)?
+			}
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account holding the funds of an escrow.
+		pub fn escrow_account(escrow_id: EscrowId) -> T::AccountId {
+			T::EscrowModuleId::get().into_sub_account(escrow_id)
+		}
+
+		fn may_receive(asset: &EscrowAsset<T::AssetId>, account: &T::AccountId) -> bool {
+			match asset {
+				EscrowAsset::Native => true,
+				EscrowAsset::Asset(id) => T::AssetRecipients::may_receive(id, account),
+			}
+		}
+
+		fn pay_beneficiary(escrow_id: EscrowId, mut escrow: EscrowOf<T>, amount: T::Balance) -> DispatchResult {
+			ensure!(Self::may_receive(&escrow.asset, &escrow.beneficiary), Error::<T>::BeneficiaryNotAllowed);
+			let account = Self::escrow_account(escrow_id);
+			let amount = match escrow.asset {
+				EscrowAsset::Native if escrow.remaining.saturating_sub(amount) < T::NativeCurrency::minimum_balance() =>
+					escrow.remaining,
+				_ => amount,
+			};
+			Self::transfer(&escrow.asset, &account, &escrow.beneficiary, amount, AllowDeath)?;
+			// Asset transfers pay all remaining funds instead of leaving dust
+			let remaining = Self::balance(&escrow.asset, &account);
+			let amount = escrow.remaining.saturating_sub(remaining);
+			escrow.remaining = remaining;
+			Self::deposit_event(Event::EscrowReleased(escrow_id, escrow.beneficiary.clone(), amount, escrow.remaining));
+			if escrow.remaining.is_zero() {
+				Self::remove(escrow_id, &escrow);
+			} else {
+				<Escrows<T>>::insert(escrow_id, escrow);
+			}
+			Ok(())
+		}
+
+		fn refund_depositor(escrow_id: EscrowId, escrow: EscrowOf<T>) -> DispatchResult {
+			Self::transfer(&escrow.asset, &Self::escrow_account(escrow_id), &escrow.depositor, escrow.remaining, AllowDeath)?;
+			Self::deposit_event(Event::EscrowRefunded(escrow_id, escrow.depositor.clone(), escrow.remaining));
+			Self::remove(escrow_id, &escrow);
+			Ok(())
+		}
+
+		fn remove(escrow_id: EscrowId, escrow: &EscrowOf<T>) {
+			<Escrows<T>>::remove(escrow_id);
+			<LocEscrows<T>>::remove(&escrow.loc_id, escrow_id);
+			if let EscrowAsset::Asset(_) = escrow.asset {
+				let account = Self::escrow_account(escrow_id);
+				if let Err(error) = frame_system::Module::<T>::dec_providers(&account) {
+					frame_support::debug::error!("Unable to release escrow account {:?}: {:?}", account, error);
+				}
+			}
+		}
+
+		fn balance(asset: &EscrowAsset<T::AssetId>, who: &T::AccountId) -> T::Balance {
+			match asset {
+				EscrowAsset::Native => T::NativeCurrency::free_balance(who),
+				EscrowAsset::Asset(id) => pallet_assets::Module::<T>::balance(id.clone(), who.clone()),
+			}
+		}
+
+		fn transfer(
+			asset: &EscrowAsset<T::AssetId>,
+			from: &T::AccountId,
+			to: &T::AccountId,
+			amount: T::Balance,
+			existence: ExistenceRequirement,
+		) -> DispatchResult {
+			match asset {
+				EscrowAsset::Native => T::NativeCurrency::transfer(from, to, amount, existence),
+				EscrowAsset::Asset(id) => pallet_assets::Module::<T>::transfer(
+					RawOrigin::Signed(from.clone()).into(),
+					id.clone(),
+					T::Lookup::unlookup(to.clone()),
+					amount,
+				),
+			}
+		}
+	}
+}
@@ -0,0 +1,179 @@
+use crate as pallet_loc_escrow;
+use sp_core::H256;
+use frame_support::{parameter_types, traits::EnsureOrigin};
+use frame_system::EnsureRoot;
+use pallet_regulated_assets::AssetRecipients;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header, BuildStorage, ModuleId,
+};
+use frame_system as system;
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		Assets: pallet_assets::{Module, Call, Storage, Event<T>},
+		LogionLoc: pallet_logion_loc::{Module, Call, Storage, Event<T>},
+		LocEscrow: pallet_loc_escrow::{Module, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 10;
+	pub const MaxLocks: u32 = 50;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = MaxLocks;
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const AssetDepositBase: u64 = 0;
+	pub const AssetDepositPerZombie: u64 = 0;
+	pub const StringLimit: u32 = 50;
+	pub const MetadataDepositBase: u64 = 0;
+	pub const MetadataDepositPerByte: u64 = 0;
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = u64;
+	type AssetId = u64;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDepositBase = AssetDepositBase;
+	type AssetDepositPerZombie = AssetDepositPerZombie;
+	type StringLimit = StringLimit;
+	type MetadataDepositBase = MetadataDepositBase;
+	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type WeightInfo = ();
+}
+
+pub const LEGAL_OFFICER: u64 = 1;
+pub const REQUESTER: u64 = 2;
+pub const BENEFICIARY: u64 = 3;
+
+pub struct EnsureLegalOfficer;
+impl EnsureOrigin<Origin> for EnsureLegalOfficer {
+	type Success = u64;
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		let origin: Result<system::RawOrigin<u64>, Origin> = o.into();
+		match origin {
+			Ok(system::RawOrigin::Signed(who)) if who == LEGAL_OFFICER => Ok(who),
+			Ok(other) => Err(Origin::from(other)),
+			Err(origin) => Err(origin),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::signed(LEGAL_OFFICER)
+	}
+}
+
+parameter_types! {
+	pub const MaxMetadataItemNameSize: usize = 255;
+	pub const MaxMetadataItemValueSize: usize = 255;
+	pub const MaxFileNatureSize: usize = 255;
+	pub const MaxLinkNatureSize: usize = 255;
+	pub const MaxCollectionItemDescriptionSize: usize = 255;
+}
+
+impl pallet_logion_loc::Config for Test {
+	type LocId = u32;
+	type Event = Event;
+	type Hash = H256;
+	type CreateOrigin = EnsureLegalOfficer;
+	type MaxMetadataItemNameSize = MaxMetadataItemNameSize;
+	type MaxMetadataItemValueSize = MaxMetadataItemValueSize;
+	type MaxFileNatureSize = MaxFileNatureSize;
+	type MaxLinkNatureSize = MaxLinkNatureSize;
+	type CollectionItemId = H256;
+	type MaxCollectionItemDescriptionSize = MaxCollectionItemDescriptionSize;
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static BLOCKED_RECIPIENT: RefCell<Option<u64>> = RefCell::new(None);
+}
+
+/// Forbids given account to receive any asset.
+pub fn block_recipient(account: u64) {
+	BLOCKED_RECIPIENT.with(|blocked| *blocked.borrow_mut() = Some(account));
+}
+
+pub struct MockAssetRecipients;
+impl AssetRecipients<u64, u64> for MockAssetRecipients {
+	fn may_receive(_asset: &u64, account: &u64) -> bool {
+		BLOCKED_RECIPIENT.with(|blocked| *blocked.borrow() != Some(*account))
+	}
+}
+
+parameter_types! {
+	pub const EscrowModuleId: ModuleId = ModuleId(*b"lg/escrw");
+	pub const MaxTimeout: u64 = 100;
+}
+
+impl pallet_loc_escrow::Config for Test {
+	type NativeCurrency = Balances;
+	type AssetRecipients = MockAssetRecipients;
+	type EscrowModuleId = EscrowModuleId;
+	type MaxTimeout = MaxTimeout;
+	type Event = Event;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = GenesisConfig {
+		frame_system: Some(Default::default()),
+		pallet_balances: Some(pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(LEGAL_OFFICER, 1_000), (REQUESTER, 1_000)],
+		}),
+	}.build_storage().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,237 @@
+use crate::{mock::*, Error, EscrowAsset, Event as LocEscrowEvent, LocEscrows};
+use frame_support::{assert_err, assert_ok};
+use frame_system::RawOrigin;
+
+const LOC_ID: u32 = 1;
+const ASSET_ID: u64 = 1;
+const AMOUNT: u64 = 100;
+const TIMEOUT: u64 = 10;
+const MIN_BALANCE: u64 = 10;
+
+fn transaction_loc() {
+	assert_ok!(LogionLoc::create_polkadot_transaction_loc(Origin::signed(LEGAL_OFFICER), LOC_ID, REQUESTER));
+}
+
+fn native_escrow() {
+	transaction_loc();
+	assert_ok!(LocEscrow::create(Origin::signed(REQUESTER), LOC_ID, BENEFICIARY, EscrowAsset::Native, AMOUNT, TIMEOUT));
+}
+
+fn asset_escrow() {
+	assert_ok!(Assets::force_create(RawOrigin::Root.into(), ASSET_ID, LEGAL_OFFICER, 10, MIN_BALANCE));
+	assert_ok!(Assets::mint(Origin::signed(LEGAL_OFFICER), ASSET_ID, REQUESTER, 1_000));
+	assert_ok!(LogionLoc::create_polkadot_transaction_loc(Origin::signed(LEGAL_OFFICER), LOC_ID, REQUESTER));
+	assert_ok!(LocEscrow::create(Origin::signed(REQUESTER), LOC_ID, BENEFICIARY, EscrowAsset::Asset(ASSET_ID), AMOUNT, TIMEOUT));
+}
+
+#[test]
+fn it_rejects_beneficiary_not_allowed_on_create() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RawOrigin::Root.into(), ASSET_ID, LEGAL_OFFICER, 10, MIN_BALANCE));
+		assert_ok!(Assets::mint(Origin::signed(LEGAL_OFFICER), ASSET_ID, REQUESTER, 1_000));
+		assert_ok!(LogionLoc::create_polkadot_transaction_loc(Origin::signed(LEGAL_OFFICER), LOC_ID, REQUESTER));
+		block_recipient(BENEFICIARY);
+
+		assert_err!(
+			LocEscrow::create(Origin::signed(REQUESTER), LOC_ID, BENEFICIARY, EscrowAsset::Asset(ASSET_ID), AMOUNT, TIMEOUT),
+			Error::<Test>::BeneficiaryNotAllowed
+		);
+	});
+}
+
+#[test]
+fn it_rejects_release_to_beneficiary_not_allowed_anymore() {
+	new_test_ext().execute_with(|| {
+		asset_escrow();
+		block_recipient(BENEFICIARY);
+
+		assert_err!(LocEscrow::release(Origin::signed(REQUESTER), 0, 10), Error::<Test>::BeneficiaryNotAllowed);
+		assert_eq!(Assets::balance(ASSET_ID, BENEFICIARY), 0);
+		assert_eq!(LocEscrow::escrow(0).unwrap().remaining, AMOUNT);
+	});
+}
+
+#[test]
+fn it_does_not_settle_closed_loc_to_beneficiary_not_allowed_anymore() {
+	new_test_ext().execute_with(|| {
+		asset_escrow();
+		assert_ok!(LogionLoc::close(Origin::signed(LEGAL_OFFICER), LOC_ID));
+		block_recipient(BENEFICIARY);
+
+		assert_err!(LocEscrow::settle(Origin::signed(BENEFICIARY), 0), Error::<Test>::BeneficiaryNotAllowed);
+		assert_eq!(Assets::balance(ASSET_ID, BENEFICIARY), 0);
+	});
+}
+
+#[test]
+fn it_refunds_after_deadline_when_beneficiary_not_allowed_anymore() {
+	new_test_ext().execute_with(|| {
+		asset_escrow();
+		assert_ok!(LogionLoc::close(Origin::signed(LEGAL_OFFICER), LOC_ID));
+		block_recipient(BENEFICIARY);
+		System::set_block_number(1 + TIMEOUT + 1);
+
+		assert_ok!(LocEscrow::settle(Origin::signed(BENEFICIARY), 0));
+		assert_eq!(Assets::balance(ASSET_ID, BENEFICIARY), 0);
+		assert_eq!(Assets::balance(ASSET_ID, REQUESTER), 1_000);
+		assert!(LocEscrow::escrow(0).is_none());
+		assert_eq!(System::events().pop().unwrap().event, Event::pallet_loc_escrow(LocEscrowEvent::EscrowRefunded(0, REQUESTER, AMOUNT)));
+	});
+}
+
+#[test]
+fn it_holds_funds_in_escrow_account() {
+	new_test_ext().execute_with(|| {
+		native_escrow();
+		assert_ok!(LocEscrow::create(Origin::signed(REQUESTER), LOC_ID, BENEFICIARY, EscrowAsset::Native, 2 * AMOUNT, TIMEOUT));
+
+		assert_eq!(Balances::free_balance(LocEscrow::escrow_account(0)), AMOUNT);
+		assert_eq!(Balances::free_balance(LocEscrow::escrow_account(1)), 2 * AMOUNT);
+		assert_eq!(Balances::free_balance(REQUESTER), 1_000 - 3 * AMOUNT);
+		assert!(LocEscrows::<Test>::contains_key(LOC_ID, 1));
+		assert_eq!(System::events().pop().unwrap().event,
+			Event::pallet_loc_escrow(LocEscrowEvent::EscrowCreated(1, LOC_ID, REQUESTER, BENEFICIARY, 2 * AMOUNT, 1 + TIMEOUT)));
+	});
+}
+
+#[test]
+fn it_rejects_native_amount_below_existential_deposit() {
+	new_test_ext().execute_with(|| {
+		transaction_loc();
+
+		assert_err!(
+			LocEscrow::create(Origin::signed(REQUESTER), LOC_ID, BENEFICIARY, EscrowAsset::Native, ExistentialDeposit::get() - 1, TIMEOUT),
+			Error::<Test>::AmountBelowMinimum
+		);
+		assert_ok!(LocEscrow::create(Origin::signed(REQUESTER), LOC_ID, BENEFICIARY, EscrowAsset::Native, ExistentialDeposit::get(), TIMEOUT));
+	});
+}
+
+#[test]
+fn it_releases_part_of_funds() {
+	new_test_ext().execute_with(|| {
+		native_escrow();
+
+		assert_ok!(LocEscrow::release(Origin::signed(LEGAL_OFFICER), 0, 40));
+		assert_eq!(Balances::free_balance(BENEFICIARY), 40);
+		assert_eq!(LocEscrow::escrow(0).unwrap().remaining, AMOUNT - 40);
+		assert_eq!(System::events().pop().unwrap().event,
+			Event::pallet_loc_escrow(LocEscrowEvent::EscrowReleased(0, BENEFICIARY, 40, AMOUNT - 40)));
+	});
+}
+
+#[test]
+fn it_rejects_release_by_others() {
+	new_test_ext().execute_with(|| {
+		native_escrow();
+
+		assert_err!(LocEscrow::release(Origin::signed(BENEFICIARY), 0, 40), Error::<Test>::NotAllowed);
+	});
+}
+
+#[test]
+fn it_releases_native_dust_with_last_payment() {
+	new_test_ext().execute_with(|| {
+		native_escrow();
+
+		assert_ok!(LocEscrow::release(Origin::signed(REQUESTER), 0, AMOUNT - ExistentialDeposit::get() + 1));
+		assert_eq!(Balances::free_balance(BENEFICIARY), AMOUNT);
+		assert_eq!(Balances::free_balance(LocEscrow::escrow_account(0)), 0);
+		assert!(LocEscrow::escrow(0).is_none());
+		assert_eq!(System::events().pop().unwrap().event,
+			Event::pallet_loc_escrow(LocEscrowEvent::EscrowReleased(0, BENEFICIARY, AMOUNT, 0)));
+	});
+}
+
+#[test]
+fn it_releases_asset_dust_with_last_payment() {
+	new_test_ext().execute_with(|| {
+		asset_escrow();
+
+		assert_ok!(LocEscrow::release(Origin::signed(REQUESTER), 0, AMOUNT - MIN_BALANCE + 1));
+		assert_eq!(Assets::balance(ASSET_ID, BENEFICIARY), AMOUNT);
+		assert_eq!(Assets::balance(ASSET_ID, LocEscrow::escrow_account(0)), 0);
+		assert!(LocEscrow::escrow(0).is_none());
+		assert_eq!(System::events().pop().unwrap().event,
+			Event::pallet_loc_escrow(LocEscrowEvent::EscrowReleased(0, BENEFICIARY, AMOUNT, 0)));
+	});
+}
+
+#[test]
+fn it_pays_beneficiary_on_settle_after_close() {
+	new_test_ext().execute_with(|| {
+		native_escrow();
+		assert_ok!(LocEscrow::release(Origin::signed(REQUESTER), 0, 40));
+		assert_ok!(LogionLoc::close(Origin::signed(LEGAL_OFFICER), LOC_ID));
+
+		assert_ok!(LocEscrow::settle(Origin::signed(BENEFICIARY), 0));
+		assert_eq!(Balances::free_balance(BENEFICIARY), AMOUNT);
+		assert!(LocEscrow::escrow(0).is_none());
+		assert!(!LocEscrows::<Test>::contains_key(LOC_ID, 0));
+	});
+}
+
+#[test]
+fn it_refunds_on_settle_after_void() {
+	new_test_ext().execute_with(|| {
+		native_escrow();
+		assert_ok!(LogionLoc::make_void(Origin::signed(LEGAL_OFFICER), LOC_ID));
+
+		assert_ok!(LocEscrow::settle(Origin::signed(BENEFICIARY), 0));
+		assert_eq!(Balances::free_balance(BENEFICIARY), 0);
+		assert_eq!(Balances::free_balance(REQUESTER), 1_000);
+		assert!(LocEscrow::escrow(0).is_none());
+		assert_eq!(System::events().pop().unwrap().event,
+			Event::pallet_loc_escrow(LocEscrowEvent::EscrowRefunded(0, REQUESTER, AMOUNT)));
+	});
+}
+
+#[test]
+fn it_settles_open_loc_only_after_deadline() {
+	new_test_ext().execute_with(|| {
+		native_escrow();
+
+		System::set_block_number(1 + TIMEOUT);
+		assert_err!(LocEscrow::settle(Origin::signed(BENEFICIARY), 0), Error::<Test>::NotSettleable);
+
+		System::set_block_number(1 + TIMEOUT + 1);
+		assert_ok!(LocEscrow::settle(Origin::signed(BENEFICIARY), 0));
+		assert_eq!(Balances::free_balance(REQUESTER), 1_000);
+		assert!(LocEscrow::escrow(0).is_none());
+	});
+}
+
+fn account_based_asset_escrow() {
+	assert_ok!(Assets::force_create(RawOrigin::Root.into(), ASSET_ID, LEGAL_OFFICER, 0, MIN_BALANCE));
+	assert_ok!(Assets::mint(Origin::signed(LEGAL_OFFICER), ASSET_ID, REQUESTER, 1_000));
+	assert_ok!(LogionLoc::create_polkadot_transaction_loc(Origin::signed(LEGAL_OFFICER), LOC_ID, REQUESTER));
+	assert_ok!(LocEscrow::create(Origin::signed(REQUESTER), LOC_ID, BENEFICIARY, EscrowAsset::Asset(ASSET_ID), AMOUNT, TIMEOUT));
+}
+
+#[test]
+fn it_provides_escrow_account_of_account_based_asset() {
+	new_test_ext().execute_with(|| {
+		account_based_asset_escrow();
+		let account = LocEscrow::escrow_account(0);
+		assert_eq!(Assets::balance(ASSET_ID, account), AMOUNT);
+
+		assert_ok!(LogionLoc::make_void(Origin::signed(LEGAL_OFFICER), LOC_ID));
+		assert_ok!(LocEscrow::settle(Origin::signed(BENEFICIARY), 0));
+		assert_eq!(Assets::balance(ASSET_ID, REQUESTER), 1_000);
+		assert!(!System::account_exists(&account));
+	});
+}
+
+#[test]
+fn it_pays_account_based_asset_to_existing_beneficiary_only() {
+	new_test_ext().execute_with(|| {
+		account_based_asset_escrow();
+		assert_ok!(LogionLoc::close(Origin::signed(LEGAL_OFFICER), LOC_ID));
+		assert_err!(LocEscrow::settle(Origin::signed(BENEFICIARY), 0), pallet_assets::Error::<Test>::TooManyZombies);
+
+		assert_ok!(Balances::transfer(Origin::signed(REQUESTER), BENEFICIARY, 100));
+		assert_ok!(LocEscrow::settle(Origin::signed(BENEFICIARY), 0));
+		assert_eq!(Assets::balance(ASSET_ID, BENEFICIARY), AMOUNT);
+		assert!(!System::account_exists(&LocEscrow::escrow_account(0)));
+	});
+}
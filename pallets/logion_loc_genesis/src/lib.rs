@@ -0,0 +1,399 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Import of existing LOCs at genesis.
+//!
+//! `LogionLoc` has no genesis configuration. This pallet carries LOCs exported from another chain
+//! (see `logion-node export-locs`) and replays them through `LogionLoc` calls when building genesis
+//! so that ids, owners, requesters and submitters are preserved and all indexes are maintained by
+//! `LogionLoc` itself. Owners of imported LOCs must be legal officers at genesis. Genesis fails
+//! with the list of LOCs which could not be imported, along with the failed step.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// The requester of an exported LOC.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum GenesisRequester<AccountId, LocId> {
+	None,
+	Account(AccountId),
+	Loc(LocId),
+}
+
+/// The type of an exported LOC.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum GenesisLocType {
+	Transaction,
+	Identity,
+	Collection,
+}
+
+/// A metadata item of an exported LOC.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GenesisMetadataItem<AccountId> {
+	pub name: Vec<u8>,
+	pub value: Vec<u8>,
+	pub submitter: AccountId,
+}
+
+/// A file of an exported LOC.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GenesisFile<Hash, AccountId> {
+	pub hash: Hash,
+	pub nature: Vec<u8>,
+	pub submitter: AccountId,
+}
+
+/// A link of an exported LOC.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GenesisLink<LocId> {
+	pub id: LocId,
+	pub nature: Vec<u8>,
+}
+
+/// Void information of an exported LOC.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GenesisVoidInfo<LocId> {
+	pub replacer: Option<LocId>,
+}
+
+/// An exported LOC, its encoding is the one of `LogionLoc`'s `LegalOfficerCase`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct GenesisLoc<AccountId, LocId, Hash, BlockNumber> {
+	pub owner: AccountId,
+	pub requester: GenesisRequester<AccountId, LocId>,
+	pub metadata: Vec<GenesisMetadataItem<AccountId>>,
+	pub files: Vec<GenesisFile<Hash, AccountId>>,
+	pub closed: bool,
+	pub loc_type: GenesisLocType,
+	pub links: Vec<GenesisLink<LocId>>,
+	pub void_info: Option<GenesisVoidInfo<LocId>>,
+	pub replacer_of: Option<LocId>,
+	pub collection_last_block_submission: Option<BlockNumber>,
+	pub collection_max_size: Option<u32>,
+}
+
+pub type GenesisLocOf<T> = GenesisLoc<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::GenesisLocId,
+	<T as Config>::GenesisHash,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::{DispatchResult, DispatchResultWithPostInfo},
+		pallet_prelude::*,
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use sp_runtime::{traits::MaybeSerializeDeserialize, DispatchError};
+	use sp_std::{collections::btree_set::BTreeSet, prelude::*};
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_logion_loc::Config {
+		/// The serializable representation of LOC ids
+		type GenesisLocId: Parameter + MaybeSerializeDeserialize + Into<<Self as pallet_logion_loc::Config>::LocId>;
+
+		/// The serializable representation of file hashes and collection item ids
+		type GenesisHash: Parameter + MaybeSerializeDeserialize
+			+ Into<<Self as pallet_logion_loc::Config>::Hash>
+			+ Into<<Self as pallet_logion_loc::Config>::CollectionItemId>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::genesis_config]
+	#[derive(Clone)]
+	pub struct GenesisConfig<T: Config> {
+		/// LOCs to import with their original id
+		pub locs: Vec<(T::GenesisLocId, GenesisLocOf<T>)>,
+		/// Collection items to import: collection LOC id, item id and description
+		pub collection_items: Vec<(T::GenesisLocId, T::GenesisHash, Vec<u8>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				locs: Vec::new(),
+				collection_items: Vec::new(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let failures = Import::<T>::new(self).run();
+			if !failures.is_empty() {
+				panic!("Unable to import LOCs (LOC id, step, error or none if blocked by another LOC): {:?}", failures);
+			}
+		}
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The requester of the LOC is not supported by its type
+		UnsupportedRequester,
+		/// Collection items are imported in a LOC which is not an imported collection LOC
+		NotCollection,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {}
+
+	/// A step of the import of a LOC.
+	#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+	pub enum ImportStep {
+		Create,
+		AddItems,
+		Close,
+		AddCollectionItems,
+		Void,
+	}
+
+	/// Replays exported LOCs in an order valid on the exporting chain: a LOC is closed before being
+	/// voided, a replacer is created before and closed after the LOC it replaces is voided, a LOC
+	/// requested by another LOC is created after its requester is closed and before it is voided,
+	/// and links are added once the linked LOCs are created.
+	pub(crate) struct Import<'a, T: Config> {
+		config: &'a GenesisConfig<T>,
+		done: BTreeSet<(usize, ImportStep)>,
+		failures: Vec<(T::GenesisLocId, ImportStep, Option<DispatchError>)>,
+	}
+
+	impl<'a, T: Config> Import<'a, T> {
+		pub(crate) fn new(config: &'a GenesisConfig<T>) -> Self {
+			Import {
+				config,
+				done: BTreeSet::new(),
+				failures: Vec::new(),
+			}
+		}
+
+		/// Runs all steps and returns the ones which failed or could not run.
+		pub(crate) fn run(mut self) -> Vec<(T::GenesisLocId, ImportStep, Option<DispatchError>)> {
+			let mut pending: Vec<(usize, ImportStep)> = Vec::new();
+			for (index, (_, loc)) in self.config.locs.iter().enumerate() {
+				pending.push((index, ImportStep::Create));
+				pending.push((index, ImportStep::AddItems));
+				pending.push((index, ImportStep::AddCollectionItems));
+				if loc.closed {
+					pending.push((index, ImportStep::Close));
+				}
+				if loc.void_info.is_some() {
+					pending.push((index, ImportStep::Void));
+				}
+			}
+			for (collection_id, _, _) in self.config.collection_items.iter() {
+				if self.index_of(collection_id).is_none() {
+					self.failures.push((collection_id.clone(), ImportStep::AddCollectionItems, Some(Error::<T>::NotCollection.into())));
+				}
+			}
+
+			loop {
+				let ready: Vec<(usize, ImportStep)> = pending.iter()
+					.filter(|(index, step)| self.is_ready(*index, *step))
+					.cloned()
+					.collect();
+				if ready.is_empty() {
+					break;
+				}
+				for (index, step) in ready {
+					pending.retain(|pending_step| *pending_step != (index, step));
+					match self.execute(index, step) {
+						Ok(()) => {
+							self.done.insert((index, step));
+						},
+						Err(error) => {
+							self.failures.push((self.config.locs[index].0.clone(), step, Some(error)));
+							pending.retain(|(pending_index, _)| *pending_index != index);
+						},
+					}
+				}
+			}
+
+			for (index, step) in pending {
+				self.failures.push((self.config.locs[index].0.clone(), step, None));
+			}
+			self.failures
+		}
+
+		fn index_of(&self, id: &T::GenesisLocId) -> Option<usize> {
+			self.config.locs.iter().position(|(loc_id, _)| loc_id == id)
+		}
+
+		/// Tells if given step of a LOC is done, steps which do not apply to the LOC being done.
+		fn is_done(&self, index: usize, step: ImportStep) -> bool {
+			let loc = &self.config.locs[index].1;
+			match step {
+				ImportStep::Close if !loc.closed => self.is_done(index, ImportStep::AddItems),
+				_ => self.done.contains(&(index, step)),
+			}
+		}
+
+		fn is_done_by_id(&self, id: &T::GenesisLocId, step: ImportStep) -> bool {
+			self.index_of(id).map(|index| self.is_done(index, step)).unwrap_or(false)
+		}
+
+		/// LOCs replaced by given LOC.
+		fn replaced_by(&self, id: &T::GenesisLocId) -> Vec<usize> {
+			self.config.locs.iter().enumerate()
+				.filter(|(_, (_, loc))| matches!(&loc.void_info, Some(GenesisVoidInfo { replacer: Some(replacer) }) if replacer == id))
+				.map(|(index, _)| index)
+				.collect()
+		}
+
+		/// LOCs requested by given LOC.
+		fn requested_by(&self, id: &T::GenesisLocId) -> Vec<usize> {
+			self.config.locs.iter().enumerate()
+				.filter(|(_, (_, loc))| matches!(&loc.requester, GenesisRequester::Loc(requester) if requester == id))
+				.map(|(index, _)| index)
+				.collect()
+		}
+
+		fn is_ready(&self, index: usize, step: ImportStep) -> bool {
+			let (id, loc) = &self.config.locs[index];
+			match step {
+				ImportStep::Create => match &loc.requester {
+					GenesisRequester::Loc(requester) => self.is_done_by_id(requester, ImportStep::Close),
+					_ => true,
+				},
+				ImportStep::AddItems => self.is_done(index, ImportStep::Create)
+					&& loc.links.iter().all(|link| self.is_done_by_id(&link.id, ImportStep::Create)),
+				ImportStep::Close => self.is_done(index, ImportStep::AddItems)
+					&& self.replaced_by(id).into_iter().all(|other| self.is_done(other, ImportStep::Void)),
+				ImportStep::AddCollectionItems => self.is_done(index, ImportStep::Close),
+				ImportStep::Void => self.is_done(index, ImportStep::AddCollectionItems)
+					&& self.replaced_by(id).into_iter().all(|other| self.is_done(other, ImportStep::Void))
+					&& self.requested_by(id).into_iter().all(|other| self.is_done(other, ImportStep::Create))
+					&& match &loc.void_info {
+						Some(GenesisVoidInfo { replacer: Some(replacer) }) => self.is_done_by_id(replacer, ImportStep::Create),
+						_ => true,
+					},
+			}
+		}
+
+		fn execute(&self, index: usize, step: ImportStep) -> DispatchResult {
+			let (id, loc) = &self.config.locs[index];
+			let result = match step {
+				ImportStep::Create => Pallet::<T>::create_loc(id, loc),
+				ImportStep::AddItems => Pallet::<T>::add_items(id, loc),
+				ImportStep::Close => pallet_logion_loc::Module::<T>::close(
+					RawOrigin::Signed(loc.owner.clone()).into(), id.clone().into()),
+				ImportStep::AddCollectionItems => self.config.collection_items.iter()
+					.filter(|(collection_id, _, _)| collection_id == id)
+					.try_for_each(|(_, item_id, description)| Pallet::<T>::add_collection_item(id, loc, item_id, description).map(|_| ()))
+					.map(|_| ().into()),
+				ImportStep::Void => {
+					let origin: OriginFor<T> = RawOrigin::Signed(loc.owner.clone()).into();
+					match &loc.void_info {
+						Some(GenesisVoidInfo { replacer: Some(replacer) }) =>
+							pallet_logion_loc::Module::<T>::make_void_and_replace(origin, id.clone().into(), replacer.clone().into()),
+						_ => pallet_logion_loc::Module::<T>::make_void(origin, id.clone().into()),
+					}
+				},
+			};
+			result.map(|_| ()).map_err(|error| error.error)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn create_loc(id: &T::GenesisLocId, loc: &GenesisLocOf<T>) -> DispatchResultWithPostInfo {
+			let origin: OriginFor<T> = RawOrigin::Signed(loc.owner.clone()).into();
+			let loc_id = id.clone().into();
+			match (loc.loc_type, &loc.requester) {
+				(GenesisLocType::Transaction, GenesisRequester::Account(requester)) =>
+					pallet_logion_loc::Module::<T>::create_polkadot_transaction_loc(origin, loc_id, requester.clone()),
+				(GenesisLocType::Transaction, GenesisRequester::Loc(requester)) =>
+					pallet_logion_loc::Module::<T>::create_logion_transaction_loc(origin, loc_id, requester.clone().into()),
+				(GenesisLocType::Identity, GenesisRequester::Account(requester)) =>
+					pallet_logion_loc::Module::<T>::create_polkadot_identity_loc(origin, loc_id, requester.clone()),
+				(GenesisLocType::Identity, GenesisRequester::None) =>
+					pallet_logion_loc::Module::<T>::create_logion_identity_loc(origin, loc_id),
+				(GenesisLocType::Collection, GenesisRequester::Account(requester)) =>
+					pallet_logion_loc::Module::<T>::create_collection_loc(origin, loc_id, requester.clone(),
+						loc.collection_last_block_submission.clone(), loc.collection_max_size),
+				_ => Err(Error::<T>::UnsupportedRequester.into()),
+			}
+		}
+
+		fn add_items(id: &T::GenesisLocId, loc: &GenesisLocOf<T>) -> DispatchResultWithPostInfo {
+			for item in loc.metadata.iter() {
+				pallet_logion_loc::Module::<T>::add_metadata(
+					RawOrigin::Signed(loc.owner.clone()).into(),
+					id.clone().into(),
+					pallet_logion_loc::MetadataItem {
+						name: item.name.clone(),
+						value: item.value.clone(),
+						submitter: item.submitter.clone(),
+					},
+				)?;
+			}
+			for file in loc.files.iter() {
+				pallet_logion_loc::Module::<T>::add_file(
+					RawOrigin::Signed(loc.owner.clone()).into(),
+					id.clone().into(),
+					pallet_logion_loc::File {
+						hash: file.hash.clone().into(),
+						nature: file.nature.clone(),
+						submitter: file.submitter.clone(),
+					},
+				)?;
+			}
+			for link in loc.links.iter() {
+				pallet_logion_loc::Module::<T>::add_link(
+					RawOrigin::Signed(loc.owner.clone()).into(),
+					id.clone().into(),
+					pallet_logion_loc::LocLink {
+						id: link.id.clone().into(),
+						nature: link.nature.clone(),
+					},
+				)?;
+			}
+			Ok(().into())
+		}
+
+		fn add_collection_item(
+			collection_id: &T::GenesisLocId,
+			collection: &GenesisLocOf<T>,
+			item_id: &T::GenesisHash,
+			description: &Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let requester = match (&collection.loc_type, &collection.requester) {
+				(GenesisLocType::Collection, GenesisRequester::Account(requester)) => requester,
+				_ => return Err(Error::<T>::NotCollection.into()),
+			};
+			pallet_logion_loc::Module::<T>::add_collection_item(
+				RawOrigin::Signed(requester.clone()).into(),
+				collection_id.clone().into(),
+				item_id.clone().into(),
+				description.clone(),
+			)
+		}
+	}
+}
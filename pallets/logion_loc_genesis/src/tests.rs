@@ -0,0 +1,173 @@
+use crate::{
+	mock::*, Error, GenesisConfig, GenesisFile, GenesisLink, GenesisLoc, GenesisLocOf, GenesisLocType,
+	GenesisMetadataItem, GenesisRequester, GenesisVoidInfo, Import, ImportStep,
+};
+use codec::Encode;
+use frame_support::traits::GenesisBuild;
+use pallet_logion_loc::{File, LegalOfficerCase, LegalOfficerCaseOf, LocLink, LocMap, LocType, LocVoidInfo, MetadataItem, Requester};
+use sp_core::H256;
+
+const LOC_ID: u32 = 1;
+const OTHER_LOC_ID: u32 = 2;
+const THIRD_LOC_ID: u32 = 3;
+const UNKNOWN_LOC_ID: u32 = 4;
+
+fn loc(requester: GenesisRequester<u64, u32>, loc_type: GenesisLocType) -> GenesisLocOf<Test> {
+	GenesisLoc {
+		owner: LEGAL_OFFICER,
+		requester,
+		metadata: Vec::new(),
+		files: Vec::new(),
+		closed: true,
+		loc_type,
+		links: Vec::new(),
+		void_info: None,
+		replacer_of: None,
+		collection_last_block_submission: None,
+		collection_max_size: None,
+	}
+}
+
+fn transaction_loc() -> GenesisLocOf<Test> {
+	loc(GenesisRequester::Account(REQUESTER), GenesisLocType::Transaction)
+}
+
+fn config(locs: Vec<(u32, GenesisLocOf<Test>)>) -> GenesisConfig<Test> {
+	GenesisConfig {
+		locs,
+		collection_items: Vec::new(),
+	}
+}
+
+fn import(config: GenesisConfig<Test>) -> Vec<(u32, ImportStep, Option<sp_runtime::DispatchError>)> {
+	Import::<Test>::new(&config).run()
+}
+
+#[test]
+fn it_encodes_like_legal_officer_case() {
+	let genesis_requesters = vec![GenesisRequester::None, GenesisRequester::Account(REQUESTER), GenesisRequester::Loc(OTHER_LOC_ID)];
+	let requesters = vec![Requester::None, Requester::Account(REQUESTER), Requester::Loc(OTHER_LOC_ID)];
+	let genesis_loc_types = vec![GenesisLocType::Transaction, GenesisLocType::Identity, GenesisLocType::Collection];
+	let loc_types = vec![LocType::Transaction, LocType::Identity, LocType::Collection];
+	for ((genesis_requester, requester), (genesis_loc_type, loc_type)) in genesis_requesters.into_iter().zip(requesters)
+		.zip(genesis_loc_types.into_iter().zip(loc_types)) {
+		let genesis_loc: GenesisLocOf<Test> = GenesisLoc {
+			owner: LEGAL_OFFICER,
+			requester: genesis_requester,
+			metadata: vec![GenesisMetadataItem { name: b"name".to_vec(), value: b"value".to_vec(), submitter: REQUESTER }],
+			files: vec![GenesisFile { hash: H256::repeat_byte(1), nature: b"nature".to_vec(), submitter: REQUESTER }],
+			closed: true,
+			loc_type: genesis_loc_type,
+			links: vec![GenesisLink { id: THIRD_LOC_ID, nature: b"nature".to_vec() }],
+			void_info: Some(GenesisVoidInfo { replacer: Some(THIRD_LOC_ID) }),
+			replacer_of: Some(UNKNOWN_LOC_ID),
+			collection_last_block_submission: Some(100),
+			collection_max_size: Some(10),
+		};
+		let loc: LegalOfficerCaseOf<Test> = LegalOfficerCase {
+			owner: LEGAL_OFFICER,
+			requester,
+			metadata: vec![MetadataItem { name: b"name".to_vec(), value: b"value".to_vec(), submitter: REQUESTER }],
+			files: vec![File { hash: H256::repeat_byte(1), nature: b"nature".to_vec(), submitter: REQUESTER }],
+			closed: true,
+			loc_type,
+			links: vec![LocLink { id: THIRD_LOC_ID, nature: b"nature".to_vec() }],
+			void_info: Some(LocVoidInfo { replacer: Some(THIRD_LOC_ID) }),
+			replacer_of: Some(UNKNOWN_LOC_ID),
+			collection_last_block_submission: Some(100),
+			collection_max_size: Some(10),
+		};
+		assert_eq!(genesis_loc.encode(), loc.encode());
+	}
+}
+
+#[test]
+fn it_imports_replacer_listed_before_replaced_loc() {
+	new_test_ext().execute_with(|| {
+		let mut replaced = transaction_loc();
+		replaced.void_info = Some(GenesisVoidInfo { replacer: Some(OTHER_LOC_ID) });
+		let mut replacer = transaction_loc();
+		replacer.replacer_of = Some(LOC_ID);
+
+		assert_eq!(import(config(vec![(OTHER_LOC_ID, replacer), (LOC_ID, replaced)])), Vec::new());
+		let replaced = LocMap::<Test>::get(LOC_ID).unwrap();
+		assert_eq!(replaced.void_info, Some(LocVoidInfo { replacer: Some(OTHER_LOC_ID) }));
+		let replacer = LocMap::<Test>::get(OTHER_LOC_ID).unwrap();
+		assert_eq!(replacer.replacer_of, Some(LOC_ID));
+		assert!(replacer.closed);
+	});
+}
+
+#[test]
+fn it_imports_links_to_locs_listed_later() {
+	new_test_ext().execute_with(|| {
+		let mut linking = transaction_loc();
+		linking.links = vec![GenesisLink { id: OTHER_LOC_ID, nature: b"nature".to_vec() }];
+
+		assert_eq!(import(config(vec![(LOC_ID, linking), (OTHER_LOC_ID, transaction_loc())])), Vec::new());
+		let linking = LocMap::<Test>::get(LOC_ID).unwrap();
+		assert_eq!(linking.links, vec![LocLink { id: OTHER_LOC_ID, nature: b"nature".to_vec() }]);
+		assert!(linking.closed);
+	});
+}
+
+#[test]
+fn it_imports_locs_requested_by_loc_listed_later() {
+	new_test_ext().execute_with(|| {
+		let mut requester = loc(GenesisRequester::None, GenesisLocType::Identity);
+		requester.void_info = Some(GenesisVoidInfo { replacer: None });
+		let requested = loc(GenesisRequester::Loc(LOC_ID), GenesisLocType::Transaction);
+
+		assert_eq!(import(config(vec![
+			(OTHER_LOC_ID, requested.clone()),
+			(THIRD_LOC_ID, requested),
+			(LOC_ID, requester),
+		])), Vec::new());
+		assert_eq!(LocMap::<Test>::get(OTHER_LOC_ID).unwrap().requester, Requester::Loc(LOC_ID));
+		assert_eq!(LocMap::<Test>::get(THIRD_LOC_ID).unwrap().requester, Requester::Loc(LOC_ID));
+		assert!(LocMap::<Test>::get(LOC_ID).unwrap().void_info.is_some());
+	});
+}
+
+#[test]
+fn it_reports_failed_and_blocked_locs() {
+	new_test_ext().execute_with(|| {
+		let mut not_owned_by_legal_officer = loc(GenesisRequester::None, GenesisLocType::Identity);
+		not_owned_by_legal_officer.owner = NOT_LEGAL_OFFICER;
+		let requested = loc(GenesisRequester::Loc(LOC_ID), GenesisLocType::Transaction);
+		let mut linking_unknown = transaction_loc();
+		linking_unknown.links = vec![GenesisLink { id: UNKNOWN_LOC_ID, nature: b"nature".to_vec() }];
+		let mut config = config(vec![
+			(LOC_ID, not_owned_by_legal_officer),
+			(OTHER_LOC_ID, requested),
+			(THIRD_LOC_ID, linking_unknown),
+		]);
+		config.collection_items = vec![(UNKNOWN_LOC_ID, H256::repeat_byte(1), b"description".to_vec())];
+
+		let failures = import(config);
+		assert!(failures.contains(&(UNKNOWN_LOC_ID, ImportStep::AddCollectionItems, Some(Error::<Test>::NotCollection.into()))));
+		assert!(failures.iter().any(|(id, step, error)| *id == LOC_ID && *step == ImportStep::Create && error.is_some()));
+		assert!(failures.contains(&(OTHER_LOC_ID, ImportStep::Create, None)));
+		assert!(failures.contains(&(THIRD_LOC_ID, ImportStep::AddItems, None)));
+		assert!(LocMap::<Test>::get(LOC_ID).is_none());
+		assert!(LocMap::<Test>::get(OTHER_LOC_ID).is_none());
+		assert!(!LocMap::<Test>::get(THIRD_LOC_ID).unwrap().closed);
+	});
+}
+
+#[test]
+fn it_rejects_unsupported_requester() {
+	new_test_ext().execute_with(|| {
+		let failures = import(config(vec![(LOC_ID, loc(GenesisRequester::None, GenesisLocType::Transaction))]));
+		assert!(failures.contains(&(LOC_ID, ImportStep::Create, Some(Error::<Test>::UnsupportedRequester.into()))));
+	});
+}
+
+#[test]
+#[should_panic(expected = "Unable to import LOCs")]
+fn it_fails_genesis_with_failed_locs() {
+	let mut not_owned_by_legal_officer = transaction_loc();
+	not_owned_by_legal_officer.owner = NOT_LEGAL_OFFICER;
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&config(vec![(LOC_ID, not_owned_by_legal_officer)]), &mut storage).unwrap();
+}
@@ -0,0 +1,38 @@
+//! Runtime API definition for the logion randomness pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::prelude::*;
+
+/// Where a random value comes from: the commit-reveal round which produced the seed,
+/// the block at which the round was closed and the validators whose secrets were mixed in.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RandomnessProvenance<BlockNumber, AccountId> {
+	pub round: u32,
+	pub closed_at: BlockNumber,
+	pub contributors: Vec<AccountId>,
+}
+
+/// A random value along with its provenance.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RandomOutput<Hash, BlockNumber, AccountId> {
+	pub random: Hash,
+	pub provenance: RandomnessProvenance<BlockNumber, AccountId>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait RandomnessApi<Hash, BlockNumber, AccountId> where
+		Hash: Codec,
+		BlockNumber: Codec,
+		AccountId: Codec,
+	{
+		/// Returns the random value for given subject and the provenance of the seed it was derived from,
+		/// none if no seed was produced yet.
+		fn random(subject: Vec<u8>) -> Option<RandomOutput<Hash, BlockNumber, AccountId>>;
+	}
+}
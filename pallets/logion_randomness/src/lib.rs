@@ -0,0 +1,211 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Commit-reveal randomness for a PoA validator set.
+//!
+//! Time is divided in rounds of `RoundLength` blocks. During a round, each validator submits the
+//! secret it committed to during the previous round and a commitment (i.e. the hash) of a new
+//! secret. At the end of a round, the revealed secrets are mixed into the previous seed which
+//! gives the new seed. Block authors are not able to predict the seed as long as at least one
+//! contributing validator is honest. A validator may still refuse to reveal its secret, this
+//! only removes its contribution from the round.
+//!
+//! There is no seed before the first round with reveals, and the seed is kept as is after rounds
+//! without reveals. Consumers should check the provenance of random values, `closed_at` being the
+//! block at which the last round with reveals was closed.
+//!
+//! Validators submit `commit_and_reveal` themselves, the node does not do it on their behalf.
+//! Until a seed is produced, `Randomness` is served by `FallbackRandomness`.
+
+pub use pallet::*;
+pub use pallet_logion_randomness_runtime_api::{RandomOutput, RandomnessProvenance};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		traits::Randomness,
+		weights::Pays,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Hash, Zero};
+	use sp_std::prelude::*;
+	use codec::Encode;
+	use super::{RandomOutput, RandomnessProvenance};
+
+	/// Tells if an account is currently a block validator.
+	pub trait IsValidator<AccountId> {
+		fn is_validator(account: &AccountId) -> bool;
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Validators allowed to contribute to randomness
+		type IsValidator: IsValidator<Self::AccountId>;
+
+		/// The number of blocks of a commit-reveal round
+		type RoundLength: Get<Self::BlockNumber>;
+
+		/// The source of randomness used while no seed was produced
+		type FallbackRandomness: Randomness<Self::Hash>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Last commitment of each validator along with the round it was submitted in
+	#[pallet::storage]
+	#[pallet::getter(fn commitment)]
+	pub type Commitments<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (u32, T::Hash)>;
+
+	/// Index of the current round
+	#[pallet::storage]
+	#[pallet::getter(fn current_round)]
+	pub type CurrentRound<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Secrets revealed during the current round, mixed, and their contributors
+	#[pallet::storage]
+	#[pallet::getter(fn pending_reveals)]
+	pub type PendingReveals<T: Config> = StorageValue<_, (T::Hash, Vec<T::AccountId>), OptionQuery>;
+
+	/// The current seed and its provenance, none until a round with reveals was closed
+	#[pallet::storage]
+	#[pallet::getter(fn seed)]
+	pub type Seed<T: Config> = StorageValue<_, RandomOutput<T::Hash, T::BlockNumber, T::AccountId>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", T::BlockNumber = "BlockNumber", T::Hash = "Hash")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when a validator commits to a secret. [validator, round]
+		Committed(T::AccountId, u32),
+		/// Issued when a validator reveals its secret. [validator, round]
+		Revealed(T::AccountId, u32),
+		/// Issued when a round is closed. [round, seed, contributors count]
+		RoundClosed(u32, T::Hash, u32),
+		/// Issued when a round is closed without any reveal, the seed is unchanged. [round]
+		RoundClosedWithoutReveal(u32),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Only validators are allowed to contribute
+		NotValidator,
+		/// Validator already committed during current round
+		AlreadyCommitted,
+		/// Revealed secret does not match commitment of previous round
+		InvalidReveal,
+		/// No commitment from previous round to reveal
+		NothingToReveal,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let round_length = T::RoundLength::get();
+			if !round_length.is_zero() && (now % round_length).is_zero() && !now.is_zero() {
+				Self::close_round(now);
+				T::DbWeight::get().reads_writes(3, 3)
+			} else {
+				T::DbWeight::get().reads(0)
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Reveals the secret committed during previous round, if any, and commits to a new secret.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 2))]
+		pub fn commit_and_reveal(
+			origin: OriginFor<T>,
+			secret: Option<Vec<u8>>,
+			next_commitment: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(T::IsValidator::is_validator(&who), Error::<T>::NotValidator);
+
+			let round = Self::current_round();
+			let previous_commitment = <Commitments<T>>::get(&who);
+			if let Some((committed_round, _)) = previous_commitment {
+				ensure!(committed_round != round, Error::<T>::AlreadyCommitted);
+			}
+
+			if let Some(secret) = secret {
+				match previous_commitment {
+					Some((committed_round, hash)) if committed_round.saturating_add(1) == round => {
+						ensure!(T::Hashing::hash(&secret) == hash, Error::<T>::InvalidReveal);
+						Self::mix(&who, &secret);
+						Self::deposit_event(Event::Revealed(who.clone(), round));
+					},
+					_ => Err(Error::<T>::NothingToReveal)?,
+				}
+			}
+
+			<Commitments<T>>::insert(&who, (round, next_commitment));
+			Self::deposit_event(Event::Committed(who, round));
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn mix(who: &T::AccountId, secret: &Vec<u8>) {
+			let (accumulator, mut contributors) = Self::pending_reveals()
+				.unwrap_or_else(|| (Default::default(), Vec::new()));
+			let accumulator = T::Hashing::hash(&(accumulator, secret).encode());
+			contributors.push(who.clone());
+			<PendingReveals<T>>::put((accumulator, contributors));
+		}
+
+		fn close_round(now: T::BlockNumber) {
+			let round = Self::current_round();
+			if let Some((accumulator, contributors)) = <PendingReveals<T>>::take() {
+				let previous = Self::seed().map(|seed| seed.random).unwrap_or_default();
+				let random = T::Hashing::hash(&(previous, accumulator, round).encode());
+				let contributors_count = contributors.len() as u32;
+				<Seed<T>>::put(RandomOutput {
+					random,
+					provenance: RandomnessProvenance {
+						round,
+						closed_at: now,
+						contributors,
+					},
+				});
+				Self::deposit_event(Event::RoundClosed(round, random, contributors_count));
+			} else {
+				Self::deposit_event(Event::RoundClosedWithoutReveal(round));
+			}
+			<CurrentRound<T>>::put(round.saturating_add(1));
+		}
+
+		/// Returns a random value for given subject and the provenance of the seed it was derived from,
+		/// none if no seed was produced yet.
+		pub fn random_with_provenance(subject: &[u8]) -> Option<RandomOutput<T::Hash, T::BlockNumber, T::AccountId>> {
+			Self::seed().map(|seed| RandomOutput {
+				random: T::Hashing::hash(&(seed.random, subject).encode()),
+				provenance: seed.provenance,
+			})
+		}
+	}
+
+	/// Before the first seed, values come from `FallbackRandomness`. Consumers which cannot accept
+	/// this must use `random_with_provenance`.
+	impl<T: Config> Randomness<T::Hash> for Pallet<T> {
+		fn random(subject: &[u8]) -> T::Hash {
+			match Self::random_with_provenance(subject) {
+				Some(output) => output.random,
+				None => T::FallbackRandomness::random(subject),
+			}
+		}
+	}
+}
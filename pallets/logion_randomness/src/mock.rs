@@ -0,0 +1,86 @@
+use crate as pallet_logion_randomness;
+use sp_core::H256;
+use frame_support::{parameter_types, traits::Randomness};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash, IdentityLookup}, testing::Header,
+};
+use frame_system as system;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		LogionRandomness: pallet_logion_randomness::{Module, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+}
+
+pub const VALIDATOR1: u64 = 1;
+pub const VALIDATOR2: u64 = 2;
+pub const NOT_VALIDATOR: u64 = 3;
+
+pub struct IsValidator;
+impl pallet_logion_randomness::IsValidator<u64> for IsValidator {
+	fn is_validator(account: &u64) -> bool {
+		*account == VALIDATOR1 || *account == VALIDATOR2
+	}
+}
+
+parameter_types! {
+	pub const RoundLength: u64 = 10;
+}
+
+pub struct FallbackRandomness;
+impl Randomness<H256> for FallbackRandomness {
+	fn random(subject: &[u8]) -> H256 {
+		BlakeTwo256::hash(&[b"fallback", subject].concat())
+	}
+}
+
+impl pallet_logion_randomness::Config for Test {
+	type IsValidator = IsValidator;
+	type RoundLength = RoundLength;
+	type FallbackRandomness = FallbackRandomness;
+	type Event = Event;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
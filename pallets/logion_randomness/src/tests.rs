@@ -0,0 +1,105 @@
+use crate::{mock::*, Error, Event as RandomnessEvent};
+use frame_support::{assert_err, assert_ok, traits::{Hooks, Randomness}};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+const SECRET1: &[u8] = b"secret1";
+const SECRET2: &[u8] = b"secret2";
+const SUBJECT: &[u8] = b"subject";
+
+fn commitment(secret: &[u8]) -> sp_core::H256 {
+	BlakeTwo256::hash(secret)
+}
+
+fn close_round(at: u64) {
+	System::set_block_number(at);
+	LogionRandomness::on_initialize(at);
+}
+
+fn commit_and_close(validator: u64, secret: &[u8]) {
+	assert_ok!(LogionRandomness::commit_and_reveal(Origin::signed(validator), None, commitment(secret)));
+	close_round(10);
+}
+
+#[test]
+fn it_has_no_seed_before_first_reveal() {
+	new_test_ext().execute_with(|| {
+		commit_and_close(VALIDATOR1, SECRET1);
+		assert_eq!(LogionRandomness::seed(), None);
+		assert_eq!(LogionRandomness::random_with_provenance(SUBJECT), None);
+		assert_eq!(LogionRandomness::random(SUBJECT), FallbackRandomness::random(SUBJECT));
+		assert!(System::events().iter().any(|record| record.event == Event::pallet_logion_randomness(RandomnessEvent::RoundClosedWithoutReveal(0))));
+	});
+}
+
+#[test]
+fn it_produces_seed_on_reveal() {
+	new_test_ext().execute_with(|| {
+		commit_and_close(VALIDATOR1, SECRET1);
+		assert_ok!(LogionRandomness::commit_and_reveal(Origin::signed(VALIDATOR1), Some(SECRET1.to_vec()), commitment(SECRET2)));
+		close_round(20);
+
+		let output = LogionRandomness::random_with_provenance(SUBJECT).unwrap();
+		assert_eq!(output.provenance.round, 1);
+		assert_eq!(output.provenance.closed_at, 20);
+		assert_eq!(output.provenance.contributors, vec![VALIDATOR1]);
+		assert_eq!(LogionRandomness::random(SUBJECT), output.random);
+	});
+}
+
+#[test]
+fn it_keeps_seed_and_provenance_without_reveal() {
+	new_test_ext().execute_with(|| {
+		commit_and_close(VALIDATOR1, SECRET1);
+		assert_ok!(LogionRandomness::commit_and_reveal(Origin::signed(VALIDATOR1), Some(SECRET1.to_vec()), commitment(SECRET2)));
+		close_round(20);
+		let output = LogionRandomness::random_with_provenance(SUBJECT).unwrap();
+
+		close_round(30);
+		assert_eq!(LogionRandomness::random_with_provenance(SUBJECT), Some(output));
+	});
+}
+
+#[test]
+fn it_mixes_previous_seed() {
+	new_test_ext().execute_with(|| {
+		commit_and_close(VALIDATOR1, SECRET1);
+		assert_ok!(LogionRandomness::commit_and_reveal(Origin::signed(VALIDATOR1), Some(SECRET1.to_vec()), commitment(SECRET1)));
+		close_round(20);
+		let first = LogionRandomness::seed().unwrap().random;
+
+		assert_ok!(LogionRandomness::commit_and_reveal(Origin::signed(VALIDATOR1), Some(SECRET1.to_vec()), commitment(SECRET1)));
+		close_round(30);
+		let second = LogionRandomness::seed().unwrap().random;
+		assert_ne!(first, second);
+	});
+}
+
+#[test]
+fn it_fails_revealing_wrong_secret() {
+	new_test_ext().execute_with(|| {
+		commit_and_close(VALIDATOR1, SECRET1);
+		assert_err!(LogionRandomness::commit_and_reveal(Origin::signed(VALIDATOR1), Some(SECRET2.to_vec()), commitment(SECRET2)), Error::<Test>::InvalidReveal);
+	});
+}
+
+#[test]
+fn it_fails_revealing_without_commitment() {
+	new_test_ext().execute_with(|| {
+		assert_err!(LogionRandomness::commit_and_reveal(Origin::signed(VALIDATOR2), Some(SECRET1.to_vec()), commitment(SECRET2)), Error::<Test>::NothingToReveal);
+	});
+}
+
+#[test]
+fn it_fails_committing_twice_in_round() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LogionRandomness::commit_and_reveal(Origin::signed(VALIDATOR1), None, commitment(SECRET1)));
+		assert_err!(LogionRandomness::commit_and_reveal(Origin::signed(VALIDATOR1), None, commitment(SECRET2)), Error::<Test>::AlreadyCommitted);
+	});
+}
+
+#[test]
+fn it_fails_committing_if_not_validator() {
+	new_test_ext().execute_with(|| {
+		assert_err!(LogionRandomness::commit_and_reveal(Origin::signed(NOT_VALIDATOR), None, commitment(SECRET1)), Error::<Test>::NotValidator);
+	});
+}
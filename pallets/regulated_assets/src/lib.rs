@@ -0,0 +1,223 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Regulated assets.
+//!
+//! A regulated asset is created by a legal officer on behalf of a client. The legal officer
+//! owns the asset and holds its admin and freezer roles, the client only gets the issuer role
+//! i.e. is allowed to mint. The legal officer is therefore able to freeze accounts, burn tokens
+//! or force transfers as required by regulation.
+//!
+//! The legal officer in charge is the current admin of the asset: handing the admin role over
+//! with `pallet_assets`' `set_team` hands over the identification rule as well.
+//!
+//! Regulated assets have no zombie slots: only existing accounts, i.e. accounts holding at least
+//! the existential deposit in the native currency, may hold them. Holding the asset keeps the
+//! account alive.
+//!
+//! The legal officer in charge may require recipients of a regulated asset to be identified, i.e.
+//! to have closed Identity LOCs with given legal officers. `pallet_assets` has no transfer hook,
+//! the runtime enforces the rule by filtering mints and transfers with `may_receive`. Pallets
+//! moving assets on their own (escrow payouts, fee payment) are not filtered and must check the
+//! rule through the `AssetRecipients` trait.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::Decode;
+
+/// Tells if an account is allowed to receive an asset.
+pub trait AssetRecipients<AssetId, AccountId> {
+	fn may_receive(asset: &AssetId, account: &AccountId) -> bool;
+}
+
+impl<AssetId, AccountId> AssetRecipients<AssetId, AccountId> for () {
+	fn may_receive(_asset: &AssetId, _account: &AccountId) -> bool {
+		true
+	}
+}
+
+/// The leading fields of `pallet_assets`' asset details, which are not public.
+#[derive(Decode)]
+struct AssetTeam<AccountId> {
+	_owner: AccountId,
+	_issuer: AccountId,
+	admin: AccountId,
+	_freezer: AccountId,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		storage::migration::get_storage_value,
+		traits::PalletInfo,
+		StorageHasher,
+	};
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use logion_shared::LocQuery;
+	use sp_runtime::traits::StaticLookup;
+	use sp_std::prelude::*;
+	use super::{AssetRecipients, AssetTeam};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_assets::Config {
+		/// The origin allowed to create regulated assets i.e. legal officers
+		type CreateOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+		/// Query for Identity LOCs of recipients
+		type LocQuery: LocQuery<Self::AccountId>;
+
+		/// The maximum number of legal officers an identification rule may require
+		type MaxRequiredLegalOfficers: Get<u32>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Regulated assets and the legal officer which created them
+	#[pallet::storage]
+	#[pallet::getter(fn legal_officer_of)]
+	pub type RegulatedAssets<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, T::AccountId>;
+
+	/// Legal officers with which recipients of a regulated asset must have closed Identity LOCs
+	#[pallet::storage]
+	#[pallet::getter(fn required_identity)]
+	pub type RequiredIdentity<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, Vec<T::AccountId>>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", T::AssetId = "AssetId")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when a regulated asset is created. [asset, legal officer, issuer]
+		RegulatedAssetCreated(T::AssetId, T::AccountId, T::AccountId),
+		/// Issued when recipients of an asset must be identified. [asset, legal officers]
+		IdentityRequired(T::AssetId, Vec<T::AccountId>),
+		/// Issued when recipients of an asset do not have to be identified anymore. [asset]
+		IdentityRequirementLifted(T::AssetId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The asset already exists
+		AlreadyExists,
+		/// The asset is not a regulated asset
+		NotRegulated,
+		/// The caller is not the current admin of the asset
+		NotLegalOfficerInCharge,
+		/// No legal officer or too many legal officers are required
+		InvalidLegalOfficers,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Creates a regulated asset, the caller being the legal officer in charge. The asset has
+		/// no zombie slots.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + 300_000_000)]
+		pub fn create_regulated(
+			origin: OriginFor<T>,
+			#[pallet::compact] id: T::AssetId,
+			issuer: <T::Lookup as StaticLookup>::Source,
+			min_balance: T::Balance,
+		) -> DispatchResultWithPostInfo {
+			let legal_officer = T::CreateOrigin::ensure_origin(origin)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			ensure!(!<RegulatedAssets<T>>::contains_key(&id), Error::<T>::AlreadyExists);
+
+			let legal_officer_source = T::Lookup::unlookup(legal_officer.clone());
+			pallet_assets::Module::<T>::create(
+				RawOrigin::Signed(legal_officer.clone()).into(),
+				id,
+				legal_officer_source.clone(),
+				0,
+				min_balance,
+			)?;
+			pallet_assets::Module::<T>::set_team(
+				RawOrigin::Signed(legal_officer.clone()).into(),
+				id,
+				T::Lookup::unlookup(issuer.clone()),
+				legal_officer_source.clone(),
+				legal_officer_source,
+			)?;
+
+			<RegulatedAssets<T>>::insert(&id, &legal_officer);
+			Self::deposit_event(Event::RegulatedAssetCreated(id, legal_officer, issuer));
+			Ok(().into())
+		}
+
+		/// Requires recipients of mints and transfers to have closed Identity LOCs with given
+		/// legal officers.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + 10_000_000)]
+		pub fn require_identity(
+			origin: OriginFor<T>,
+			#[pallet::compact] id: T::AssetId,
+			legal_officers: Vec<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_in_charge(origin, &id)?;
+			ensure!(
+				!legal_officers.is_empty() && legal_officers.len() <= T::MaxRequiredLegalOfficers::get() as usize,
+				Error::<T>::InvalidLegalOfficers
+			);
+
+			<RequiredIdentity<T>>::insert(&id, &legal_officers);
+			Self::deposit_event(Event::IdentityRequired(id, legal_officers));
+			Ok(().into())
+		}
+
+		/// Lets any account receive the asset.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + 10_000_000)]
+		pub fn lift_identity_requirement(
+			origin: OriginFor<T>,
+			#[pallet::compact] id: T::AssetId,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_in_charge(origin, &id)?;
+
+			<RequiredIdentity<T>>::remove(&id);
+			Self::deposit_event(Event::IdentityRequirementLifted(id));
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Tells if given account is allowed to receive given asset.
+		pub fn may_receive(id: &T::AssetId, account: &T::AccountId) -> bool {
+			match <RequiredIdentity<T>>::get(id) {
+				Some(legal_officers) => T::LocQuery::has_closed_identity_locs(account, &legal_officers),
+				None => true,
+			}
+		}
+
+		fn ensure_in_charge(origin: OriginFor<T>, id: &T::AssetId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(<RegulatedAssets<T>>::contains_key(id), Error::<T>::NotRegulated);
+			ensure!(Self::admin_of(id) == Some(who), Error::<T>::NotLegalOfficerInCharge);
+			Ok(())
+		}
+
+		/// The current admin of an asset, read from `pallet_assets`' `Asset` storage.
+		fn admin_of(id: &T::AssetId) -> Option<T::AccountId> {
+			let pallet = T::PalletInfo::name::<pallet_assets::Module<T>>()?;
+			get_storage_value::<AssetTeam<T::AccountId>>(pallet.as_bytes(), b"Asset", &id.using_encoded(Blake2_128Concat::hash))
+				.map(|team| team.admin)
+		}
+	}
+
+	impl<T: Config> AssetRecipients<T::AssetId, T::AccountId> for Pallet<T> {
+		fn may_receive(asset: &T::AssetId, account: &T::AccountId) -> bool {
+			Pallet::<T>::may_receive(asset, account)
+		}
+	}
+}
@@ -0,0 +1,121 @@
+use crate::{mock::*, Error, Event as RegulatedAssetsEvent};
+use frame_support::{assert_err, assert_ok, error::BadOrigin};
+use frame_system::RawOrigin;
+
+const ASSET_ID: u64 = 1;
+const LOC_ID: u32 = 1;
+
+fn create_regulated() {
+	assert_ok!(RegulatedAssets::create_regulated(Origin::signed(LEGAL_OFFICER), ASSET_ID, ISSUER, 1));
+}
+
+fn identify_recipient() {
+	assert_ok!(LogionLoc::create_polkadot_identity_loc(Origin::signed(LEGAL_OFFICER), LOC_ID, RECIPIENT));
+	assert_ok!(LogionLoc::close(Origin::signed(LEGAL_OFFICER), LOC_ID));
+}
+
+#[test]
+fn it_creates_regulated_asset() {
+	new_test_ext().execute_with(|| {
+		create_regulated();
+		assert_eq!(RegulatedAssets::legal_officer_of(ASSET_ID), Some(LEGAL_OFFICER));
+		assert_eq!(System::events().pop().unwrap().event,
+			Event::pallet_regulated_assets(RegulatedAssetsEvent::RegulatedAssetCreated(ASSET_ID, LEGAL_OFFICER, ISSUER)));
+
+		assert_ok!(Assets::mint(Origin::signed(ISSUER), ASSET_ID, RECIPIENT, 100));
+		assert_ok!(Assets::burn(Origin::signed(LEGAL_OFFICER), ASSET_ID, RECIPIENT, 50));
+		assert_eq!(Assets::balance(ASSET_ID, RECIPIENT), 50);
+	});
+}
+
+#[test]
+fn it_accepts_only_existing_holders() {
+	new_test_ext().execute_with(|| {
+		create_regulated();
+		assert_err!(Assets::mint(Origin::signed(ISSUER), ASSET_ID, NO_ACCOUNT, 100), pallet_assets::Error::<Test>::TooManyZombies);
+
+		assert_ok!(Balances::transfer(Origin::signed(LEGAL_OFFICER), NO_ACCOUNT, 10));
+		assert_ok!(Assets::mint(Origin::signed(ISSUER), ASSET_ID, NO_ACCOUNT, 100));
+		assert_eq!(Assets::balance(ASSET_ID, NO_ACCOUNT), 100);
+	});
+}
+
+#[test]
+fn it_fails_creating_if_not_legal_officer() {
+	new_test_ext().execute_with(|| {
+		assert_err!(RegulatedAssets::create_regulated(Origin::signed(ISSUER), ASSET_ID, ISSUER, 1), BadOrigin);
+	});
+}
+
+#[test]
+fn it_fails_creating_existing_asset() {
+	new_test_ext().execute_with(|| {
+		create_regulated();
+		assert_err!(RegulatedAssets::create_regulated(Origin::signed(OTHER_LEGAL_OFFICER), ASSET_ID, ISSUER, 1), Error::<Test>::AlreadyExists);
+	});
+}
+
+#[test]
+fn it_requires_identity() {
+	new_test_ext().execute_with(|| {
+		create_regulated();
+		assert!(RegulatedAssets::may_receive(&ASSET_ID, &RECIPIENT));
+
+		assert_ok!(RegulatedAssets::require_identity(Origin::signed(LEGAL_OFFICER), ASSET_ID, vec![LEGAL_OFFICER]));
+		assert!(!RegulatedAssets::may_receive(&ASSET_ID, &RECIPIENT));
+
+		identify_recipient();
+		assert!(RegulatedAssets::may_receive(&ASSET_ID, &RECIPIENT));
+	});
+}
+
+#[test]
+fn it_lifts_identity_requirement() {
+	new_test_ext().execute_with(|| {
+		create_regulated();
+		assert_ok!(RegulatedAssets::require_identity(Origin::signed(LEGAL_OFFICER), ASSET_ID, vec![LEGAL_OFFICER]));
+
+		assert_ok!(RegulatedAssets::lift_identity_requirement(Origin::signed(LEGAL_OFFICER), ASSET_ID));
+		assert!(RegulatedAssets::may_receive(&ASSET_ID, &RECIPIENT));
+		assert_eq!(System::events().pop().unwrap().event,
+			Event::pallet_regulated_assets(RegulatedAssetsEvent::IdentityRequirementLifted(ASSET_ID)));
+	});
+}
+
+#[test]
+fn it_rejects_invalid_legal_officers() {
+	new_test_ext().execute_with(|| {
+		create_regulated();
+		assert_err!(RegulatedAssets::require_identity(Origin::signed(LEGAL_OFFICER), ASSET_ID, vec![]), Error::<Test>::InvalidLegalOfficers);
+		assert_err!(RegulatedAssets::require_identity(Origin::signed(LEGAL_OFFICER), ASSET_ID,
+			vec![LEGAL_OFFICER, OTHER_LEGAL_OFFICER, ISSUER]), Error::<Test>::InvalidLegalOfficers);
+	});
+}
+
+#[test]
+fn it_fails_if_not_admin() {
+	new_test_ext().execute_with(|| {
+		create_regulated();
+		assert_err!(RegulatedAssets::require_identity(Origin::signed(ISSUER), ASSET_ID, vec![LEGAL_OFFICER]), Error::<Test>::NotLegalOfficerInCharge);
+		assert_err!(RegulatedAssets::lift_identity_requirement(Origin::signed(OTHER_LEGAL_OFFICER), ASSET_ID), Error::<Test>::NotLegalOfficerInCharge);
+	});
+}
+
+#[test]
+fn it_fails_on_unregulated_asset() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RawOrigin::Root.into(), ASSET_ID, LEGAL_OFFICER, 10, 1));
+		assert_err!(RegulatedAssets::require_identity(Origin::signed(LEGAL_OFFICER), ASSET_ID, vec![LEGAL_OFFICER]), Error::<Test>::NotRegulated);
+	});
+}
+
+#[test]
+fn it_follows_admin_role_handover() {
+	new_test_ext().execute_with(|| {
+		create_regulated();
+		assert_ok!(Assets::set_team(Origin::signed(LEGAL_OFFICER), ASSET_ID, ISSUER, OTHER_LEGAL_OFFICER, LEGAL_OFFICER));
+
+		assert_err!(RegulatedAssets::require_identity(Origin::signed(LEGAL_OFFICER), ASSET_ID, vec![LEGAL_OFFICER]), Error::<Test>::NotLegalOfficerInCharge);
+		assert_ok!(RegulatedAssets::require_identity(Origin::signed(OTHER_LEGAL_OFFICER), ASSET_ID, vec![OTHER_LEGAL_OFFICER]));
+	});
+}
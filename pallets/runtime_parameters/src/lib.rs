@@ -0,0 +1,174 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime parameters tunable by governance.
+//!
+//! Some values used by other pallets' `Config` are stored on-chain instead of being compile-time
+//! constants, so that governance can change them without a runtime upgrade. Each parameter has a
+//! typed key; new values are checked against the bounds given by the runtime. The runtime reads
+//! parameters through `Get` implementations falling back to a default when no value was set.
+//!
+//! Values which clients read from the metadata, such as the existential deposit, must not become
+//! parameters: metadata is cached for a given `spec_version` and would not reflect changes.
+//! Moreover, raising the existential deposit would not reap accounts already below it.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+
+/// The key of a runtime parameter.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ParameterKey {
+	MaxMetadataItemValueSize,
+	MaxCollectionItemDescriptionSize,
+	MultiSigDepositBase,
+	RecoveryDeposit,
+	TransactionByteFee,
+}
+
+/// A runtime parameter with its value.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Parameter<Balance> {
+	/// Maximum size in bytes of the value of a LOC metadata item
+	MaxMetadataItemValueSize(u32),
+	/// Maximum size in bytes of the description of a collection item
+	MaxCollectionItemDescriptionSize(u32),
+	/// Base deposit of a multisig operation
+	MultiSigDepositBase(Balance),
+	/// Deposit of a recovery attempt
+	RecoveryDeposit(Balance),
+	/// Fee of each byte of a transaction
+	TransactionByteFee(Balance),
+}
+
+impl<Balance> Parameter<Balance> {
+	pub fn key(&self) -> ParameterKey {
+		match self {
+			Parameter::MaxMetadataItemValueSize(_) => ParameterKey::MaxMetadataItemValueSize,
+			Parameter::MaxCollectionItemDescriptionSize(_) => ParameterKey::MaxCollectionItemDescriptionSize,
+			Parameter::MultiSigDepositBase(_) => ParameterKey::MultiSigDepositBase,
+			Parameter::RecoveryDeposit(_) => ParameterKey::RecoveryDeposit,
+			Parameter::TransactionByteFee(_) => ParameterKey::TransactionByteFee,
+		}
+	}
+}
+
+/// Tells if the value of a parameter is acceptable.
+pub trait ParameterBounds<Balance> {
+	fn within_bounds(parameter: &Parameter<Balance>) -> bool;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::AtLeast32BitUnsigned;
+	use super::{Parameter, ParameterBounds, ParameterKey};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The balance type of balance parameters
+		type Balance: frame_support::Parameter + Member + AtLeast32BitUnsigned + Default + Copy;
+
+		/// The origin allowed to change parameters
+		type ParameterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The bounds of each parameter
+		type ParameterBounds: ParameterBounds<Self::Balance>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// Parameters set by governance
+	#[pallet::storage]
+	#[pallet::getter(fn parameter)]
+	pub type Parameters<T: Config> = StorageMap<_, Twox64Concat, ParameterKey, Parameter<T::Balance>>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::Balance = "Balance")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when a parameter is set. [parameter]
+		ParameterSet(Parameter<T::Balance>),
+		/// Issued when a parameter is reset to its default. [key]
+		ParameterReset(ParameterKey),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The value of the parameter is out of bounds
+		OutOfBounds,
+		/// The parameter was not set
+		NotSet,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Sets the value of a parameter.
+		#[pallet::weight(T::DbWeight::get().writes(1) + 10_000_000)]
+		pub fn set_parameter(
+			origin: OriginFor<T>,
+			parameter: Parameter<T::Balance>,
+		) -> DispatchResultWithPostInfo {
+			T::ParameterOrigin::ensure_origin(origin)?;
+			ensure!(T::ParameterBounds::within_bounds(&parameter), Error::<T>::OutOfBounds);
+
+			<Parameters<T>>::insert(parameter.key(), parameter);
+			Self::deposit_event(Event::ParameterSet(parameter));
+			Ok(().into())
+		}
+
+		/// Resets a parameter to the default value of the runtime.
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1) + 10_000_000)]
+		pub fn reset_parameter(
+			origin: OriginFor<T>,
+			key: ParameterKey,
+		) -> DispatchResultWithPostInfo {
+			T::ParameterOrigin::ensure_origin(origin)?;
+			ensure!(<Parameters<T>>::contains_key(key), Error::<T>::NotSet);
+
+			<Parameters<T>>::remove(key);
+			Self::deposit_event(Event::ParameterReset(key));
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The value of a size parameter, if set.
+		pub fn size(key: ParameterKey) -> Option<u32> {
+			match <Parameters<T>>::get(key)? {
+				Parameter::MaxMetadataItemValueSize(size)
+				| Parameter::MaxCollectionItemDescriptionSize(size) => Some(size),
+				_ => None,
+			}
+		}
+
+		/// The value of a balance parameter, if set.
+		pub fn balance(key: ParameterKey) -> Option<T::Balance> {
+			match <Parameters<T>>::get(key)? {
+				Parameter::MultiSigDepositBase(balance)
+				| Parameter::RecoveryDeposit(balance)
+				| Parameter::TransactionByteFee(balance) => Some(balance),
+				_ => None,
+			}
+		}
+	}
+}
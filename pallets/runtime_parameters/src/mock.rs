@@ -0,0 +1,84 @@
+use crate as pallet_runtime_parameters;
+use crate::{Parameter, ParameterBounds};
+use sp_core::H256;
+use frame_support::parameter_types;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+};
+use frame_system as system;
+use frame_system::EnsureRoot;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		RuntimeParameters: pallet_runtime_parameters::{Module, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+}
+
+pub const MIN_SIZE: u32 = 255;
+pub const MAX_SIZE: u32 = 16_384;
+pub const MIN_BALANCE: u128 = 10;
+pub const MAX_BALANCE: u128 = 1_000;
+
+pub struct TestParameterBounds;
+impl ParameterBounds<u128> for TestParameterBounds {
+	fn within_bounds(parameter: &Parameter<u128>) -> bool {
+		match parameter {
+			Parameter::MaxMetadataItemValueSize(size)
+			| Parameter::MaxCollectionItemDescriptionSize(size) => (MIN_SIZE..=MAX_SIZE).contains(size),
+			Parameter::MultiSigDepositBase(balance)
+			| Parameter::RecoveryDeposit(balance)
+			| Parameter::TransactionByteFee(balance) => (MIN_BALANCE..=MAX_BALANCE).contains(balance),
+		}
+	}
+}
+
+impl pallet_runtime_parameters::Config for Test {
+	type Balance = u128;
+	type ParameterOrigin = EnsureRoot<u64>;
+	type ParameterBounds = TestParameterBounds;
+	type Event = Event;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,107 @@
+use crate::{mock::*, Error, Event as ParametersEvent, Parameter, ParameterKey};
+use frame_support::{assert_err, assert_ok};
+use sp_runtime::DispatchError;
+
+const USER: u64 = 1;
+
+#[test]
+fn it_sets_parameter() {
+	new_test_ext().execute_with(|| {
+		let parameter = Parameter::RecoveryDeposit(100);
+		assert_ok!(RuntimeParameters::set_parameter(Origin::root(), parameter));
+		assert_eq!(RuntimeParameters::parameter(ParameterKey::RecoveryDeposit), Some(parameter));
+		assert_eq!(System::events().pop().unwrap().event, Event::pallet_runtime_parameters(ParametersEvent::ParameterSet(parameter)));
+	});
+}
+
+#[test]
+fn it_overwrites_parameter() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(RuntimeParameters::set_parameter(Origin::root(), Parameter::MaxMetadataItemValueSize(1_000)));
+		assert_ok!(RuntimeParameters::set_parameter(Origin::root(), Parameter::MaxMetadataItemValueSize(2_000)));
+		assert_eq!(RuntimeParameters::size(ParameterKey::MaxMetadataItemValueSize), Some(2_000));
+	});
+}
+
+#[test]
+fn it_resets_parameter() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(RuntimeParameters::set_parameter(Origin::root(), Parameter::TransactionByteFee(100)));
+		assert_ok!(RuntimeParameters::reset_parameter(Origin::root(), ParameterKey::TransactionByteFee));
+		assert_eq!(RuntimeParameters::parameter(ParameterKey::TransactionByteFee), None);
+		assert_eq!(System::events().pop().unwrap().event, Event::pallet_runtime_parameters(ParametersEvent::ParameterReset(ParameterKey::TransactionByteFee)));
+	});
+}
+
+#[test]
+fn it_fails_resetting_unset_parameter() {
+	new_test_ext().execute_with(|| {
+		assert_err!(RuntimeParameters::reset_parameter(Origin::root(), ParameterKey::RecoveryDeposit), Error::<Test>::NotSet);
+	});
+}
+
+#[test]
+fn it_fails_setting_parameter_below_bounds() {
+	new_test_ext().execute_with(|| {
+		assert_err!(RuntimeParameters::set_parameter(Origin::root(), Parameter::MultiSigDepositBase(MIN_BALANCE - 1)), Error::<Test>::OutOfBounds);
+		assert_err!(RuntimeParameters::set_parameter(Origin::root(), Parameter::MaxCollectionItemDescriptionSize(MIN_SIZE - 1)), Error::<Test>::OutOfBounds);
+		assert_eq!(RuntimeParameters::parameter(ParameterKey::MultiSigDepositBase), None);
+		assert_eq!(RuntimeParameters::parameter(ParameterKey::MaxCollectionItemDescriptionSize), None);
+	});
+}
+
+#[test]
+fn it_fails_setting_parameter_above_bounds() {
+	new_test_ext().execute_with(|| {
+		assert_err!(RuntimeParameters::set_parameter(Origin::root(), Parameter::TransactionByteFee(MAX_BALANCE + 1)), Error::<Test>::OutOfBounds);
+		assert_err!(RuntimeParameters::set_parameter(Origin::root(), Parameter::MaxMetadataItemValueSize(MAX_SIZE + 1)), Error::<Test>::OutOfBounds);
+		assert_eq!(RuntimeParameters::parameter(ParameterKey::TransactionByteFee), None);
+		assert_eq!(RuntimeParameters::parameter(ParameterKey::MaxMetadataItemValueSize), None);
+	});
+}
+
+#[test]
+fn it_fails_setting_parameter_if_not_root() {
+	new_test_ext().execute_with(|| {
+		assert_err!(RuntimeParameters::set_parameter(Origin::signed(USER), Parameter::RecoveryDeposit(100)), DispatchError::BadOrigin);
+		assert_eq!(RuntimeParameters::parameter(ParameterKey::RecoveryDeposit), None);
+	});
+}
+
+#[test]
+fn it_fails_resetting_parameter_if_not_root() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(RuntimeParameters::set_parameter(Origin::root(), Parameter::RecoveryDeposit(100)));
+		assert_err!(RuntimeParameters::reset_parameter(Origin::signed(USER), ParameterKey::RecoveryDeposit), DispatchError::BadOrigin);
+		assert_eq!(RuntimeParameters::balance(ParameterKey::RecoveryDeposit), Some(100));
+	});
+}
+
+#[test]
+fn it_falls_back_to_default_when_not_set() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(RuntimeParameters::size(ParameterKey::MaxMetadataItemValueSize), None);
+		assert_eq!(RuntimeParameters::balance(ParameterKey::MultiSigDepositBase), None);
+		assert_eq!(RuntimeParameters::balance(ParameterKey::MultiSigDepositBase).unwrap_or(500), 500);
+	});
+}
+
+#[test]
+fn it_falls_back_to_default_after_reset() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(RuntimeParameters::set_parameter(Origin::root(), Parameter::MultiSigDepositBase(MAX_BALANCE)));
+		assert_eq!(RuntimeParameters::balance(ParameterKey::MultiSigDepositBase).unwrap_or(500), MAX_BALANCE);
+		assert_ok!(RuntimeParameters::reset_parameter(Origin::root(), ParameterKey::MultiSigDepositBase));
+		assert_eq!(RuntimeParameters::balance(ParameterKey::MultiSigDepositBase).unwrap_or(500), 500);
+	});
+}
+
+#[test]
+fn it_reads_parameter_with_matching_kind_only() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(RuntimeParameters::set_parameter(Origin::root(), Parameter::MaxMetadataItemValueSize(1_000)));
+		assert_ok!(RuntimeParameters::set_parameter(Origin::root(), Parameter::RecoveryDeposit(100)));
+		assert_eq!(RuntimeParameters::balance(ParameterKey::MaxMetadataItemValueSize), None);
+		assert_eq!(RuntimeParameters::size(ParameterKey::RecoveryDeposit), None);
+	});
+}
@@ -0,0 +1,203 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Emergency safe mode.
+//!
+//! While safe mode is active, the runtime only lets through a whitelist of calls (see the
+//! runtime's `BaseCallFilter`). Safe mode is entered either by the `EnterOrigin` or when a
+//! supermajority (at least two thirds) of the legal officers voted for it. It automatically
+//! expires after a given number of blocks or may be exited earlier by the `ExitOrigin`.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Saturating, Zero};
+	use sp_std::prelude::*;
+
+	/// Gives access to the current set of legal officers.
+	pub trait LegalOfficerSet<AccountId> {
+		fn is_legal_officer(account: &AccountId) -> bool;
+		fn legal_officers_count() -> u32;
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The origin allowed to enter safe mode without vote
+		type EnterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin allowed to exit safe mode before expiration
+		type ExitOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The legal officers allowed to vote for safe mode
+		type LegalOfficerSet: LegalOfficerSet<Self::AccountId>;
+
+		/// The number of blocks safe mode lasts for when no duration is given
+		type DefaultDuration: Get<Self::BlockNumber>;
+
+		/// The maximum number of blocks safe mode may last for
+		type MaxDuration: Get<Self::BlockNumber>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// The block at which safe mode expires, if active
+	#[pallet::storage]
+	#[pallet::getter(fn active_until)]
+	pub type ActiveUntil<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	/// Legal officers who voted for entering safe mode
+	#[pallet::storage]
+	#[pallet::getter(fn votes)]
+	pub type Votes<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId", T::BlockNumber = "BlockNumber")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when safe mode is entered. [expiration block]
+		Entered(T::BlockNumber),
+		/// Issued when safe mode is exited before expiration.
+		Exited,
+		/// Issued when safe mode expires.
+		Expired,
+		/// Issued when a legal officer votes for safe mode. [legal officer, votes count]
+		Voted(T::AccountId, u32),
+		/// Issued when a legal officer withdraws its vote. [legal officer, votes count]
+		VoteWithdrawn(T::AccountId, u32),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Safe mode is already active
+		AlreadyActive,
+		/// Safe mode is not active
+		NotActive,
+		/// Only legal officers are allowed to vote
+		NotLegalOfficer,
+		/// Legal officer already voted
+		AlreadyVoted,
+		/// Legal officer did not vote
+		NotVoted,
+		/// Requested duration is zero or above the maximum
+		InvalidDuration,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			match Self::active_until() {
+				Some(until) if now >= until => {
+					<ActiveUntil<T>>::kill();
+					Self::deposit_event(Event::Expired);
+					T::DbWeight::get().reads_writes(1, 1)
+				},
+				_ => T::DbWeight::get().reads(1),
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Enters safe mode for given number of blocks or `DefaultDuration` if none is given.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		pub fn force_enter(
+			origin: OriginFor<T>,
+			duration: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			T::EnterOrigin::ensure_origin(origin)?;
+			ensure!(Self::active_until().is_none(), Error::<T>::AlreadyActive);
+			let duration = duration.unwrap_or_else(T::DefaultDuration::get);
+			ensure!(!duration.is_zero() && duration <= T::MaxDuration::get(), Error::<T>::InvalidDuration);
+
+			Self::enter(duration);
+			Ok(().into())
+		}
+
+		/// Exits safe mode before its expiration.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn force_exit(
+			origin: OriginFor<T>,
+		) -> DispatchResultWithPostInfo {
+			T::ExitOrigin::ensure_origin(origin)?;
+			ensure!(Self::active_until().is_some(), Error::<T>::NotActive);
+
+			<ActiveUntil<T>>::kill();
+			Self::deposit_event(Event::Exited);
+			Ok(().into())
+		}
+
+		/// Votes for entering safe mode. Safe mode is entered for `DefaultDuration` blocks
+		/// as soon as two thirds of the legal officers voted.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 2))]
+		pub fn vote_enter(
+			origin: OriginFor<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(T::LegalOfficerSet::is_legal_officer(&who), Error::<T>::NotLegalOfficer);
+			ensure!(Self::active_until().is_none(), Error::<T>::AlreadyActive);
+
+			let mut votes: Vec<T::AccountId> = Self::votes()
+				.into_iter()
+				.filter(T::LegalOfficerSet::is_legal_officer)
+				.collect();
+			ensure!(!votes.contains(&who), Error::<T>::AlreadyVoted);
+			votes.push(who.clone());
+			let votes_count = votes.len() as u32;
+			Self::deposit_event(Event::Voted(who, votes_count));
+
+			if votes_count.saturating_mul(3) >= T::LegalOfficerSet::legal_officers_count().saturating_mul(2) {
+				Self::enter(T::DefaultDuration::get());
+			} else {
+				<Votes<T>>::put(votes);
+			}
+			Ok(().into())
+		}
+
+		/// Withdraws a vote for entering safe mode.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn withdraw_vote(
+			origin: OriginFor<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut votes = Self::votes();
+			let index = votes.iter().position(|voter| *voter == who).ok_or(Error::<T>::NotVoted)?;
+			votes.swap_remove(index);
+			let votes_count = votes.len() as u32;
+
+			<Votes<T>>::put(votes);
+			Self::deposit_event(Event::VoteWithdrawn(who, votes_count));
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn enter(duration: T::BlockNumber) {
+			let until = frame_system::Module::<T>::block_number().saturating_add(duration);
+			<ActiveUntil<T>>::put(until);
+			<Votes<T>>::kill();
+			Self::deposit_event(Event::Entered(until));
+		}
+
+		/// Tells if safe mode is currently active.
+		pub fn is_active() -> bool {
+			Self::active_until().is_some()
+		}
+	}
+}
@@ -0,0 +1,99 @@
+use crate as pallet_safe_mode;
+use sp_core::H256;
+use frame_support::parameter_types;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup}, testing::Header,
+};
+use frame_system as system;
+use frame_system::EnsureRoot;
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		SafeMode: pallet_safe_mode::{Module, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+}
+
+pub const LEGAL_OFFICER_1: u64 = 1;
+pub const LEGAL_OFFICER_2: u64 = 2;
+pub const LEGAL_OFFICER_3: u64 = 3;
+pub const LEGAL_OFFICER_4: u64 = 4;
+pub const NOT_LEGAL_OFFICER: u64 = 5;
+
+thread_local! {
+	static LEGAL_OFFICERS: RefCell<Vec<u64>> = RefCell::new(vec![LEGAL_OFFICER_1, LEGAL_OFFICER_2, LEGAL_OFFICER_3, LEGAL_OFFICER_4]);
+}
+
+/// Removes given account from the set of legal officers.
+pub fn remove_legal_officer(account: u64) {
+	LEGAL_OFFICERS.with(|legal_officers| legal_officers.borrow_mut().retain(|legal_officer| *legal_officer != account));
+}
+
+pub struct LegalOfficers;
+impl pallet_safe_mode::LegalOfficerSet<u64> for LegalOfficers {
+	fn is_legal_officer(account: &u64) -> bool {
+		LEGAL_OFFICERS.with(|legal_officers| legal_officers.borrow().contains(account))
+	}
+
+	fn legal_officers_count() -> u32 {
+		LEGAL_OFFICERS.with(|legal_officers| legal_officers.borrow().len() as u32)
+	}
+}
+
+parameter_types! {
+	pub const DefaultDuration: u64 = 10;
+	pub const MaxDuration: u64 = 100;
+}
+
+impl pallet_safe_mode::Config for Test {
+	type EnterOrigin = EnsureRoot<u64>;
+	type ExitOrigin = EnsureRoot<u64>;
+	type LegalOfficerSet = LegalOfficers;
+	type DefaultDuration = DefaultDuration;
+	type MaxDuration = MaxDuration;
+	type Event = Event;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities = system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
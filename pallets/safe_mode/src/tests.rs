@@ -0,0 +1,188 @@
+use crate::{mock::*, Error, Event as SafeModeEvent};
+use frame_support::{assert_err, assert_ok, traits::{Get, Hooks}};
+use sp_runtime::DispatchError;
+
+fn last_event() -> Event {
+	System::events().pop().unwrap().event
+}
+
+fn run_to(block: u64) {
+	System::set_block_number(block);
+	SafeMode::on_initialize(block);
+}
+
+#[test]
+fn it_enters_on_force_enter() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::force_enter(Origin::root(), Some(5)));
+		assert!(SafeMode::is_active());
+		assert_eq!(SafeMode::active_until(), Some(6));
+		assert_eq!(last_event(), Event::pallet_safe_mode(SafeModeEvent::Entered(6)));
+	});
+}
+
+#[test]
+fn it_enters_for_default_duration() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::force_enter(Origin::root(), None));
+		assert_eq!(SafeMode::active_until(), Some(1 + DefaultDuration::get()));
+	});
+}
+
+#[test]
+fn it_fails_force_enter_if_not_enter_origin() {
+	new_test_ext().execute_with(|| {
+		assert_err!(SafeMode::force_enter(Origin::signed(LEGAL_OFFICER_1), None), DispatchError::BadOrigin);
+		assert!(!SafeMode::is_active());
+	});
+}
+
+#[test]
+fn it_fails_force_enter_with_invalid_duration() {
+	new_test_ext().execute_with(|| {
+		assert_err!(SafeMode::force_enter(Origin::root(), Some(0)), Error::<Test>::InvalidDuration);
+		assert_err!(SafeMode::force_enter(Origin::root(), Some(MaxDuration::get() + 1)), Error::<Test>::InvalidDuration);
+		assert!(!SafeMode::is_active());
+	});
+}
+
+#[test]
+fn it_fails_force_enter_if_active() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::force_enter(Origin::root(), Some(5)));
+		assert_err!(SafeMode::force_enter(Origin::root(), Some(50)), Error::<Test>::AlreadyActive);
+		assert_eq!(SafeMode::active_until(), Some(6));
+	});
+}
+
+#[test]
+fn it_exits_on_force_exit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::force_enter(Origin::root(), Some(5)));
+		assert_ok!(SafeMode::force_exit(Origin::root()));
+		assert!(!SafeMode::is_active());
+		assert_eq!(last_event(), Event::pallet_safe_mode(SafeModeEvent::Exited));
+	});
+}
+
+#[test]
+fn it_fails_force_exit_if_not_exit_origin() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::force_enter(Origin::root(), Some(5)));
+		assert_err!(SafeMode::force_exit(Origin::signed(LEGAL_OFFICER_1)), DispatchError::BadOrigin);
+		assert!(SafeMode::is_active());
+	});
+}
+
+#[test]
+fn it_fails_force_exit_if_not_active() {
+	new_test_ext().execute_with(|| {
+		assert_err!(SafeMode::force_exit(Origin::root()), Error::<Test>::NotActive);
+	});
+}
+
+#[test]
+fn it_expires_on_initialize() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::force_enter(Origin::root(), Some(5)));
+
+		run_to(5);
+		assert!(SafeMode::is_active());
+
+		run_to(6);
+		assert!(!SafeMode::is_active());
+		assert_eq!(last_event(), Event::pallet_safe_mode(SafeModeEvent::Expired));
+	});
+}
+
+#[test]
+fn it_enters_when_two_thirds_voted() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_1)));
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_2)));
+		assert!(!SafeMode::is_active());
+		assert_eq!(SafeMode::votes(), vec![LEGAL_OFFICER_1, LEGAL_OFFICER_2]);
+
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_3)));
+		assert!(SafeMode::is_active());
+		assert_eq!(SafeMode::active_until(), Some(1 + DefaultDuration::get()));
+		assert!(SafeMode::votes().is_empty());
+		assert_eq!(last_event(), Event::pallet_safe_mode(SafeModeEvent::Entered(1 + DefaultDuration::get())));
+	});
+}
+
+#[test]
+fn it_enters_on_exactly_two_thirds() {
+	new_test_ext().execute_with(|| {
+		remove_legal_officer(LEGAL_OFFICER_4);
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_1)));
+		assert!(!SafeMode::is_active());
+
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_2)));
+		assert!(SafeMode::is_active());
+	});
+}
+
+#[test]
+fn it_fails_voting_twice() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_1)));
+		assert_err!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_1)), Error::<Test>::AlreadyVoted);
+		assert_eq!(SafeMode::votes(), vec![LEGAL_OFFICER_1]);
+	});
+}
+
+#[test]
+fn it_fails_voting_if_not_legal_officer() {
+	new_test_ext().execute_with(|| {
+		assert_err!(SafeMode::vote_enter(Origin::signed(NOT_LEGAL_OFFICER)), Error::<Test>::NotLegalOfficer);
+		assert!(SafeMode::votes().is_empty());
+	});
+}
+
+#[test]
+fn it_fails_voting_if_active() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::force_enter(Origin::root(), None));
+		assert_err!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_1)), Error::<Test>::AlreadyActive);
+	});
+}
+
+#[test]
+fn it_ignores_votes_of_former_legal_officers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_1)));
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_2)));
+		remove_legal_officer(LEGAL_OFFICER_1);
+
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_3)));
+		assert!(SafeMode::is_active());
+
+		assert_ok!(SafeMode::force_exit(Origin::root()));
+		remove_legal_officer(LEGAL_OFFICER_2);
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_3)));
+		assert!(!SafeMode::is_active());
+		assert_eq!(last_event(), Event::pallet_safe_mode(SafeModeEvent::Voted(LEGAL_OFFICER_3, 1)));
+	});
+}
+
+#[test]
+fn it_withdraws_vote() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_1)));
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_2)));
+		assert_ok!(SafeMode::withdraw_vote(Origin::signed(LEGAL_OFFICER_1)));
+		assert_eq!(SafeMode::votes(), vec![LEGAL_OFFICER_2]);
+		assert_eq!(last_event(), Event::pallet_safe_mode(SafeModeEvent::VoteWithdrawn(LEGAL_OFFICER_1, 1)));
+
+		assert_ok!(SafeMode::vote_enter(Origin::signed(LEGAL_OFFICER_3)));
+		assert!(!SafeMode::is_active());
+	});
+}
+
+#[test]
+fn it_fails_withdrawing_missing_vote() {
+	new_test_ext().execute_with(|| {
+		assert_err!(SafeMode::withdraw_vote(Origin::signed(LEGAL_OFFICER_1)), Error::<Test>::NotVoted);
+	});
+}
@@ -0,0 +1,200 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime upgrades announced ahead of time.
+//!
+//! Governance schedules a runtime upgrade at a given block, at least `MinUpgradeNotice` blocks
+//! ahead. The hash of the new code and the block of the upgrade are exposed in storage and in the
+//! `UpgradeScheduled` event so that legal officer backends can prepare. The upgrade is applied by
+//! `pallet_scheduler` through a named task, and may be cancelled until then.
+//!
+//! The version of the new code is checked when the upgrade is scheduled. If setting the code still
+//! fails, the upgrade is dropped and `UpgradeFailed` is issued so that another one may be scheduled.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+
+/// A scheduled runtime upgrade.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PendingUpgrade<Hash, BlockNumber> {
+	/// The hash of the new runtime code
+	pub code_hash: Hash,
+	/// The block at which the new code is set
+	pub at: BlockNumber,
+}
+
+pub type PendingUpgradeOf<T> = PendingUpgrade<
+	<T as frame_system::Config>::Hash,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+/// The name of the scheduler task applying the upgrade.
+pub const UPGRADE_TASK_ID: &[u8] = b"logion/runtime-upgrade";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo,
+		pallet_prelude::*,
+		traits::schedule::{DispatchTime, Named as ScheduleNamed, HARD_DEADLINE},
+	};
+	use codec::Decode;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Hash, Saturating};
+	use sp_std::prelude::*;
+	use sp_version::RuntimeVersion;
+	use super::{PendingUpgradeOf, UPGRADE_TASK_ID};
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The runtime call type, built from this pallet's calls
+		type Call: From<Call<Self>>;
+
+		/// The origin of scheduled calls
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+		/// The scheduler applying upgrades
+		type Scheduler: ScheduleNamed<Self::BlockNumber, <Self as Config>::Call, Self::PalletsOrigin>;
+
+		/// The origin allowed to schedule and cancel upgrades
+		type UpgradeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The minimum number of blocks between the scheduling of an upgrade and the upgrade
+		type MinUpgradeNotice: Get<Self::BlockNumber>;
+
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	/// The scheduled upgrade, if any
+	#[pallet::storage]
+	#[pallet::getter(fn pending_upgrade)]
+	pub type PendingUpgrades<T: Config> = StorageValue<_, PendingUpgradeOf<T>>;
+
+	/// The code of the scheduled upgrade
+	#[pallet::storage]
+	pub type PendingCode<T> = StorageValue<_, Vec<u8>>;
+
+	#[pallet::event]
+	#[pallet::metadata(T::Hash = "Hash", T::BlockNumber = "BlockNumber")]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Issued when an upgrade is scheduled. [code hash, block]
+		UpgradeScheduled(T::Hash, T::BlockNumber),
+		/// Issued when a scheduled upgrade is cancelled. [code hash]
+		UpgradeCancelled(T::Hash),
+		/// Issued when a scheduled upgrade is applied. [code hash]
+		UpgradeApplied(T::Hash),
+		/// Issued when the code of a scheduled upgrade could not be set. [code hash]
+		UpgradeFailed(T::Hash),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Another upgrade is already scheduled
+		AlreadyScheduled,
+		/// The upgrade is not announced long enough in advance
+		NoticeTooShort,
+		/// The scheduler rejected the task
+		SchedulingFailed,
+		/// No upgrade is scheduled
+		NotScheduled,
+		/// The runtime version of the code could not be read
+		InvalidCode,
+		/// The code is not a logion runtime
+		InvalidSpecName,
+		/// The spec version of the code is not greater than the current one
+		SpecVersionNotIncreased,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+
+		/// Schedules the upgrade of the runtime code at given block.
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 4) + 100_000_000)]
+		pub fn schedule_upgrade(
+			origin: OriginFor<T>,
+			code: Vec<u8>,
+			at: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::UpgradeOrigin::ensure_origin(origin)?;
+			ensure!(!<PendingUpgrades<T>>::exists(), Error::<T>::AlreadyScheduled);
+			let now = frame_system::Module::<T>::block_number();
+			ensure!(at >= now.saturating_add(T::MinUpgradeNotice::get()), Error::<T>::NoticeTooShort);
+			Self::check_version(&code)?;
+
+			T::Scheduler::schedule_named(
+				UPGRADE_TASK_ID.to_vec(),
+				DispatchTime::At(at),
+				None,
+				HARD_DEADLINE,
+				frame_system::RawOrigin::Root.into(),
+				Call::<T>::apply_upgrade().into(),
+			).map_err(|_| Error::<T>::SchedulingFailed)?;
+			let code_hash = T::Hashing::hash(&code);
+			<PendingUpgrades<T>>::put(PendingUpgradeOf::<T> { code_hash, at });
+			<PendingCode<T>>::put(code);
+			Self::deposit_event(Event::UpgradeScheduled(code_hash, at));
+			Ok(().into())
+		}
+
+		/// Cancels the scheduled upgrade, even if its scheduler task is gone.
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 4) + 100_000_000)]
+		pub fn cancel_upgrade(
+			origin: OriginFor<T>,
+		) -> DispatchResultWithPostInfo {
+			T::UpgradeOrigin::ensure_origin(origin)?;
+			let upgrade = <PendingUpgrades<T>>::get().ok_or(Error::<T>::NotScheduled)?;
+
+			let _ = T::Scheduler::cancel_named(UPGRADE_TASK_ID.to_vec());
+			<PendingUpgrades<T>>::kill();
+			<PendingCode<T>>::kill();
+			Self::deposit_event(Event::UpgradeCancelled(upgrade.code_hash));
+			Ok(().into())
+		}
+
+		/// Sets the code of the scheduled upgrade, dispatched by the scheduler. The upgrade is dropped
+		/// whether the code is set or not.
+		#[pallet::weight((T::BlockWeights::get().max_block, DispatchClass::Operational))]
+		pub fn apply_upgrade(
+			origin: OriginFor<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let upgrade = <PendingUpgrades<T>>::take().ok_or(Error::<T>::NotScheduled)?;
+			let code = <PendingCode<T>>::take().ok_or(Error::<T>::NotScheduled)?;
+
+			match frame_system::Module::<T>::set_code(frame_system::RawOrigin::Root.into(), code) {
+				Ok(_) => Self::deposit_event(Event::UpgradeApplied(upgrade.code_hash)),
+				Err(_) => Self::deposit_event(Event::UpgradeFailed(upgrade.code_hash)),
+			}
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Checks that the code is a later version of the current runtime.
+		fn check_version(code: &[u8]) -> DispatchResult {
+			let current = <T as frame_system::Config>::Version::get();
+			let new = sp_io::misc::runtime_version(code)
+				.and_then(|version| RuntimeVersion::decode(&mut &version[..]).ok())
+				.ok_or(Error::<T>::InvalidCode)?;
+			ensure!(new.spec_name == current.spec_name, Error::<T>::InvalidSpecName);
+			ensure!(new.spec_version > current.spec_version, Error::<T>::SpecVersionNotIncreased);
+			Ok(())
+		}
+	}
+}
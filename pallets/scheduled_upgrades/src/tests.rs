@@ -0,0 +1,74 @@
+use crate::{mock::*, Error, Event as ScheduledUpgradesEvent, PendingCode, PendingUpgrade, PendingUpgrades};
+use frame_support::{assert_err, assert_ok};
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+const AT: u64 = 1 + 10;
+
+fn invalid_code() -> Vec<u8> {
+	vec![1, 2, 3]
+}
+
+/// Stores an upgrade as if it was scheduled, the code not being a runtime.
+fn pending_upgrade() {
+	<PendingUpgrades<Test>>::put(PendingUpgrade { code_hash: BlakeTwo256::hash(&invalid_code()), at: AT });
+	<PendingCode<Test>>::put(invalid_code());
+}
+
+#[test]
+fn it_rejects_short_notice() {
+	new_test_ext().execute_with(|| {
+		assert_err!(ScheduledUpgrades::schedule_upgrade(Origin::root(), invalid_code(), AT - 1), Error::<Test>::NoticeTooShort);
+	});
+}
+
+#[test]
+fn it_rejects_code_without_runtime_version() {
+	new_test_ext().execute_with(|| {
+		assert_err!(ScheduledUpgrades::schedule_upgrade(Origin::root(), invalid_code(), AT), Error::<Test>::InvalidCode);
+		assert!(ScheduledUpgrades::pending_upgrade().is_none());
+		assert!(!<PendingCode<Test>>::exists());
+	});
+}
+
+#[test]
+fn it_drops_upgrade_if_code_is_not_set() {
+	new_test_ext().execute_with(|| {
+		pending_upgrade();
+
+		assert_ok!(ScheduledUpgrades::apply_upgrade(Origin::root()));
+		assert!(ScheduledUpgrades::pending_upgrade().is_none());
+		assert!(!<PendingCode<Test>>::exists());
+		assert_eq!(System::events().pop().unwrap().event,
+			Event::pallet_scheduled_upgrades(ScheduledUpgradesEvent::UpgradeFailed(BlakeTwo256::hash(&invalid_code()))));
+
+		// Another upgrade may be scheduled
+		assert_err!(ScheduledUpgrades::schedule_upgrade(Origin::root(), invalid_code(), AT), Error::<Test>::InvalidCode);
+	});
+}
+
+#[test]
+fn it_cancels_upgrade_without_scheduler_task() {
+	new_test_ext().execute_with(|| {
+		pending_upgrade();
+
+		assert_ok!(ScheduledUpgrades::cancel_upgrade(Origin::root()));
+		assert!(ScheduledUpgrades::pending_upgrade().is_none());
+		assert!(!<PendingCode<Test>>::exists());
+		assert_eq!(System::events().pop().unwrap().event,
+			Event::pallet_scheduled_upgrades(ScheduledUpgradesEvent::UpgradeCancelled(BlakeTwo256::hash(&invalid_code()))));
+	});
+}
+
+#[test]
+fn it_rejects_cancel_without_upgrade() {
+	new_test_ext().execute_with(|| {
+		assert_err!(ScheduledUpgrades::cancel_upgrade(Origin::root()), Error::<Test>::NotScheduled);
+	});
+}
+
+#[test]
+fn it_rejects_apply_without_upgrade() {
+	new_test_ext().execute_with(|| {
+		assert_err!(ScheduledUpgrades::apply_upgrade(Origin::root()), Error::<Test>::NotScheduled);
+	});
+}
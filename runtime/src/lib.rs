@@ -10,12 +10,12 @@ use pallet_multisig::Timepoint;
 use sp_std::prelude::*;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
-	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
+	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature, ModuleId,
 	transaction_validity::{TransactionValidity, TransactionSource},
-	traits::{ OpaqueKeys }
+	traits::{ OpaqueKeys, AccountIdConversion }
 };
 use sp_runtime::traits::{
-	BlakeTwo256, Block as BlockT, AccountIdLookup, Verify, IdentifyAccount, NumberFor,
+	BlakeTwo256, Block as BlockT, Verify, IdentifyAccount, NumberFor, StaticLookup,
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -33,15 +33,17 @@ pub use pallet_balances::Call as BalancesCall;
 pub use sp_runtime::{Permill, Perbill};
 pub use frame_support::{
 	construct_runtime, parameter_types, StorageValue, RuntimeDebug,
-	traits::{KeyOwnerProofSystem, Randomness, InstanceFilter, Filter},
+	traits::{KeyOwnerProofSystem, Randomness, InstanceFilter, Filter, Get, GetCallMetadata},
 	weights::{
 		Weight, IdentityFee,
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 	},
 	codec::{Encode, Decode},
 };
+use frame_support::weights::GetDispatchInfo;
 use pallet_transaction_payment::CurrencyAdapter;
-use frame_system::EnsureRoot;
+use frame_system::{EnsureOneOf, EnsureRoot};
+use pallet_runtime_parameters::{Parameter, ParameterKey};
 use logion_shared::{CreateRecoveryCallFactory, MultisigApproveAsMultiCallFactory, MultisigAsMultiCallFactory};
 
 /// An index to a block.
@@ -61,6 +63,10 @@ pub type AccountIndex = u32;
 /// Balance of an account.
 pub type Balance = u128;
 
+/// One LGNT, the native token has 18 decimals.
+pub const LGNT: Balance = 1_000_000_000_000_000_000;
+pub const MILLI_LGNT: Balance = LGNT / 1_000;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -108,10 +114,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 107,
+	spec_version: 147,
 	impl_version: 2,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 5,
+	transaction_version: 17,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -157,10 +163,91 @@ pub struct BaseCallFilter;
 impl Filter<Call> for BaseCallFilter {
 	fn filter(call: &Call) -> bool {
 		match call {
+			// Safety defaults, they cannot be lifted by governance.
 			Call::Recovery(pallet_recovery::Call::create_recovery(..)) => false,
 			Call::Multisig(pallet_multisig::Call::approve_as_multi(..)) => false,
 			Call::Multisig(pallet_multisig::Call::as_multi(..)) => false,
-			_ => true
+			// Node ownership is managed through LoNodeAuthorization.
+			Call::NodeAuthorization(pallet_node_authorization::Call::claim_node(..)) => false,
+			Call::NodeAuthorization(pallet_node_authorization::Call::remove_claim(..)) => false,
+			Call::NodeAuthorization(pallet_node_authorization::Call::transfer_node(..)) => false,
+			// Judgements and registrars are managed through LoIdentity.
+			Call::Identity(pallet_identity::Call::provide_judgement(..)) => false,
+			Call::Identity(pallet_identity::Call::set_account_id(..)) => false,
+			Call::LogionLoc(pallet_logion_loc::Call::make_void(loc_id))
+			| Call::LogionLoc(pallet_logion_loc::Call::make_void_and_replace(loc_id, _))
+				if LoIdentity::backs_judgement(loc_id) => false,
+			_ => (!SafeMode::is_active() || SafeModeWhitelist::filter(call))
+				&& !CallFilter::is_paused(&call.get_call_metadata())
+				&& RegulatedTransfers::filter(call)
+				&& AccountBasedAssets::filter(call)
+				&& AtomicLocBatches::filter(call)
+		}
+	}
+}
+
+/// Charged LOC operations are only batched with `batch_all`: fees and deposits are kept only if the
+/// whole batch succeeds.
+pub struct AtomicLocBatches;
+impl Filter<Call> for AtomicLocBatches {
+	fn filter(call: &Call) -> bool {
+		match call {
+			Call::Utility(pallet_utility::Call::batch(calls)) => calls.iter()
+				.all(|call| <LocFeeCalls as pallet_lo_fees::LocFeeCalls<Call, u128, AccountId>>::fee_calls(call).is_empty()),
+			_ => true,
+		}
+	}
+}
+
+/// Assets without zombie slots: only existing accounts hold them and holding an asset keeps the
+/// account alive. Slots of assets created before remain usable until given up with `set_max_zombies`.
+pub struct AccountBasedAssets;
+impl Filter<Call> for AccountBasedAssets {
+	fn filter(call: &Call) -> bool {
+		match call {
+			Call::Assets(pallet_assets::Call::create(_, _, max_zombies, _))
+			| Call::Assets(pallet_assets::Call::force_create(_, _, max_zombies, _))
+			| Call::Assets(pallet_assets::Call::set_max_zombies(_, max_zombies)) => *max_zombies == 0,
+			_ => true,
+		}
+	}
+}
+
+/// Mints and transfers of regulated assets requiring identified recipients.
+pub struct RegulatedTransfers;
+impl Filter<Call> for RegulatedTransfers {
+	fn filter(call: &Call) -> bool {
+		let (id, recipient) = match call {
+			Call::Assets(pallet_assets::Call::mint(id, beneficiary, _)) => (id, beneficiary),
+			Call::Assets(pallet_assets::Call::transfer(id, target, _)) => (id, target),
+			Call::Assets(pallet_assets::Call::force_transfer(id, _, dest, _)) => (id, dest),
+			_ => return true,
+		};
+		match Indices::lookup(recipient.clone()) {
+			Ok(recipient) => RegulatedAssets::may_receive(id, &recipient),
+			// Dispatch fails anyway
+			Err(_) => true,
+		}
+	}
+}
+
+/// Calls which remain available while safe mode is active: governance and validator housekeeping.
+pub struct SafeModeWhitelist;
+impl Filter<Call> for SafeModeWhitelist {
+	fn filter(call: &Call) -> bool {
+		match call {
+			Call::Timestamp(..) => true,
+			Call::Session(..) => true,
+			Call::Grandpa(..) => true,
+			Call::Sudo(..) => true,
+			Call::ValidatorSet(..) => true,
+			Call::NodeAuthorization(..) => true,
+			Call::LoNodeAuthorization(..) => true,
+			Call::CallFilter(..) => true,
+			Call::SafeMode(..) => true,
+			Call::Scheduler(..) => true,
+			Call::ScheduledUpgrades(..) => true,
+			_ => false
 		}
 	}
 }
@@ -177,7 +264,7 @@ impl frame_system::Config for Runtime {
 	/// The aggregated dispatch type that is available for extrinsics.
 	type Call = Call;
 	/// The lookup mechanism to get account ID from whatever is passed in dispatchers.
-	type Lookup = AccountIdLookup<AccountId, ()>;
+	type Lookup = Indices;
 	/// The index type for storing how many extrinsics an account has signed.
 	type Index = Index;
 	/// The index type for blocks.
@@ -249,6 +336,49 @@ impl pallet_timestamp::Config for Runtime {
 	type WeightInfo = ();
 }
 
+/// Declares a `Get` reading a parameter of `RuntimeParameters`, or its default if governance did not
+/// set it.
+macro_rules! tunable_parameter {
+	($name:ident: Balance = $default:expr) => {
+		pub struct $name;
+		impl Get<Balance> for $name {
+			fn get() -> Balance {
+				RuntimeParameters::balance(ParameterKey::$name).unwrap_or($default)
+			}
+		}
+	};
+	($name:ident: usize = $default:expr) => {
+		pub struct $name;
+		impl Get<usize> for $name {
+			fn get() -> usize {
+				RuntimeParameters::size(ParameterKey::$name).map(|size| size as usize).unwrap_or($default)
+			}
+		}
+	};
+}
+
+/// Acceptable values of runtime parameters.
+pub struct RuntimeParameterBounds;
+impl pallet_runtime_parameters::ParameterBounds<Balance> for RuntimeParameterBounds {
+	fn within_bounds(parameter: &Parameter<Balance>) -> bool {
+		match parameter {
+			Parameter::MaxMetadataItemValueSize(size)
+			| Parameter::MaxCollectionItemDescriptionSize(size) => (255..=16_384).contains(size),
+			// Deposits and byte fee never go below their defaults, storage must not become free.
+			Parameter::MultiSigDepositBase(balance) => (500..=100 * LGNT).contains(balance),
+			Parameter::RecoveryDeposit(balance) => (10..=100 * LGNT).contains(balance),
+			Parameter::TransactionByteFee(balance) => (1..=MILLI_LGNT).contains(balance),
+		}
+	}
+}
+
+impl pallet_runtime_parameters::Config for Runtime {
+	type Balance = Balance;
+	type ParameterOrigin = EnsureRoot<AccountId>;
+	type ParameterBounds = RuntimeParameterBounds;
+	type Event = Event;
+}
+
 parameter_types! {
 	pub const ExistentialDeposit: u128 = 500;
 	pub const MaxLocks: u32 = 50;
@@ -267,9 +397,19 @@ impl pallet_balances::Config for Runtime {
 }
 
 parameter_types! {
-	pub const TransactionByteFee: Balance = 1;
+	pub const IndexDeposit: Balance = LGNT;
+}
+
+impl pallet_indices::Config for Runtime {
+	type AccountIndex = AccountIndex;
+	type Currency = Balances;
+	type Deposit = IndexDeposit;
+	type Event = Event;
+	type WeightInfo = ();
 }
 
+tunable_parameter!(TransactionByteFee: Balance = 1);
+
 impl pallet_transaction_payment::Config for Runtime {
 	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
 	type TransactionByteFee = TransactionByteFee;
@@ -282,24 +422,90 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+pub struct LegalOfficers;
+impl pallet_safe_mode::LegalOfficerSet<AccountId> for LegalOfficers {
+	fn is_legal_officer(account: &AccountId) -> bool {
+		pallet_lo_authority_list::LegalOfficerSet::<Runtime>::contains_key(account)
+	}
+
+	fn legal_officers_count() -> u32 {
+		pallet_lo_authority_list::LegalOfficerSet::<Runtime>::iter().count() as u32
+	}
+}
+
+impl pallet_lo_profiles::LegalOfficers<AccountId> for LegalOfficers {
+	fn legal_officers() -> Vec<AccountId> {
+		pallet_lo_authority_list::LegalOfficerSet::<Runtime>::iter().map(|(account, _)| account).collect()
+	}
+}
+
+parameter_types! {
+	pub const SafeModeDefaultDuration: BlockNumber = 4 * HOURS;
+	pub const SafeModeMaxDuration: BlockNumber = 7 * DAYS;
+}
+
+impl pallet_safe_mode::Config for Runtime {
+	type EnterOrigin = EnsureRoot<AccountId>;
+	type ExitOrigin = EnsureRoot<AccountId>;
+	type LegalOfficerSet = LegalOfficers;
+	type DefaultDuration = SafeModeDefaultDuration;
+	type MaxDuration = SafeModeMaxDuration;
+	type Event = Event;
+}
+
+parameter_types! {
+	pub UnpausablePallets: Vec<Vec<u8>> = vec![
+		b"System".to_vec(),
+		b"Timestamp".to_vec(),
+		b"Sudo".to_vec(),
+		b"CallFilter".to_vec(),
+		b"SafeMode".to_vec(),
+	];
+	pub const MaxCallFilterNameLength: u32 = 64;
+}
+
+impl pallet_call_filter::Config for Runtime {
+	type Call = Call;
+	type PauseOrigin = EnsureRoot<AccountId>;
+	type UnpausablePallets = UnpausablePallets;
+	type MaxNameLength = MaxCallFilterNameLength;
+	type Event = Event;
+}
+
 parameter_types! {
     pub const MaxWellKnownNodes: u32 = 100;
     pub const MaxPeerIdLength: u32 = 128;
 }
 
+/// Node removals checked by `LoNodeAuthorization`, or made by root in an emergency.
+type NodeManagerOrRoot = EnsureOneOf<AccountId, EnsureRoot<AccountId>, pallet_lo_node_authorization::EnsureNodeManager>;
+
 impl pallet_node_authorization::Config for Runtime {
     type Event = Event;
     type MaxWellKnownNodes = MaxWellKnownNodes;
     type MaxPeerIdLength = MaxPeerIdLength;
-    type AddOrigin = EnsureRoot<AccountId>;
-    type RemoveOrigin = EnsureRoot<AccountId>;
-    type SwapOrigin = EnsureRoot<AccountId>;
-    type ResetOrigin = EnsureRoot<AccountId>;
+    type AddOrigin = pallet_lo_node_authorization::EnsureNodeManager;
+    type RemoveOrigin = NodeManagerOrRoot;
+    type SwapOrigin = pallet_lo_node_authorization::EnsureNodeManager;
+    type ResetOrigin = pallet_lo_node_authorization::EnsureNodeManager;
     type WeightInfo = ();
 }
 
 parameter_types! {
-	pub const MultiSigDepositBase: Balance = 500;
+	pub const MaxNodesPerLegalOfficer: u32 = 2;
+}
+
+impl pallet_lo_node_authorization::Config for Runtime {
+	type LegalOfficerOrigin = LoAuthorityList;
+	type RevokeOrigin = EnsureRoot<AccountId>;
+	type MaxNodesPerLegalOfficer = MaxNodesPerLegalOfficer;
+	type NodeOrigin = Origin;
+	type Event = Event;
+}
+
+tunable_parameter!(MultiSigDepositBase: Balance = 500);
+
+parameter_types! {
 	pub const MultiSigDepositFactor: Balance = 100;
 	pub const MaxSignatories: u16 = 20;
 }
@@ -314,13 +520,49 @@ impl pallet_multisig::Config for Runtime {
 	type WeightInfo = ();
 }
 
+impl pallet_utility::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
+	pub const MaxScheduledPerBlock: u32 = 50;
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinUpgradeNotice: BlockNumber = DAYS;
+}
+
+impl pallet_scheduled_upgrades::Config for Runtime {
+	type Call = Call;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type UpgradeOrigin = EnsureRoot<AccountId>;
+	type MinUpgradeNotice = MinUpgradeNotice;
+	type Event = Event;
+}
+
 parameter_types! {
 	pub const RecoveryConfigDepositBase: u64 = 10;
 	pub const RecoveryFrieldDepositFactor: u64 = 1;
 	pub const MaxFriends: u16 = 3;
-	pub const RecoveryDeposit: u64 = 10;
 }
 
+tunable_parameter!(RecoveryDeposit: Balance = 10);
+
 impl pallet_recovery::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
@@ -331,12 +573,13 @@ impl pallet_recovery::Config for Runtime {
 	type RecoveryDeposit = RecoveryDeposit;
 }
 
+// No zombie slot is allowed (see AccountBasedAssets), AssetDepositPerZombie only applies to assets created before.
 parameter_types! {
-	pub const AssetDepositBase: u64 = 1;
-	pub const AssetDepositPerZombie: u64 = 1;
-	pub const StringLimit: u32 = 50;
-	pub const MetadataDepositBase: u64 = 1;
-	pub const MetadataDepositPerByte: u64 = 1;
+	pub const AssetDepositBase: Balance = 100 * LGNT;
+	pub const AssetDepositPerZombie: Balance = 10 * MILLI_LGNT;
+	pub const StringLimit: u32 = 64;
+	pub const MetadataDepositBase: Balance = 10 * LGNT;
+	pub const MetadataDepositPerByte: Balance = 100 * MILLI_LGNT;
 }
 
 impl pallet_assets::Config for Runtime {
@@ -353,6 +596,29 @@ impl pallet_assets::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const AssetFeeCollectorId: ModuleId = ModuleId(*b"lg/asfee");
+	pub AssetFeeCollector: AccountId = AssetFeeCollectorId::get().into_account();
+}
+
+impl pallet_asset_fee_payment::Config for Runtime {
+	type RateOrigin = EnsureRoot<AccountId>;
+	type FeeCollector = AssetFeeCollector;
+	type AssetRecipients = RegulatedAssets;
+	type Event = Event;
+}
+
+parameter_types! {
+	pub const MaxRequiredLegalOfficers: u32 = 5;
+}
+
+impl pallet_regulated_assets::Config for Runtime {
+	type CreateOrigin = LoAuthorityList;
+	type LocQuery = LogionLoc;
+	type MaxRequiredLegalOfficers = MaxRequiredLegalOfficers;
+	type Event = Event;
+}
+
 impl pallet_lo_authority_list::Config for Runtime {
 	type AddOrigin = EnsureRoot<AccountId>;
     type RemoveOrigin = EnsureRoot<AccountId>;
@@ -361,12 +627,13 @@ impl pallet_lo_authority_list::Config for Runtime {
 
 parameter_types! {
 	pub const MaxMetadataItemNameSize: usize = 255;
-	pub const MaxMetadataItemValueSize: usize = 4096;
 	pub const MaxFileNatureSize: usize = 255;
 	pub const MaxLinkNatureSize: usize = 255;
-	pub const MaxCollectionItemDescriptionSize: usize = 4096;
 }
 
+tunable_parameter!(MaxMetadataItemValueSize: usize = 4096);
+tunable_parameter!(MaxCollectionItemDescriptionSize: usize = 4096);
+
 impl pallet_logion_loc::Config for Runtime {
 	type LocId = u128;
 	type Event = Event;
@@ -381,6 +648,14 @@ impl pallet_logion_loc::Config for Runtime {
 	type WeightInfo = ();
 }
 
+impl pallet_logion_loc_genesis::Config for Runtime {
+	type GenesisLocId = u128;
+	type GenesisHash = Hash;
+}
+
+#[cfg(feature = "dev-fixtures")]
+impl pallet_dev_fixtures::Config for Runtime {}
+
 pub struct PalletRecoveryCreateRecoveryCallFactory;
 impl CreateRecoveryCallFactory<Origin, AccountId, BlockNumber> for PalletRecoveryCreateRecoveryCallFactory {
 	type Call = Call;
@@ -454,7 +729,171 @@ impl pallet_logion_vault::Config for Runtime {
 	type WeightInfo = ();
 }
 
+pub struct SessionValidators;
+impl pallet_logion_randomness::IsValidator<AccountId> for SessionValidators {
+	fn is_validator(account: &AccountId) -> bool {
+		Session::validators().contains(account)
+	}
+}
+
+parameter_types! {
+	pub const RandomnessRoundLength: BlockNumber = 10 * MINUTES;
+}
+
+impl pallet_logion_randomness::Config for Runtime {
+	type IsValidator = SessionValidators;
+	type RoundLength = RandomnessRoundLength;
+	type FallbackRandomness = RandomnessCollectiveFlip;
+	type Event = Event;
+}
+
+parameter_types! {
+	pub const IdentityBasicDeposit: Balance = 10 * LGNT;
+	pub const IdentityFieldDeposit: Balance = 250 * MILLI_LGNT;
+	pub const IdentitySubAccountDeposit: Balance = 2 * LGNT;
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+}
+
+impl pallet_identity::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BasicDeposit = IdentityBasicDeposit;
+	type FieldDeposit = IdentityFieldDeposit;
+	type SubAccountDeposit = IdentitySubAccountDeposit;
+	type MaxSubAccounts = MaxSubAccounts;
+	type MaxAdditionalFields = MaxAdditionalFields;
+	type MaxRegistrars = MaxRegistrars;
+	type Slashed = ();
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type RegistrarOrigin = pallet_lo_identity::EnsureRegistrarManager;
+	type WeightInfo = ();
+}
+
+impl pallet_lo_identity::Config for Runtime {
+	type LegalOfficerOrigin = LoAuthorityList;
+	type RegistrarOrigin = Origin;
+	type Event = Event;
+}
+
+parameter_types! {
+	pub const EscrowModuleId: ModuleId = ModuleId(*b"lg/escrw");
+	pub const MaxEscrowTimeout: BlockNumber = 365 * DAYS;
+}
+
+impl pallet_loc_escrow::Config for Runtime {
+	type NativeCurrency = Balances;
+	type AssetRecipients = RegulatedAssets;
+	type EscrowModuleId = EscrowModuleId;
+	type MaxTimeout = MaxEscrowTimeout;
+	type Event = Event;
+}
+
+/// Fee-bearing operations of `LogionLoc` calls, including calls nested in `Utility` calls. LOCs
+/// requested by another LOC are free.
+pub struct LocFeeCalls;
+impl pallet_lo_fees::LocFeeCalls<Call, u128, AccountId> for LocFeeCalls {
+	fn fee_calls(call: &Call) -> Vec<pallet_lo_fees::LocFeeCall<u128, AccountId>> {
+		use pallet_lo_fees::LocFeeCall::*;
+		use pallet_logion_loc::Call as LocCall;
+		match call {
+			Call::Utility(pallet_utility::Call::batch(calls))
+			| Call::Utility(pallet_utility::Call::batch_all(calls)) =>
+				calls.iter().flat_map(Self::fee_calls).collect(),
+			Call::Utility(pallet_utility::Call::as_derivative(_, call)) => Self::fee_calls(call),
+			Call::LogionLoc(LocCall::create_polkadot_transaction_loc(loc_id, requester))
+			| Call::LogionLoc(LocCall::create_polkadot_identity_loc(loc_id, requester))
+			| Call::LogionLoc(LocCall::create_collection_loc(loc_id, requester, ..)) =>
+				vec![Open { loc_id: *loc_id, requester: requester.clone() }],
+			Call::LogionLoc(LocCall::add_metadata(loc_id, ..))
+			| Call::LogionLoc(LocCall::add_file(loc_id, ..))
+			| Call::LogionLoc(LocCall::add_link(loc_id, ..))
+			| Call::LogionLoc(LocCall::add_collection_item(loc_id, ..)) =>
+				vec![AddItems { loc_id: *loc_id, count: 1 }],
+			Call::LogionLoc(LocCall::close(loc_id)) => vec![Close { loc_id: *loc_id }],
+			_ => Vec::new(),
+		}
+	}
+}
+
+parameter_types! {
+	pub const MaxLocFee: Balance = 1_000 * LGNT;
+}
+
+impl pallet_lo_fees::Config for Runtime {
+	type Currency = Balances;
+	type LegalOfficerOrigin = LoAuthorityList;
+	type LocFeeCalls = LocFeeCalls;
+	type MaxFee = MaxLocFee;
+	type Event = Event;
+}
+
+/// Items added to LOCs by `LogionLoc` calls, including calls nested in `Utility` calls, threshold 1
+/// multisig calls and recovered account calls. Deposits are paid by the account dispatching the
+/// call, i.e. the signer, the derivative, the multisig or the recovered account, whatever submitter
+/// an item declares.
+pub struct LocDepositCalls;
+impl pallet_loc_deposits::LocDepositCalls<Call, u128, AccountId> for LocDepositCalls {
+	fn added_items(who: &AccountId, call: &Call) -> Vec<pallet_loc_deposits::AddedItem<u128, AccountId>> {
+		use pallet_loc_deposits::AddedItem;
+		use pallet_logion_loc::Call as LocCall;
+		let (loc_id, size) = match call {
+			Call::Utility(pallet_utility::Call::batch(calls))
+			| Call::Utility(pallet_utility::Call::batch_all(calls)) =>
+				return calls.iter().flat_map(|call| Self::added_items(who, call)).collect(),
+			Call::Utility(pallet_utility::Call::as_derivative(index, call)) =>
+				return Self::added_items(&Utility::derivative_account_id(who.clone(), *index), call),
+			Call::Multisig(pallet_multisig::Call::as_multi_threshold_1(other_signatories, call)) => {
+				let mut signatories = other_signatories.clone();
+				signatories.push(who.clone());
+				signatories.sort();
+				return Self::added_items(&Multisig::multi_account_id(&signatories, 1), call);
+			},
+			Call::Recovery(pallet_recovery::Call::as_recovered(account, call)) =>
+				return Self::added_items(account, call),
+			Call::LogionLoc(LocCall::add_metadata(loc_id, item)) => (loc_id, item.encoded_size()),
+			Call::LogionLoc(LocCall::add_file(loc_id, file)) => (loc_id, file.encoded_size()),
+			Call::LogionLoc(LocCall::add_link(loc_id, link)) => (loc_id, link.encoded_size()),
+			Call::LogionLoc(LocCall::add_collection_item(loc_id, item_id, description)) =>
+				(loc_id, item_id.encoded_size() + description.encoded_size()),
+			_ => return Vec::new(),
+		};
+		vec![AddedItem { loc_id: *loc_id, submitter: who.clone(), size: size as u32 }]
+	}
+}
+
+parameter_types! {
+	pub const LocItemDepositBase: Balance = 10 * MILLI_LGNT;
+	pub const LocDepositPerByte: Balance = MILLI_LGNT;
+	pub const LocDepositsMigrationBatchSize: u32 = 50;
+}
+
+impl pallet_loc_deposits::Config for Runtime {
+	type Currency = Balances;
+	type DepositBase = LocItemDepositBase;
+	type DepositPerByte = LocDepositPerByte;
+	type LocDepositCalls = LocDepositCalls;
+	type MigrationBatchSize = LocDepositsMigrationBatchSize;
+	type Event = Event;
+}
+
+parameter_types! {
+	pub const MaxProfileFieldSize: u32 = 255;
+}
+
+impl pallet_lo_profiles::Config for Runtime {
+	type LegalOfficerOrigin = LoAuthorityList;
+	type LegalOfficers = LegalOfficers;
+	type MaxProfileFieldSize = MaxProfileFieldSize;
+	type Event = Event;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
+/// Builds the runtime, development pallets being given last so that they do not change the index
+/// of other pallets.
+macro_rules! logion_runtime {
+	($($dev_pallets:tt)*) => {
 construct_runtime!(
 	pub enum Runtime where
 		Block = Block,
@@ -479,11 +918,40 @@ construct_runtime!(
 		LogionLoc: pallet_logion_loc::{Module, Call, Storage, Event<T>},
 		VerifiedRecovery: pallet_verified_recovery::{Module, Call, Event<T>},
 		Vault: pallet_logion_vault::{Module, Call, Event<T>},
+		CallFilter: pallet_call_filter::{Module, Call, Storage, Event<T>},
+		SafeMode: pallet_safe_mode::{Module, Call, Storage, Event<T>},
+		AssetFeePayment: pallet_asset_fee_payment::{Module, Call, Storage, Event<T>},
+		RegulatedAssets: pallet_regulated_assets::{Module, Call, Storage, Event<T>},
+		LogionLocGenesis: pallet_logion_loc_genesis::{Module, Config<T>},
+		LoNodeAuthorization: pallet_lo_node_authorization::{Module, Call, Storage, Event<T>, Config, Origin},
+		LoProfiles: pallet_lo_profiles::{Module, Call, Storage, Event<T>, Config<T>},
+		Indices: pallet_indices::{Module, Call, Storage, Event<T>, Config<T>},
+		Identity: pallet_identity::{Module, Call, Storage, Event<T>},
+		LoIdentity: pallet_lo_identity::{Module, Call, Storage, Event<T>, Config<T>, Origin},
+		LocEscrow: pallet_loc_escrow::{Module, Call, Storage, Event<T>},
+		LoFees: pallet_lo_fees::{Module, Call, Storage, Event<T>},
+		LocDeposits: pallet_loc_deposits::{Module, Call, Storage, Event<T>, Config},
+		RuntimeParameters: pallet_runtime_parameters::{Module, Call, Storage, Event<T>},
+		Utility: pallet_utility::{Module, Call, Event},
+		Scheduler: pallet_scheduler::{Module, Call, Storage, Event<T>},
+		ScheduledUpgrades: pallet_scheduled_upgrades::{Module, Call, Storage, Event<T>},
+		LogionRandomness: pallet_logion_randomness::{Module, Call, Storage, Event<T>},
+		$($dev_pallets)*
 	}
 );
+	};
+}
+
+#[cfg(not(feature = "dev-fixtures"))]
+logion_runtime!();
+
+#[cfg(feature = "dev-fixtures")]
+logion_runtime!(
+	DevFixtures: pallet_dev_fixtures::{Module, Config<T>},
+);
 
 /// The address format for describing accounts.
-pub type Address = sp_runtime::MultiAddress<AccountId, ()>;
+pub type Address = sp_runtime::MultiAddress<AccountId, AccountIndex>;
 /// Block header type as expected by this runtime.
 pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
 /// Block type as expected by this runtime.
@@ -500,12 +968,16 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>
+	pallet_asset_fee_payment::ChargeAssetTxPayment<Runtime>,
+	pallet_lo_fees::ChargeLocFees<Runtime>,
+	pallet_loc_deposits::ReserveLocDeposits<Runtime>
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
+/// The payload being signed in transactions.
+pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -526,6 +998,11 @@ mod migration {
 	impl OnRuntimeUpgrade for Upgrade {
 		fn on_runtime_upgrade() -> Weight {
 			pallet_logion_loc::migrate::<Runtime>()
+				.saturating_add(pallet_lo_profiles::migrate::<Runtime>())
+				.saturating_add(pallet_lo_identity::migrate::<Runtime>(
+					<LegalOfficers as pallet_lo_profiles::LegalOfficers<AccountId>>::legal_officers()))
+				.saturating_add(pallet_loc_deposits::migrate::<Runtime>())
+				.saturating_add(pallet_lo_node_authorization::migrate::<Runtime>())
 		}
 	}
 }
@@ -572,7 +1049,7 @@ impl_runtime_apis! {
 		}
 
 		fn random_seed() -> <Block as BlockT>::Hash {
-			RandomnessCollectiveFlip::random_seed()
+			LogionRandomness::random_seed()
 		}
 	}
 
@@ -660,6 +1137,42 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_asset_fee_payment_runtime_api::AssetFeePaymentApi<Block, u64, Balance> for Runtime {
+		fn query_asset_fee(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			asset_id: u64,
+		) -> Option<Balance> {
+			let native_fee = TransactionPayment::compute_fee(len, &uxt.get_dispatch_info(), 0);
+			AssetFeePayment::asset_fee(asset_id, native_fee)
+		}
+	}
+
+	impl pallet_logion_randomness_runtime_api::RandomnessApi<Block, Hash, BlockNumber, AccountId> for Runtime {
+		fn random(subject: Vec<u8>) -> Option<pallet_logion_randomness_runtime_api::RandomOutput<Hash, BlockNumber, AccountId>> {
+			LogionRandomness::random_with_provenance(&subject)
+		}
+	}
+
+	impl pallet_lo_fees_runtime_api::LocFeesApi<Block, AccountId, Balance> for Runtime {
+		fn fee_schedule(legal_officer: AccountId) -> pallet_lo_fees_runtime_api::FeeSchedule<Balance> {
+			LoFees::fee_schedule(legal_officer)
+		}
+
+		fn query_loc_fee(uxt: <Block as BlockT>::Extrinsic) -> Option<Balance> {
+			let (address, _, _) = uxt.signature.as_ref()?;
+			let signer = Indices::lookup(address.clone()).ok()?;
+			Some(LoFees::loc_fees(&signer, &uxt.function).iter()
+				.fold(0, |total, fee| total.saturating_add(fee.amount)))
+		}
+	}
+
+	impl pallet_lo_profiles_runtime_api::LegalOfficerProfilesApi<Block, AccountId> for Runtime {
+		fn legal_officers() -> Vec<(AccountId, pallet_lo_profiles_runtime_api::LegalOfficerProfile)> {
+			LoProfiles::legal_officers()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -696,3 +1209,6 @@ impl_runtime_apis! {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests;
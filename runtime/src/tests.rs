@@ -0,0 +1,196 @@
+//! Calls nested in `Utility` calls go through `BaseCallFilter`, which also restricts calls while
+//! safe mode is active.
+
+use super::*;
+use sp_runtime::{DispatchError, traits::Dispatchable};
+
+const ALICE: [u8; 32] = [1u8; 32];
+const BOB: [u8; 32] = [2u8; 32];
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn alice() -> Origin {
+	Origin::signed(AccountId::from(ALICE))
+}
+
+fn remark() -> Call {
+	Call::System(frame_system::Call::remark(Vec::new()))
+}
+
+fn create_recovery() -> Call {
+	Call::Recovery(pallet_recovery::Call::create_recovery(vec![AccountId::from(BOB)], 1, 10))
+}
+
+fn approve_as_multi() -> Call {
+	Call::Multisig(pallet_multisig::Call::approve_as_multi(2, vec![AccountId::from(BOB)], None, [0u8; 32], 0))
+}
+
+fn as_multi() -> Call {
+	Call::Multisig(pallet_multisig::Call::as_multi(2, vec![AccountId::from(BOB)], None, remark().encode(), false, 0))
+}
+
+fn batch(calls: Vec<Call>) -> Call {
+	Call::Utility(pallet_utility::Call::batch(calls))
+}
+
+fn batch_all(calls: Vec<Call>) -> Call {
+	Call::Utility(pallet_utility::Call::batch_all(calls))
+}
+
+fn as_derivative(call: Call) -> Call {
+	Call::Utility(pallet_utility::Call::as_derivative(0, Box::new(call)))
+}
+
+fn last_event() -> Event {
+	System::events().pop().expect("Event expected").event
+}
+
+fn batch_interrupted(index: u32) -> Event {
+	Event::pallet_utility(pallet_utility::Event::BatchInterrupted(index, DispatchError::BadOrigin))
+}
+
+#[test]
+fn batch_dispatches_allowed_calls() {
+	new_test_ext().execute_with(|| {
+		assert!(batch(vec![remark(), remark()]).dispatch(alice()).is_ok());
+		assert_eq!(last_event(), Event::pallet_utility(pallet_utility::Event::BatchCompleted));
+	});
+}
+
+#[test]
+fn batch_does_not_bypass_filter() {
+	new_test_ext().execute_with(|| {
+		assert!(batch(vec![remark(), create_recovery()]).dispatch(alice()).is_ok());
+		assert_eq!(last_event(), batch_interrupted(1));
+
+		assert!(batch(vec![as_multi()]).dispatch(alice()).is_ok());
+		assert_eq!(last_event(), batch_interrupted(0));
+	});
+}
+
+#[test]
+fn batch_all_does_not_bypass_filter() {
+	new_test_ext().execute_with(|| {
+		let result = batch_all(vec![remark(), as_multi()]).dispatch(alice());
+		assert_eq!(result.map_err(|e| e.error), Err(DispatchError::BadOrigin));
+
+		let result = batch_all(vec![approve_as_multi()]).dispatch(alice());
+		assert_eq!(result.map_err(|e| e.error), Err(DispatchError::BadOrigin));
+	});
+}
+
+#[test]
+fn as_derivative_does_not_bypass_filter() {
+	new_test_ext().execute_with(|| {
+		let result = as_derivative(create_recovery()).dispatch(alice());
+		assert_eq!(result.map_err(|e| e.error), Err(DispatchError::BadOrigin));
+	});
+}
+
+#[test]
+fn nesting_does_not_bypass_filter() {
+	new_test_ext().execute_with(|| {
+		let result = batch_all(vec![batch_all(vec![as_derivative(as_multi())])]).dispatch(alice());
+		assert_eq!(result.map_err(|e| e.error), Err(DispatchError::BadOrigin));
+
+		let result = as_derivative(batch_all(vec![create_recovery()])).dispatch(alice());
+		assert_eq!(result.map_err(|e| e.error), Err(DispatchError::BadOrigin));
+
+		assert!(batch(vec![batch(vec![remark(), as_derivative(approve_as_multi())])]).dispatch(alice()).is_ok());
+		assert!(System::events().iter().any(|record| record.event == batch_interrupted(1)));
+	});
+}
+
+#[test]
+fn charged_loc_calls_are_only_batched_atomically() {
+	new_test_ext().execute_with(|| {
+		let close = Call::LogionLoc(pallet_logion_loc::Call::close(0));
+		assert!(!BaseCallFilter::filter(&batch(vec![remark(), close.clone()])));
+		assert!(!BaseCallFilter::filter(&batch(vec![batch_all(vec![close.clone()])])));
+		assert!(BaseCallFilter::filter(&batch_all(vec![remark(), close])));
+	});
+}
+
+fn enter_safe_mode() {
+	assert!(SafeMode::force_enter(Origin::root(), None).is_ok());
+}
+
+#[test]
+fn safe_mode_rejects_calls_out_of_whitelist() {
+	new_test_ext().execute_with(|| {
+		enter_safe_mode();
+		assert!(!BaseCallFilter::filter(&remark()));
+		assert!(!BaseCallFilter::filter(&batch_all(vec![remark()])));
+
+		let result = remark().dispatch(alice());
+		assert_eq!(result.map_err(|e| e.error), Err(DispatchError::BadOrigin));
+	});
+}
+
+#[test]
+fn safe_mode_lets_whitelisted_calls_through() {
+	new_test_ext().execute_with(|| {
+		enter_safe_mode();
+		assert!(BaseCallFilter::filter(&Call::Timestamp(pallet_timestamp::Call::set(0))));
+		assert!(BaseCallFilter::filter(&Call::SafeMode(pallet_safe_mode::Call::force_exit())));
+		assert!(BaseCallFilter::filter(&Call::Sudo(pallet_sudo::Call::sudo(Box::new(remark())))));
+	});
+}
+
+#[test]
+fn safe_mode_keeps_safety_defaults() {
+	new_test_ext().execute_with(|| {
+		enter_safe_mode();
+		let claim_node = Call::NodeAuthorization(pallet_node_authorization::Call::claim_node(Vec::new()));
+		assert!(!BaseCallFilter::filter(&claim_node));
+	});
+}
+
+#[test]
+fn calls_are_allowed_again_after_safe_mode() {
+	new_test_ext().execute_with(|| {
+		enter_safe_mode();
+		assert!(SafeMode::force_exit(Origin::root()).is_ok());
+		assert!(BaseCallFilter::filter(&remark()));
+		assert!(remark().dispatch(alice()).is_ok());
+	});
+}
+
+#[test]
+fn paused_calls_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let close = Call::LogionLoc(pallet_logion_loc::Call::close(0));
+		assert!(CallFilter::pause_call(Origin::root(), b"LogionLoc".to_vec(), b"close".to_vec()).is_ok());
+		assert!(!BaseCallFilter::filter(&close));
+
+		assert!(CallFilter::unpause_call(Origin::root(), b"LogionLoc".to_vec(), b"close".to_vec()).is_ok());
+		assert!(BaseCallFilter::filter(&close));
+	});
+}
+
+#[test]
+fn unpausable_pallets_cannot_be_paused() {
+	new_test_ext().execute_with(|| {
+		for pallet in UnpausablePallets::get() {
+			assert!(CallFilter::pause_pallet(Origin::root(), pallet).is_err());
+		}
+	});
+}
+
+#[test]
+fn assets_have_no_zombie_slots() {
+	new_test_ext().execute_with(|| {
+		let owner = || AccountId::from(ALICE).into();
+		assert!(!BaseCallFilter::filter(&Call::Assets(pallet_assets::Call::create(1, owner(), 10, 1))));
+		assert!(!BaseCallFilter::filter(&Call::Assets(pallet_assets::Call::force_create(1, owner(), 10, 1))));
+		assert!(!BaseCallFilter::filter(&Call::Assets(pallet_assets::Call::set_max_zombies(1, 10))));
+
+		assert!(BaseCallFilter::filter(&Call::Assets(pallet_assets::Call::create(1, owner(), 0, 1))));
+		assert!(BaseCallFilter::filter(&Call::Assets(pallet_assets::Call::set_max_zombies(1, 0))));
+	});
+}